@@ -29,6 +29,7 @@ pub(crate) fn generate_class_struct(class: &GodotClass, class_doc: TokenStream)
 }
 
 pub(crate) fn generate_class_impl(
+    api: &Api,
     class: &GodotClass,
     icalls: &mut HashMap<String, methods::MethodSig>,
     docs: Option<&GodotXmlDocs>,
@@ -51,7 +52,7 @@ pub(crate) fn generate_class_impl(
         Default::default()
     };
 
-    let class_methods = methods::generate_methods(class, icalls, docs);
+    let class_methods = methods::generate_methods(api, class, icalls, docs);
 
     let class_name = format_ident!("{}", class.name);
     quote! {
@@ -109,7 +109,7 @@ fn generate_class_bindings(
             Default::default()
         };
 
-        let class_impl = generate_class_impl(class, icalls, docs);
+        let class_impl = generate_class_impl(api, class, icalls, docs);
 
         quote! {
             #module_doc
@@ -251,7 +251,7 @@ pub(crate) mod test_prelude {
                 validate_and_clear_buffer!(buffer);
             }
 
-            let code = generate_class_impl(&class, &mut icalls, None);
+            let code = generate_class_impl(&api, &class, &mut icalls, None);
             write!(buffer, "{}", code).unwrap();
             validate_and_clear_buffer!(buffer);
 
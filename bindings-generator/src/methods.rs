@@ -275,6 +275,7 @@ const UNSAFE_OBJECT_METHODS: &[(&str, &str)] = &[
 ];
 
 pub(crate) fn generate_methods(
+    api: &Api,
     class: &GodotClass,
     icalls: &mut HashMap<String, MethodSig>,
     docs: Option<&GodotXmlDocs>,
@@ -312,6 +313,9 @@ pub(crate) fn generate_methods(
     let mut generated = HashMap::new();
     let mut result = TokenStream::new();
 
+    // Nodes may be inside the scene tree, in which case they must only be used from the main thread.
+    let is_node = class.name == "Node" || api.class_inherits(class, "Node");
+
     for method in &class.methods {
         let MethodName {
             rust_name: method_name,
@@ -377,6 +381,8 @@ pub(crate) fn generate_methods(
             maybe_unsafe_reason = "";
         }
 
+        let (check_attrs, checks) = access_checks(is_node, !maybe_unsafe.is_empty());
+
         icalls.insert(icall_name.clone(), method_sig);
 
         let rusty_name = rust_safe_name(rusty_method_name);
@@ -400,7 +406,10 @@ pub(crate) fn generate_methods(
             #[doc = #doc_comment]
             #[doc = #maybe_unsafe_reason]
             #[inline]
+            #check_attrs
             pub #maybe_unsafe fn #rusty_name(&self #params_decl) -> #rust_ret_type {
+                #checks
+
                 unsafe {
                     #method_bind_fetch
 
@@ -439,6 +448,8 @@ pub(crate) fn generate_methods(
             maybe_unsafe_reason,
         }) = generated.get(&property.getter)
         {
+            let (check_attrs, checks) = access_checks(is_node, !maybe_unsafe.is_empty());
+
             let rusty_name = rust_safe_name(&property.name);
             let rust_ret_type = ty.to_rust();
 
@@ -466,7 +477,10 @@ pub(crate) fn generate_methods(
                 #[doc = #doc_comment]
                 #[doc = #maybe_unsafe_reason]
                 #[inline]
+                #check_attrs
                 pub #maybe_unsafe fn #rusty_name(&self) -> #rust_ret_type {
+                    #checks
+
                     unsafe {
                         #method_bind_fetch
 
@@ -487,6 +501,8 @@ pub(crate) fn generate_methods(
             maybe_unsafe_reason,
         }) = generated.get(&property.setter)
         {
+            let (check_attrs, checks) = access_checks(is_node, !maybe_unsafe.is_empty());
+
             let rusty_name = rust_safe_name(&format!("set_{}", property.name));
 
             let rust_arg_ty = ty.to_rust_arg();
@@ -517,7 +533,10 @@ pub(crate) fn generate_methods(
                 #[doc = #doc_comment]
                 #[doc = #maybe_unsafe_reason]
                 #[inline]
+                #check_attrs
                 pub #maybe_unsafe fn #rusty_name(&self, #arg_ident: #rust_arg_ty) {
+                    #checks
+
                     unsafe {
                         #method_bind_fetch
 
@@ -556,6 +575,41 @@ fn unsafe_reason(
     }
 }
 
+/// Returns the attributes and statements for the debug checks of a generated method, which are
+/// enabled by the `checked-refs` and `main-thread-checks` features of `gdnative-bindings`.
+fn access_checks(is_node: bool, is_unsafe: bool) -> (TokenStream, TokenStream) {
+    let mut features = Vec::new();
+    let mut checks = TokenStream::new();
+
+    if is_unsafe {
+        features.push("checked-refs");
+        checks.extend(quote! {
+            #[cfg(feature = "checked-refs")]
+            unsafe {
+                gdnative_core::object::bounds::check_object_access(&self.this);
+            }
+        });
+    }
+
+    if is_node {
+        features.push("main-thread-checks");
+        checks.extend(quote! {
+            #[cfg(feature = "main-thread-checks")]
+            unsafe {
+                gdnative_core::object::bounds::check_main_thread_affinity(&self.this);
+            }
+        });
+    }
+
+    let attrs = if features.is_empty() {
+        TokenStream::new()
+    } else {
+        quote! { #[cfg_attr(any(#(feature = #features),*), track_caller)] }
+    };
+
+    (attrs, checks)
+}
+
 fn ret_recover(ty: &Ty, icall_ty: IcallType) -> TokenStream {
     match icall_ty {
         #[cfg(feature = "ptrcall")]
@@ -0,0 +1,143 @@
+//! Queue of closures run on the main thread.
+
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+use gdnative_bindings::Reference;
+use gdnative_core::core_types::Variant;
+use gdnative_core::export::user_data::ArcData;
+use gdnative_core::export::{ClassBuilder, Method, NativeClass, NativeClassMethods, Varargs};
+use gdnative_core::godot_site;
+use gdnative_core::object::{GodotObject, Instance, TInstance, TRef, WeakRef};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+static QUEUE: Lazy<Mutex<Vec<Job>>> = Lazy::new(Mutex::default);
+static SCHEDULED: AtomicBool = AtomicBool::new(false);
+static PUMP: Lazy<Mutex<Option<Instance<DispatchPump>>>> = Lazy::new(Mutex::default);
+
+/// Queues `f` to be run on the main thread. This can be called from any thread.
+///
+/// Queued closures are run in order at the end of the current or next frame, when the engine
+/// flushes deferred calls, like with `Object::call_deferred`.
+///
+/// ```ignore
+/// std::thread::spawn(move || {
+///     let path = find_path(from, to);
+///     gdnative::tasks::run_on_main_thread(move || apply_path(path));
+/// });
+/// ```
+#[inline]
+pub fn run_on_main_thread<F>(f: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    QUEUE.lock().push(Box::new(f));
+
+    if !SCHEDULED.swap(true, Ordering::AcqRel) {
+        schedule();
+    }
+}
+
+/// Queues `f` to be run on the main thread with a reference to `obj`, if it is still alive by
+/// then. See [`run_on_main_thread`].
+#[inline]
+pub fn defer_for<T, F>(obj: WeakRef<T>, f: F)
+where
+    T: GodotObject,
+    F: FnOnce(TRef<'_, T>) + Send + 'static,
+{
+    run_on_main_thread(move || {
+        if let Some(obj) = obj.upgrade() {
+            f(obj.get());
+        }
+    });
+}
+
+/// Extension trait for deferring closures on objects. See [`run_on_main_thread`].
+pub trait DeferExt: GodotObject {
+    /// Queues `f` to be run on the main thread with a reference to this object. `f` is skipped
+    /// if the object has been freed by then.
+    ///
+    /// ```ignore
+    /// owner.defer(|owner| owner.set_position(Vector2::ZERO));
+    /// ```
+    fn defer<F>(&self, f: F)
+    where
+        F: FnOnce(TRef<'_, Self>) + Send + 'static;
+}
+
+impl<T: GodotObject> DeferExt for T {
+    #[inline]
+    fn defer<F>(&self, f: F)
+    where
+        F: FnOnce(TRef<'_, Self>) + Send + 'static,
+    {
+        defer_for(WeakRef::new(self), f);
+    }
+}
+
+/// Schedules a call to `DispatchPump::_drain` with `call_deferred`, which is thread-safe.
+fn schedule() {
+    let mut pump = PUMP.lock();
+    let pump = pump.get_or_insert_with(|| Instance::emplace(DispatchPump).into_shared());
+
+    // SAFETY: `call_deferred` is thread-safe, and the pump object has no other state.
+    unsafe {
+        pump.base().assume_safe().call_deferred("_drain", &[]);
+    }
+}
+
+/// Runs all queued closures.
+fn drain() {
+    // Closures queued from now on need another deferred call.
+    SCHEDULED.store(false, Ordering::Release);
+
+    let jobs = std::mem::take(&mut *QUEUE.lock());
+    for job in jobs {
+        // Panics are reported, without discarding the closures queued after the panicking one.
+        gdnative_core::private::report_panics("main-thread closure", AssertUnwindSafe(job));
+    }
+}
+
+/// Drops queued closures and the pump object.
+pub(crate) fn terminate() {
+    let jobs = std::mem::take(&mut *QUEUE.lock());
+    drop(jobs);
+
+    *PUMP.lock() = None;
+    SCHEDULED.store(false, Ordering::Release);
+}
+
+/// Object whose deferred calls drain the queue.
+pub(crate) struct DispatchPump;
+
+impl NativeClass for DispatchPump {
+    type Base = Reference;
+    type UserData = ArcData<DispatchPump>;
+
+    fn nativeclass_register_properties(_builder: &ClassBuilder<Self>) {}
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct DrainFn;
+
+impl Method<DispatchPump> for DrainFn {
+    fn call(&self, _this: TInstance<'_, DispatchPump>, _args: Varargs<'_>) -> Variant {
+        drain();
+        Variant::nil()
+    }
+
+    fn site() -> Option<gdnative_core::log::Site<'static>> {
+        Some(godot_site!(DispatchPump::_drain))
+    }
+}
+
+impl NativeClassMethods for DispatchPump {
+    fn nativeclass_register(builder: &ClassBuilder<Self>) {
+        builder.method("_drain", DrainFn).done_stateless();
+    }
+}
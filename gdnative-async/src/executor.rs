@@ -6,8 +6,12 @@ thread_local!(
     static LOCAL_SPAWN: Cell<Option<&'static dyn LocalSpawn>> = Cell::new(None);
 );
 
+/// Returns the global executor for the current thread. Falls back to the built-in executor on
+/// the main thread if none is set.
 pub(crate) fn local_spawn() -> Option<&'static dyn LocalSpawn> {
-    LOCAL_SPAWN.with(|cell| cell.get())
+    LOCAL_SPAWN.with(|cell| cell.get()).or_else(|| {
+        gdnative_core::init::is_main_thread().then(crate::frame_executor::builtin_executor)
+    })
 }
 
 /// Sets the global executor for the current thread to a `Box<dyn LocalSpawn>`. This value is leaked.
@@ -0,0 +1,321 @@
+//! Built-in executor driven by the frames of the scene tree.
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::Context;
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::{Receiver, Sender};
+use futures_task::{waker, ArcWake, LocalFutureObj, LocalSpawn, SpawnError};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+
+use gdnative_bindings::Reference;
+use gdnative_core::core_types::{Variant, VariantArray};
+use gdnative_core::export::user_data::ArcData;
+use gdnative_core::export::{ClassBuilder, Method, NativeClass, NativeClassMethods, Varargs};
+use gdnative_core::godot_site;
+use gdnative_core::object::{Instance, TInstance};
+
+static BUDGET: Lazy<RwLock<Option<Duration>>> = Lazy::new(|| RwLock::new(None));
+
+thread_local! {
+    static EXECUTOR: &'static FrameExecutor = Box::leak(Box::default());
+}
+
+/// Sets the maximum time the built-in executor spends polling tasks per frame. Tasks that are
+/// still ready when the budget runs out are polled on the next frame. `None`, the default,
+/// means no limit.
+///
+/// The budget is checked between polls, so a single poll that takes longer is never
+/// interrupted.
+#[inline]
+pub fn set_frame_budget(budget: Option<Duration>) {
+    *BUDGET.write() = budget;
+}
+
+/// Returns the time budget per frame of the built-in executor. See [`set_frame_budget`].
+#[inline]
+pub fn frame_budget() -> Option<Duration> {
+    *BUDGET.read()
+}
+
+/// Returns task statistics of the built-in executor for the current thread.
+#[inline]
+pub fn executor_stats() -> ExecutorStats {
+    EXECUTOR.with(|executor| executor.state.borrow().stats)
+}
+
+/// Task statistics of the built-in executor. See [`executor_stats`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+#[non_exhaustive]
+pub struct ExecutorStats {
+    /// Number of tasks that are not completed yet.
+    pub pending: usize,
+    /// Total number of tasks spawned.
+    pub spawned: u64,
+    /// Total number of tasks completed, including cancelled tasks and tasks that panicked.
+    pub completed: u64,
+    /// Number of polls during the last frame.
+    pub polls_last_frame: usize,
+    /// Number of ready tasks deferred to the next frame by the time budget during the last
+    /// frame.
+    pub deferred_last_frame: usize,
+}
+
+/// Returns the built-in executor for the current thread. Tasks can be spawned on it directly,
+/// regardless of the executor set with [`set_executor`](crate::set_executor). Since it's
+/// driven by the scene tree, it should only be used on the main thread.
+#[inline]
+pub fn builtin_executor() -> &'static dyn LocalSpawn {
+    EXECUTOR.with(|executor| *executor as &'static dyn LocalSpawn)
+}
+
+/// Drops all tasks and disconnects from the scene tree.
+pub(crate) fn terminate() {
+    let state = EXECUTOR.with(|executor| std::mem::take(&mut *executor.state.borrow_mut()));
+
+    // Tasks are dropped outside of the borrow, since they may spawn tasks while being dropped.
+    drop(state);
+}
+
+/// Polls ready tasks until there are none left from the start of the frame, or the budget
+/// runs out.
+fn pump() {
+    EXECUTOR.with(|executor| executor.pump());
+}
+
+struct FrameExecutor {
+    state: RefCell<State>,
+    ready_send: Sender<usize>,
+    ready_recv: Receiver<usize>,
+}
+
+#[derive(Default)]
+struct State {
+    tasks: Vec<Option<Task>>,
+    free: Vec<usize>,
+    stats: ExecutorStats,
+    pump: Option<Instance<ExecutorPump>>,
+}
+
+struct Task {
+    future: LocalFutureObj<'static, ()>,
+    waker: Arc<TaskWaker>,
+}
+
+struct TaskWaker {
+    id: usize,
+    queued: AtomicBool,
+    ready_send: Sender<usize>,
+}
+
+impl ArcWake for TaskWaker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        if !arc_self.queued.swap(true, Ordering::AcqRel) {
+            // The receiver lives as long as the thread, so this can only fail during shutdown.
+            let _ = arc_self.ready_send.send(arc_self.id);
+        }
+    }
+}
+
+impl Default for FrameExecutor {
+    fn default() -> Self {
+        let (ready_send, ready_recv) = crossbeam_channel::unbounded();
+        FrameExecutor {
+            state: RefCell::default(),
+            ready_send,
+            ready_recv,
+        }
+    }
+}
+
+impl LocalSpawn for FrameExecutor {
+    fn spawn_local_obj(&self, future: LocalFutureObj<'static, ()>) -> Result<(), SpawnError> {
+        let mut state = self.state.borrow_mut();
+
+        if !state
+            .pump
+            .as_ref()
+            .map_or(false, ExecutorPump::is_connected)
+        {
+            state.pump = Some(ExecutorPump::connect().ok_or_else(SpawnError::shutdown)?);
+        }
+
+        let id = state.free.pop().unwrap_or(state.tasks.len());
+        let waker = Arc::new(TaskWaker {
+            id,
+            queued: AtomicBool::new(false),
+            ready_send: self.ready_send.clone(),
+        });
+
+        let task = Task {
+            future,
+            waker: Arc::clone(&waker),
+        };
+
+        if id == state.tasks.len() {
+            state.tasks.push(Some(task));
+        } else {
+            state.tasks[id] = Some(task);
+        }
+
+        state.stats.pending += 1;
+        state.stats.spawned += 1;
+
+        // Newly spawned tasks are polled on the next frame.
+        ArcWake::wake(waker);
+
+        Ok(())
+    }
+}
+
+impl FrameExecutor {
+    fn pump(&self) {
+        let start = Instant::now();
+        let budget = frame_budget();
+
+        // Tasks woken while polling are polled on the next frame.
+        let ready = self.ready_recv.try_iter().collect::<Vec<_>>();
+        let mut polls = 0;
+
+        for (i, &id) in ready.iter().enumerate() {
+            if budget.map_or(false, |budget| start.elapsed() >= budget) {
+                for &id in &ready[i..] {
+                    // Still queued, so they are not sent again by wakers.
+                    let _ = self.ready_send.send(id);
+                }
+
+                let mut state = self.state.borrow_mut();
+                state.stats.polls_last_frame = polls;
+                state.stats.deferred_last_frame = ready.len() - i;
+                return;
+            }
+
+            // The task is taken out while polling, so that it can spawn other tasks.
+            let task = match self
+                .state
+                .borrow_mut()
+                .tasks
+                .get_mut(id)
+                .and_then(Option::take)
+            {
+                Some(task) => task,
+                None => continue,
+            };
+
+            let Task {
+                mut future,
+                waker: task_waker,
+            } = task;
+            task_waker.queued.store(false, Ordering::Release);
+
+            let waker = waker(Arc::clone(&task_waker));
+            let mut cx = Context::from_waker(&waker);
+
+            // Panics are reported, and the task is dropped as if it completed.
+            let mut poll = None;
+            gdnative_core::private::report_panics(
+                "async task",
+                AssertUnwindSafe(|| poll = Some(Pin::new(&mut future).poll(&mut cx))),
+            );
+            polls += 1;
+
+            if poll.map_or(false, |poll| poll.is_pending()) {
+                self.state.borrow_mut().tasks[id] = Some(Task {
+                    future,
+                    waker: task_waker,
+                });
+            } else {
+                // Dropped outside of the borrow, since it may spawn tasks while being dropped.
+                drop(future);
+
+                let mut state = self.state.borrow_mut();
+                state.free.push(id);
+                state.stats.pending -= 1;
+                state.stats.completed += 1;
+            }
+        }
+
+        let mut state = self.state.borrow_mut();
+        state.stats.polls_last_frame = polls;
+        state.stats.deferred_last_frame = 0;
+    }
+}
+
+/// Object connected to the frame signals of the scene tree, that pumps the built-in executor.
+///
+/// If it gets disconnected, a new one is connected on the next spawn, and pending tasks resume.
+pub(crate) struct ExecutorPump;
+
+const FRAME_SIGNALS: [&str; 2] = ["idle_frame", "physics_frame"];
+
+impl ExecutorPump {
+    fn connect() -> Option<Instance<Self>> {
+        let tree = match crate::time::try_scene_tree() {
+            Some(tree) => tree,
+            None => {
+                gdnative_core::log::error(
+                    Default::default(),
+                    "the built-in executor requires the main loop to be a SceneTree",
+                );
+                return None;
+            }
+        };
+
+        let pump = Instance::emplace(ExecutorPump).into_shared();
+
+        for signal in FRAME_SIGNALS {
+            if let Err(err) =
+                tree.connect(signal, pump.base(), "_pump", VariantArray::new_shared(), 0)
+            {
+                gdnative_core::log::error(
+                    Default::default(),
+                    format_args!("failed to connect the built-in executor to `{signal}`: {err}"),
+                );
+                return None;
+            }
+        }
+
+        Some(pump)
+    }
+
+    fn is_connected(pump: &Instance<Self>) -> bool {
+        crate::time::try_scene_tree().map_or(false, |tree| {
+            FRAME_SIGNALS
+                .iter()
+                .all(|signal| tree.is_connected(*signal, pump.base(), "_pump"))
+        })
+    }
+}
+
+impl NativeClass for ExecutorPump {
+    type Base = Reference;
+    type UserData = ArcData<ExecutorPump>;
+
+    fn nativeclass_register_properties(_builder: &ClassBuilder<Self>) {}
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct PumpFn;
+
+impl Method<ExecutorPump> for PumpFn {
+    fn call(&self, _this: TInstance<'_, ExecutorPump>, _args: Varargs<'_>) -> Variant {
+        pump();
+        Variant::nil()
+    }
+
+    fn site() -> Option<gdnative_core::log::Site<'static>> {
+        Some(godot_site!(ExecutorPump::_pump))
+    }
+}
+
+impl NativeClassMethods for ExecutorPump {
+    fn nativeclass_register(builder: &ClassBuilder<Self>) {
+        builder.method("_pump", PumpFn).done_stateless();
+    }
+}
@@ -2,6 +2,15 @@
 //!
 //! This crate contains types and functions that enable using async code with godot-rust.
 //!
+//! # Executors
+//!
+//! Async methods and tasks are spawned on the global executor of the current thread, which can
+//! be set with [`set_executor`] or [`set_boxed_executor`]. If none is set, a built-in executor
+//! is used on the main thread. It polls ready tasks once per idle frame and once per physics
+//! frame of the scene tree, within an optional time budget set with [`set_frame_budget`]. It
+//! can also be used directly through [`builtin_executor`]. Tasks that panic are reported
+//! according to the panic policy, and dropped.
+//!
 //! # Safety assumptions
 //!
 //! This crate assumes that all user non-Rust code follow the official threading guidelines.
@@ -11,12 +20,26 @@
 // Workaround for macros that expect the `gdnative` crate.
 extern crate gdnative_core as gdnative;
 
+mod dispatch;
 mod executor;
+mod frame_executor;
 mod future;
+mod load;
 mod method;
 mod rt;
+mod task;
+mod time;
 
+pub use dispatch::{defer_for, run_on_main_thread, DeferExt};
 pub use executor::{set_boxed_executor, set_executor};
+pub use frame_executor::{
+    builtin_executor, executor_stats, frame_budget, set_frame_budget, ExecutorStats,
+};
 pub use future::Yield;
+pub use load::LoadError;
 pub use method::{Async, AsyncMethod, Spawner, StaticArgs, StaticArgsAsyncMethod};
-pub use rt::{register_runtime, terminate_runtime, Context};
+pub use rt::{register_runtime, terminate_runtime, Context, SignalStream};
+pub use task::{
+    set_task_policy, spawn_local_for, spawn_local_for_with_policy, task_policy, TaskPolicy,
+};
+pub use time::{Elapsed, Timeout};
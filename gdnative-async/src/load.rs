@@ -0,0 +1,128 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+use gdnative_bindings::{Resource, ResourceLoader};
+use gdnative_core::core_types::GodotError;
+use gdnative_core::object::memory::RefCounted;
+use gdnative_core::object::{GodotObject, Ref, SubClass};
+
+use crate::rt::Context;
+
+/// Time spent polling a `ResourceInteractiveLoader` per frame.
+const FRAME_BUDGET: Duration = Duration::from_millis(8);
+
+/// Error returned by [`Context::load`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum LoadError {
+    /// The resource could not be opened for loading, for example because it does not exist.
+    Open {
+        /// The path to the resource.
+        path: String,
+    },
+
+    /// An error occurred while loading the resource. This is `GodotError::Failed` if the loader
+    /// finished without producing a resource.
+    Failed {
+        /// The path to the resource.
+        path: String,
+        /// The error reported by the loader.
+        error: GodotError,
+    },
+
+    /// The resource was loaded, but it is not of the expected class.
+    WrongType {
+        /// The path to the resource.
+        path: String,
+        /// The expected class.
+        expected: &'static str,
+        /// The actual class of the resource.
+        actual: String,
+    },
+}
+
+impl Display for LoadError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Open { path } => write!(f, "cannot open resource {path:?} for loading"),
+            LoadError::Failed { path, error } => {
+                write!(f, "failed to load resource {path:?}: {error}")
+            }
+            LoadError::WrongType {
+                path,
+                expected,
+                actual,
+            } => write!(f, "resource {path:?} is a {actual}, expected {expected}"),
+        }
+    }
+}
+
+impl Error for LoadError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadError::Failed { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Loads the resource at `path` with a `ResourceInteractiveLoader`, polling it for up to
+/// `FRAME_BUDGET` each idle frame, and calling `on_progress` with the progress after each frame.
+pub(crate) async fn load_interactive<T, F>(
+    path: String,
+    mut on_progress: F,
+) -> Result<Ref<T>, LoadError>
+where
+    T: SubClass<Resource> + GodotObject<Memory = RefCounted>,
+    F: FnMut(f32),
+{
+    let loader = ResourceLoader::godot_singleton()
+        .load_interactive(path.as_str(), T::class_name())
+        .ok_or_else(|| LoadError::Open { path: path.clone() })?;
+
+    loop {
+        // SAFETY: the loader is only used on this thread, and is not shared with the engine.
+        let loader = unsafe { loader.assume_safe() };
+        let start = Instant::now();
+
+        let done = loop {
+            match loader.poll() {
+                Ok(()) => {}
+                Err(GodotError::FileEof) => break true,
+                Err(error) => return Err(LoadError::Failed { path, error }),
+            }
+
+            if start.elapsed() >= FRAME_BUDGET {
+                break false;
+            }
+        };
+
+        if done {
+            on_progress(1.0);
+
+            // The loader reached the end without producing a resource.
+            let resource = loader.get_resource().ok_or_else(|| LoadError::Failed {
+                path: path.clone(),
+                error: GodotError::Failed,
+            })?;
+
+            return resource.try_cast::<T>().map_err(|resource| {
+                // SAFETY: the resource was just loaded on this thread.
+                let actual = unsafe { resource.assume_safe() }.get_class().to_string();
+                LoadError::WrongType {
+                    path,
+                    expected: T::class_name(),
+                    actual,
+                }
+            });
+        }
+
+        let stage_count = loader.get_stage_count().max(1);
+        on_progress(loader.get_stage() as f32 / stage_count as f32);
+
+        Context::wait_for_idle_frame().await;
+    }
+}
@@ -4,12 +4,14 @@ use std::sync::Arc;
 
 use futures_task::{LocalFutureObj, LocalSpawn, SpawnError};
 
+use gdnative_bindings::Object;
 use gdnative_core::core_types::{ToVariant, Variant};
 use gdnative_core::export::{FromVarargs, Method, NativeClass, Varargs};
 use gdnative_core::log::{self, Site};
-use gdnative_core::object::TInstance;
+use gdnative_core::object::{GodotObject, TInstance};
 
 use crate::rt::Context;
+use crate::task;
 
 /// Trait for async methods. When exported, such methods return `FunctionState`-like
 /// objects that can be manually resumed or yielded to completion.
@@ -163,11 +165,20 @@ impl<'a, C: NativeClass, A> Spawner<'a, C, A> {
         R: Future<Output = Variant> + 'static,
     {
         let ctx = Arc::new(self.ctx);
+
+        // The owner is the object the method is being called on.
+        let base = self.this.base();
+        // SAFETY: Every engine object is an `Object`.
+        let owner = Object::cast_ref(unsafe { base.as_raw().cast_unchecked::<Object>() });
+
         let future = f(Arc::clone(&ctx), self.this, self.args);
+        let future = task::bind(owner, task::task_policy(), future);
         *self.result = Some(
             self.sp
                 .spawn_local_obj(LocalFutureObj::new(Box::new(async move {
-                    let value = future.await;
+                    // Cancelled tasks resolve to nil, so callers waiting for `completed` are
+                    // not left hanging.
+                    let value = future.await.unwrap_or_else(Variant::nil);
                     ctx.resolve(value);
                 }))),
         );
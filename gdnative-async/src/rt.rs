@@ -1,16 +1,26 @@
 use std::fmt::Display;
+use std::future::Future;
 use std::marker::PhantomData;
+use std::time::Duration;
 
 use func_state::FuncState;
-use gdnative_bindings::Object;
+use gdnative_bindings::{Object, Resource};
 use gdnative_core::core_types::{GodotError, Variant};
+use gdnative_core::export::FromVarargs;
 use gdnative_core::init::InitHandle;
-use gdnative_core::object::{Instance, SubClass, TInstance, TRef};
+use gdnative_core::object::memory::RefCounted;
+use gdnative_core::object::{GodotObject, Instance, Ref, SubClass, TInstance, TRef};
 
 use crate::future;
+use crate::load::{self, LoadError};
+use crate::time::{self, Timeout};
+
+pub use stream::SignalStream;
 
 mod bridge;
 mod func_state;
+mod stream;
+pub(crate) mod task_guard;
 
 /// Context for creating `yield`-like futures in async methods.
 pub struct Context {
@@ -86,6 +96,239 @@ impl Context {
         bridge::SignalBridge::connect(obj.upcast(), signal, resume)?;
         Ok(future)
     }
+
+    /// Returns a stream of the emissions of the specified signal, with arguments parsed as `A`,
+    /// which can be a tuple or a type deriving `FromVarargs`. Emissions whose arguments fail to
+    /// parse are logged and skipped.
+    ///
+    /// Unlike [`signal`](Self::signal), the connection persists as long as the stream lives,
+    /// and every emission is delivered, so any number of streams can be used together, for
+    /// example with `select`. The stream ends when `obj` is freed.
+    ///
+    /// ```ignore
+    /// let mut hits = ctx.signal_stream::<(Ref<Node>,)>(area, "body_entered")?;
+    /// while let Some((body,)) = hits.next().await {
+    ///     // ...
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If connection to the signal failed.
+    pub fn signal_stream<A>(
+        &self,
+        obj: TRef<'_, impl SubClass<Object>>,
+        signal: &str,
+    ) -> Result<SignalStream<A>, GodotError>
+    where
+        A: FromVarargs + Send + 'static,
+    {
+        SignalStream::connect(obj.upcast(), signal)
+    }
+
+    /// Calls `method` on `obj` with `args`, returning a future that resolves to its final return
+    /// value. If the method is a GDScript coroutine that yields, and returns a
+    /// `GDScriptFunctionState`, the future waits for its `completed` signal. Otherwise, the
+    /// future resolves immediately to the return value.
+    ///
+    /// This is the equivalent of `yield(obj.method(args), "completed")` in GDScript, except that
+    /// it also works for methods that don't yield.
+    ///
+    /// The method is called, and its `completed` signal connected to, before this returns, so the
+    /// future can be awaited later, even after the coroutine has completed.
+    ///
+    /// # Safety
+    ///
+    /// The same as for `Object::call`: this function bypasses Rust's static type checks
+    /// (aliasing, thread boundaries, calls to free(), ...).
+    pub unsafe fn await_call<C>(
+        &self,
+        obj: TRef<'_, C>,
+        method: &str,
+        args: &[Variant],
+    ) -> impl Future<Output = Variant> + 'static
+    where
+        C: SubClass<Object>,
+    {
+        let result = obj.upcast::<Object>().call(method, args);
+        Self::function_state_result(result)
+    }
+
+    /// Waits for the `completed` signal of the `GDScriptFunctionState` in `result`, if any.
+    fn function_state_result(result: Variant) -> impl Future<Output = Variant> + 'static {
+        // The first state is connected to right away, so the signal isn't missed if the coroutine
+        // completes before the future is first polled.
+        let completed = Self::function_state(&result).map(Self::completed);
+
+        async move {
+            let mut result = match completed {
+                Some(Some(completed)) => completed.await.into_iter().next().unwrap_or_default(),
+                Some(None) => return Variant::nil(),
+                None => return result,
+            };
+
+            // A resumed coroutine that yields again completes its first function state with the
+            // final value, but check again in case of a nested state. Nested states are connected
+            // to as soon as they are returned.
+            while let Some(state) = Self::function_state(&result) {
+                match Self::completed(state) {
+                    Some(completed) => {
+                        result = completed.await.into_iter().next().unwrap_or_default();
+                    }
+                    None => return Variant::nil(),
+                }
+            }
+
+            result
+        }
+    }
+
+    /// Connects to the `completed` signal of a `GDScriptFunctionState`, returning a future that
+    /// resolves with the signal arguments, or `None` if the connection failed.
+    fn completed(state: Ref<Object>) -> Option<future::Yield<Vec<Variant>>> {
+        // SAFETY: the state object was returned to this thread, and the future is never sent to
+        // another thread.
+        let state = unsafe { state.assume_safe() };
+        let (future, resume) = future::make();
+        match bridge::SignalBridge::connect(state, "completed", resume) {
+            Ok(()) => Some(future),
+            Err(err) => {
+                gdnative_core::log::error(
+                    Default::default(),
+                    format_args!("failed to connect to `GDScriptFunctionState::completed`: {err}"),
+                );
+                None
+            }
+        }
+    }
+
+    /// Returns the `GDScriptFunctionState` in `value`, if any.
+    fn function_state(value: &Variant) -> Option<Ref<Object>> {
+        let obj = value.to_object::<Object>()?;
+
+        // SAFETY: the object was just returned to this thread.
+        let is_state = unsafe { obj.assume_safe() }.is_class("GDScriptFunctionState");
+        is_state.then_some(obj)
+    }
+
+    /// Returns a future that resolves after `secs` seconds, using a `SceneTreeTimer`. This is the
+    /// equivalent of `yield(get_tree().create_timer(secs), "timeout")` in GDScript.
+    ///
+    /// The timer is processed even if the scene tree is paused.
+    ///
+    /// # Panics
+    ///
+    /// If the main loop is not a `SceneTree`.
+    pub fn sleep(&self, secs: f64) -> impl Future<Output = ()> + 'static {
+        let timer = time::scene_tree()
+            .create_timer(secs, true)
+            .expect("SceneTree::create_timer should return a timer");
+
+        // SAFETY: the timer was just created and is kept alive by the scene tree until it
+        // times out.
+        let timer = unsafe { timer.assume_safe() };
+        Self::wait_for(timer.upcast(), "timeout")
+    }
+
+    /// Returns a future that resolves on the next idle frame of the scene tree, before
+    /// `_process` is called on nodes. This is the equivalent of
+    /// `yield(get_tree(), "idle_frame")` in GDScript.
+    ///
+    /// # Panics
+    ///
+    /// If the main loop is not a `SceneTree`.
+    pub fn next_idle_frame(&self) -> impl Future<Output = ()> + 'static {
+        Self::wait_for_idle_frame()
+    }
+
+    /// Returns a future that resolves on the next physics frame of the scene tree, before
+    /// `_physics_process` is called on nodes. This is the equivalent of
+    /// `yield(get_tree(), "physics_frame")` in GDScript.
+    ///
+    /// # Panics
+    ///
+    /// If the main loop is not a `SceneTree`.
+    pub fn next_physics_frame(&self) -> impl Future<Output = ()> + 'static {
+        Self::wait_for(time::scene_tree().upcast(), "physics_frame")
+    }
+
+    /// Loads the resource at `path` without blocking the main thread, resolving to the resource
+    /// cast to `T`. This is the async equivalent of `gdnative::load`.
+    ///
+    /// The resource is loaded with a `ResourceInteractiveLoader`, which is polled for a few
+    /// milliseconds every idle frame until it is done.
+    ///
+    /// ```ignore
+    /// let scene = ctx.load::<PackedScene>("res://levels/Level1.tscn").await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the resource can't be opened or loaded, or isn't a `T`.
+    ///
+    /// # Panics
+    ///
+    /// If the main loop is not a `SceneTree`.
+    pub fn load<T>(&self, path: &str) -> impl Future<Output = Result<Ref<T>, LoadError>> + 'static
+    where
+        T: SubClass<Resource> + GodotObject<Memory = RefCounted> + 'static,
+    {
+        load::load_interactive(path.to_owned(), |_| {})
+    }
+
+    /// Like [`load`](Self::load), but calls `on_progress` with the progress from `0.0` to `1.0`
+    /// after every frame spent loading, for example to update a loading screen.
+    ///
+    /// # Errors
+    ///
+    /// If the resource can't be opened or loaded, or isn't a `T`.
+    ///
+    /// # Panics
+    ///
+    /// If the main loop is not a `SceneTree`.
+    pub fn load_with_progress<T, F>(
+        &self,
+        path: &str,
+        on_progress: F,
+    ) -> impl Future<Output = Result<Ref<T>, LoadError>> + 'static
+    where
+        T: SubClass<Resource> + GodotObject<Memory = RefCounted> + 'static,
+        F: FnMut(f32) + 'static,
+    {
+        load::load_interactive(path.to_owned(), on_progress)
+    }
+
+    /// Runs `future` with a time limit, resolving to `Err(Elapsed)` if it doesn't complete
+    /// within `duration`. The timer is started immediately.
+    ///
+    /// ```ignore
+    /// match ctx.timeout(Duration::from_secs(5), ctx.until_resume()).await {
+    ///     Ok(value) => godot_print!("resumed with {}", value),
+    ///     Err(_) => godot_print!("gave up waiting"),
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the main loop is not a `SceneTree`.
+    pub fn timeout<F: Future>(&self, duration: Duration, future: F) -> Timeout<F> {
+        Timeout::new(future, self.sleep(duration.as_secs_f64()))
+    }
+
+    /// Returns a future that resolves on the next idle frame. See [`next_idle_frame`](Self::next_idle_frame).
+    pub(crate) fn wait_for_idle_frame() -> impl Future<Output = ()> + 'static {
+        Self::wait_for(time::scene_tree().upcast(), "idle_frame")
+    }
+
+    fn wait_for(obj: TRef<'_, Object>, signal: &str) -> impl Future<Output = ()> + 'static {
+        let (future, resume) = future::make();
+        bridge::SignalBridge::connect(obj, signal, resume)
+            .unwrap_or_else(|err| panic!("failed to connect to signal `{signal}`: {err}"));
+
+        async move {
+            future.await;
+        }
+    }
 }
 
 /// Adds required supporting NativeScript classes to `handle`. This must be called once and
@@ -109,10 +352,18 @@ where
 {
     handle.add_class_as::<bridge::SignalBridge>(format!("{prefix}SignalBridge"));
     handle.add_class_as::<func_state::FuncState>(format!("{prefix}FuncState"));
+    handle.add_class_as::<stream::SignalStreamBridge>(format!("{prefix}SignalStreamBridge"));
+    handle.add_class_as::<stream::SignalStreamWatch>(format!("{prefix}SignalStreamWatch"));
+    handle.add_class_as::<task_guard::TaskGuard>(format!("{prefix}TaskGuard"));
+    handle.add_class_as::<crate::frame_executor::ExecutorPump>(format!("{prefix}ExecutorPump"));
+    handle.add_class_as::<crate::dispatch::DispatchPump>(format!("{prefix}DispatchPump"));
 }
 
 /// Releases all observers still in use. This should be called in the
 /// `godot_gdnative_terminate` callback.
 pub fn terminate_runtime() {
     bridge::terminate();
+    task_guard::terminate();
+    crate::frame_executor::terminate();
+    crate::dispatch::terminate();
 }
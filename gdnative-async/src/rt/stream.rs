@@ -0,0 +1,258 @@
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use atomic_waker::AtomicWaker;
+use crossbeam_channel::{Receiver, Sender, TryRecvError};
+use futures_core::Stream;
+use parking_lot::Mutex;
+
+use gdnative_bindings::{Object, Reference};
+use gdnative_core::core_types::{GodotError, ToVariant, Variant, VariantArray};
+use gdnative_core::export::user_data::{ArcData, Map};
+use gdnative_core::export::{
+    ClassBuilder, FromVarargs, Method, NativeClass, NativeClassMethods, Varargs,
+};
+use gdnative_core::godot_site;
+use gdnative_core::object::{Instance, TInstance, TRef, WeakRef};
+
+/// Signal used to tie the lifetime of a `SignalStreamWatch` to the source object. Every object
+/// has it, and it is rarely emitted.
+const WATCH_SIGNAL: &str = "script_changed";
+
+type OnSignal = Box<dyn FnMut(Varargs<'_>) + Send>;
+
+/// Stream of typed signal emissions. See [`Context::signal_stream`](crate::Context::signal_stream).
+///
+/// The connection is kept alive as long as the stream is. The stream ends when the source object
+/// is freed.
+#[must_use = "streams do nothing unless polled"]
+pub struct SignalStream<A> {
+    recv: Receiver<A>,
+    state: Arc<StreamState>,
+    source: WeakRef<Object>,
+    watch: WeakRef<Reference>,
+    // Dropping the bridge also removes the connection.
+    _bridge: Instance<SignalStreamBridge>,
+    /// Remove Send and Sync
+    _marker: PhantomData<*const ()>,
+}
+
+/// State shared between a stream and the objects feeding it.
+#[derive(Default)]
+struct StreamState {
+    waker: AtomicWaker,
+    source_freed: AtomicBool,
+}
+
+impl<A> SignalStream<A>
+where
+    A: FromVarargs + Send + 'static,
+{
+    pub(crate) fn connect(source: TRef<'_, Object>, signal: &str) -> Result<Self, GodotError> {
+        let (send, recv) = crossbeam_channel::unbounded();
+        let state = Arc::<StreamState>::default();
+
+        let sender = StreamSender {
+            send,
+            state: Arc::clone(&state),
+        };
+
+        let on_signal: OnSignal = Box::new(move |mut args: Varargs<'_>| {
+            let args = match args.read_many::<A>() {
+                Ok(parsed) => match args.done() {
+                    Ok(()) => parsed,
+                    Err(err) => {
+                        err.with_site(OnSignalFn::site().unwrap_or_default())
+                            .log_error();
+                        return;
+                    }
+                },
+                Err(errors) => {
+                    for err in errors {
+                        err.with_site(OnSignalFn::site().unwrap_or_default())
+                            .log_error();
+                    }
+                    return;
+                }
+            };
+
+            sender.send(args);
+        });
+
+        let bridge = Instance::emplace(SignalStreamBridge {
+            on_signal: Mutex::new(on_signal),
+        })
+        .into_shared();
+
+        source.connect(
+            signal,
+            bridge.base(),
+            "_on_signal",
+            VariantArray::new_shared(),
+            0,
+        )?;
+
+        // The watch is only referenced by the arguments bound to its connection, so it is
+        // dropped, ending the stream, along with the source. Unlike metadata, connections that
+        // aren't persistent are neither saved with scenes nor duplicated.
+        let watch = Instance::emplace(SignalStreamWatch {
+            state: Arc::clone(&state),
+        })
+        .into_shared();
+
+        let mut binds = VariantArray::new();
+        binds.push(watch.base().to_variant());
+
+        source.connect(
+            WATCH_SIGNAL,
+            watch.base(),
+            "_on_source_changed",
+            binds.into_shared(),
+            0,
+        )?;
+
+        // SAFETY: The watch was just created on this thread, and is kept alive by the source.
+        let watch = unsafe { watch.base().assume_safe() }.downgrade();
+
+        Ok(SignalStream {
+            recv,
+            state,
+            source: source.downgrade(),
+            watch,
+            _bridge: bridge,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<A> Stream for SignalStream<A> {
+    type Item = A;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<A>> {
+        match self.recv.try_recv() {
+            Ok(args) => return Poll::Ready(Some(args)),
+            Err(TryRecvError::Disconnected) => return Poll::Ready(None),
+            Err(TryRecvError::Empty) => {}
+        }
+
+        self.state.waker.register(cx.waker());
+
+        // Check again in case an emission happened before the waker was registered.
+        match self.recv.try_recv() {
+            Ok(args) => Poll::Ready(Some(args)),
+            Err(TryRecvError::Disconnected) => Poll::Ready(None),
+            Err(TryRecvError::Empty) if self.state.source_freed.load(Ordering::Acquire) => {
+                Poll::Ready(None)
+            }
+            Err(TryRecvError::Empty) => Poll::Pending,
+        }
+    }
+}
+
+impl<A> Drop for SignalStream<A> {
+    fn drop(&mut self) {
+        // Disconnecting the watch from a source that is still alive frees it.
+        if let (Some(source), Some(watch)) = (self.source.upgrade(), self.watch.upgrade()) {
+            if source.is_connected(WATCH_SIGNAL, watch.get(), "_on_source_changed") {
+                source.disconnect(WATCH_SIGNAL, watch.get(), "_on_source_changed");
+            }
+        }
+    }
+}
+
+/// Sending half of a stream, which wakes the stream when dropped so that it can end.
+struct StreamSender<A> {
+    send: Sender<A>,
+    state: Arc<StreamState>,
+}
+
+impl<A> StreamSender<A> {
+    fn send(&self, args: A) {
+        // The receiver may be gone if the stream is being dropped.
+        if self.send.send(args).is_ok() {
+            self.state.waker.wake();
+        }
+    }
+}
+
+impl<A> Drop for StreamSender<A> {
+    fn drop(&mut self) {
+        self.state.waker.wake();
+    }
+}
+
+pub(super) struct SignalStreamBridge {
+    on_signal: Mutex<OnSignal>,
+}
+
+impl NativeClass for SignalStreamBridge {
+    type Base = Reference;
+    type UserData = ArcData<SignalStreamBridge>;
+
+    fn nativeclass_register_properties(_builder: &ClassBuilder<Self>) {}
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct OnSignalFn;
+
+impl Method<SignalStreamBridge> for OnSignalFn {
+    fn call(&self, this: TInstance<'_, SignalStreamBridge>, args: Varargs<'_>) -> Variant {
+        this.script().map(|s| (s.on_signal.lock())(args)).unwrap();
+
+        Variant::nil()
+    }
+
+    fn site() -> Option<gdnative_core::log::Site<'static>> {
+        Some(godot_site!(SignalStreamBridge::_on_signal))
+    }
+}
+
+impl NativeClassMethods for SignalStreamBridge {
+    fn nativeclass_register(builder: &ClassBuilder<Self>) {
+        builder.method("_on_signal", OnSignalFn).done_stateless();
+    }
+}
+
+/// Object kept alive by a connection on the source of a stream, which ends the stream when
+/// dropped along with the source.
+pub(super) struct SignalStreamWatch {
+    state: Arc<StreamState>,
+}
+
+impl NativeClass for SignalStreamWatch {
+    type Base = Reference;
+    type UserData = ArcData<SignalStreamWatch>;
+
+    fn nativeclass_register_properties(_builder: &ClassBuilder<Self>) {}
+}
+
+impl Drop for SignalStreamWatch {
+    fn drop(&mut self) {
+        self.state.source_freed.store(true, Ordering::Release);
+        self.state.waker.wake();
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct OnSourceChangedFn;
+
+impl Method<SignalStreamWatch> for OnSourceChangedFn {
+    fn call(&self, _this: TInstance<'_, SignalStreamWatch>, _args: Varargs<'_>) -> Variant {
+        Variant::nil()
+    }
+
+    fn site() -> Option<gdnative_core::log::Site<'static>> {
+        Some(godot_site!(SignalStreamWatch::_on_source_changed))
+    }
+}
+
+impl NativeClassMethods for SignalStreamWatch {
+    fn nativeclass_register(builder: &ClassBuilder<Self>) {
+        builder
+            .method("_on_source_changed", OnSourceChangedFn)
+            .done_stateless();
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+use gdnative_bindings::{Node, Object, Reference};
+use gdnative_core::core_types::{ToVariant, Variant, VariantArray};
+use gdnative_core::export::user_data::{ArcData, Map};
+use gdnative_core::export::{ClassBuilder, Method, NativeClass, NativeClassMethods, Varargs};
+use gdnative_core::godot_site;
+use gdnative_core::object::{Instance, TInstance};
+
+use crate::task::{TaskHandle, TaskPolicy};
+
+/// Signal used to tie the lifetime of a guard to its owner. Every object has it, and it is
+/// rarely emitted.
+const GUARD_SIGNAL: &str = "script_changed";
+
+// Tasks that are not done yet, by owner instance ID
+static TASKS: Lazy<Mutex<HashMap<i64, Vec<Arc<TaskHandle>>>>> = Lazy::new(Mutex::default);
+
+// Instance IDs of owners with a guard attached
+static GUARDED: Lazy<Mutex<HashSet<i64>>> = Lazy::new(Mutex::default);
+
+pub(super) fn terminate() {
+    let tasks = std::mem::take(&mut *TASKS.lock());
+    for handle in tasks.into_values().flatten() {
+        handle.cancel();
+    }
+
+    GUARDED.lock().clear();
+}
+
+/// Associates `handle` with `owner`, attaching a guard object to `owner` if there isn't one.
+///
+/// The guard is only referenced by the arguments bound to a connection on `owner`, so it is
+/// dropped along with `owner`. Unlike metadata, connections that aren't persistent are neither
+/// saved with scenes nor copied by `Node::duplicate`, so copies of `owner` don't share it.
+pub(crate) fn register(owner: &Object, handle: Arc<TaskHandle>) {
+    let owner_id = owner.get_instance_id();
+
+    {
+        let mut tasks = TASKS.lock();
+        let handles = tasks.entry(owner_id).or_default();
+        handles.retain(|handle| !handle.is_done());
+        handles.push(handle);
+    }
+
+    // The lock must not be held while attaching: a guard that fails to attach is dropped.
+    let newly_guarded = GUARDED.lock().insert(owner_id);
+    if newly_guarded {
+        let guard = Instance::emplace(TaskGuard { owner_id }).into_shared();
+
+        if let Some(node) = owner.cast::<Node>() {
+            if let Err(err) = node.connect(
+                "tree_exiting",
+                guard.base(),
+                "_on_tree_exiting",
+                VariantArray::new_shared(),
+                0,
+            ) {
+                gdnative_core::log::error(
+                    Default::default(),
+                    format_args!(
+                        "failed to watch the owner of an async task for tree exits: {err}"
+                    ),
+                );
+            }
+        }
+
+        let mut binds = VariantArray::new();
+        binds.push(guard.base().to_variant());
+
+        if let Err(err) = owner.connect(
+            GUARD_SIGNAL,
+            guard.base(),
+            "_on_script_changed",
+            binds.into_shared(),
+            0,
+        ) {
+            gdnative_core::log::error(
+                Default::default(),
+                format_args!("failed to attach a guard to the owner of an async task: {err}"),
+            );
+        }
+    }
+}
+
+/// Cancels tasks of `owner_id` for which `filter` returns `true`.
+fn cancel(owner_id: i64, filter: impl Fn(&TaskHandle) -> bool) {
+    let cancelled = {
+        let mut tasks = TASKS.lock();
+        let handles = match tasks.get_mut(&owner_id) {
+            Some(handles) => handles,
+            None => return,
+        };
+
+        let (cancelled, kept) = std::mem::take(handles)
+            .into_iter()
+            .filter(|handle| !handle.is_done())
+            .partition::<Vec<_>, _>(|handle| filter(handle));
+
+        if kept.is_empty() {
+            tasks.remove(&owner_id);
+        } else {
+            *handles = kept;
+        }
+
+        cancelled
+    };
+
+    // Waking may run executor code, so it's done outside of the lock.
+    for handle in cancelled {
+        handle.cancel();
+    }
+}
+
+/// Object attached to the owners of async tasks through a connection, which is dropped when
+/// the owner is freed.
+pub(super) struct TaskGuard {
+    owner_id: i64,
+}
+
+impl NativeClass for TaskGuard {
+    type Base = Reference;
+    type UserData = ArcData<TaskGuard>;
+
+    fn nativeclass_register_properties(_builder: &ClassBuilder<Self>) {}
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        GUARDED.lock().remove(&self.owner_id);
+        cancel(self.owner_id, |_| true);
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct OnTreeExitingFn;
+
+impl Method<TaskGuard> for OnTreeExitingFn {
+    fn call(&self, this: TInstance<'_, TaskGuard>, _args: Varargs<'_>) -> Variant {
+        let owner_id = this.script().map(|s| s.owner_id).unwrap();
+        cancel(owner_id, |handle| {
+            handle.policy == TaskPolicy::UntilExitTree
+        });
+        Variant::nil()
+    }
+
+    fn site() -> Option<gdnative_core::log::Site<'static>> {
+        Some(godot_site!(TaskGuard::_on_tree_exiting))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct OnScriptChangedFn;
+
+impl Method<TaskGuard> for OnScriptChangedFn {
+    fn call(&self, _this: TInstance<'_, TaskGuard>, _args: Varargs<'_>) -> Variant {
+        Variant::nil()
+    }
+
+    fn site() -> Option<gdnative_core::log::Site<'static>> {
+        Some(godot_site!(TaskGuard::_on_script_changed))
+    }
+}
+
+impl NativeClassMethods for TaskGuard {
+    fn nativeclass_register(builder: &ClassBuilder<Self>) {
+        builder
+            .method("_on_tree_exiting", OnTreeExitingFn)
+            .done_stateless();
+        builder
+            .method("_on_script_changed", OnScriptChangedFn)
+            .done_stateless();
+    }
+}
@@ -0,0 +1,188 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use atomic_waker::AtomicWaker;
+use futures_task::{LocalFutureObj, SpawnError};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+
+use gdnative_bindings::Object;
+use gdnative_core::object::{SubClass, TRef};
+
+use crate::rt::task_guard;
+
+static POLICY: Lazy<RwLock<TaskPolicy>> = Lazy::new(|| RwLock::new(TaskPolicy::default()));
+
+/// When tasks associated with an owner object are cancelled. Cancelled tasks are dropped
+/// without being polled again, along with everything they hold. The `FunctionState`-like
+/// object of a cancelled async method completes with `null`.
+///
+/// The default policy for async methods and [`spawn_local_for`] can be changed with
+/// [`set_task_policy`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum TaskPolicy {
+    /// Cancel the task when the owner is freed. This is the default.
+    UntilFreed,
+
+    /// Cancel the task when the owner is freed, or when it is about to leave the scene tree if
+    /// it is a `Node`.
+    UntilExitTree,
+
+    /// Never cancel the task. It may outlive its owner.
+    Detached,
+}
+
+impl Default for TaskPolicy {
+    #[inline]
+    fn default() -> Self {
+        TaskPolicy::UntilFreed
+    }
+}
+
+/// Sets the default [`TaskPolicy`] for async methods and [`spawn_local_for`].
+#[inline]
+pub fn set_task_policy(policy: TaskPolicy) {
+    *POLICY.write() = policy;
+}
+
+/// Returns the current default [`TaskPolicy`].
+#[inline]
+pub fn task_policy() -> TaskPolicy {
+    *POLICY.read()
+}
+
+/// Spawns a fire-and-forget `future` on the global executor of the current thread, tied to the
+/// lifetime of `owner` according to the default [`TaskPolicy`].
+///
+/// This is useful for starting async work from synchronous methods like `_process`:
+///
+/// ```ignore
+/// #[method]
+/// fn _on_hit(&self, #[base] base: TRef<Node>) {
+///     gdnative::tasks::spawn_local_for(base, async move {
+///         // Dropped when `base` is freed
+///     })
+///     .unwrap();
+/// }
+/// ```
+///
+/// # Errors
+///
+/// If no executor is set for the current thread, or if the executor fails to spawn the task.
+#[inline]
+pub fn spawn_local_for<C, F>(owner: TRef<'_, C>, future: F) -> Result<(), SpawnError>
+where
+    C: SubClass<Object>,
+    F: Future<Output = ()> + 'static,
+{
+    spawn_local_for_with_policy(owner, task_policy(), future)
+}
+
+/// Spawns a fire-and-forget `future` on the global executor of the current thread, tied to the
+/// lifetime of `owner` according to `policy`. See [`spawn_local_for`].
+///
+/// # Errors
+///
+/// If no executor is set for the current thread, or if the executor fails to spawn the task.
+#[inline]
+pub fn spawn_local_for_with_policy<C, F>(
+    owner: TRef<'_, C>,
+    policy: TaskPolicy,
+    future: F,
+) -> Result<(), SpawnError>
+where
+    C: SubClass<Object>,
+    F: Future<Output = ()> + 'static,
+{
+    let sp = crate::executor::local_spawn().ok_or_else(SpawnError::shutdown)?;
+    let future = bind(owner.upcast().as_ref(), policy, future);
+    sp.spawn_local_obj(LocalFutureObj::new(Box::new(async move {
+        future.await;
+    })))
+}
+
+/// Ties `future` to the lifetime of `owner`.
+pub(crate) fn bind<F>(owner: &Object, policy: TaskPolicy, future: F) -> OwnedTask<F>
+where
+    F: Future,
+{
+    let handle = Arc::new(TaskHandle {
+        policy,
+        cancelled: AtomicBool::new(false),
+        done: AtomicBool::new(false),
+        waker: AtomicWaker::new(),
+    });
+
+    if policy != TaskPolicy::Detached {
+        task_guard::register(owner, Arc::clone(&handle));
+    }
+
+    OwnedTask {
+        future: Some(Box::pin(future)),
+        handle,
+    }
+}
+
+/// Shared state between an [`OwnedTask`] and the guard of its owner.
+pub(crate) struct TaskHandle {
+    pub(crate) policy: TaskPolicy,
+    cancelled: AtomicBool,
+    done: AtomicBool,
+    waker: AtomicWaker,
+}
+
+impl TaskHandle {
+    /// Cancels the task, waking it so the executor can drop it.
+    pub(crate) fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+        self.waker.wake();
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.done.load(Ordering::Acquire)
+    }
+}
+
+/// Future wrapper that resolves early, dropping the inner future, once cancelled.
+pub(crate) struct OwnedTask<F> {
+    future: Option<Pin<Box<F>>>,
+    handle: Arc<TaskHandle>,
+}
+
+impl<F: Future> Future for OwnedTask<F> {
+    type Output = Option<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.handle.waker.register(cx.waker());
+
+        if self.handle.cancelled.load(Ordering::Acquire) {
+            self.future = None;
+            self.handle.done.store(true, Ordering::Release);
+            return Poll::Ready(None);
+        }
+
+        let future = match self.future.as_mut() {
+            Some(future) => future,
+            None => return Poll::Ready(None),
+        };
+
+        match future.as_mut().poll(cx) {
+            Poll::Ready(output) => {
+                self.future = None;
+                self.handle.done.store(true, Ordering::Release);
+                Poll::Ready(Some(output))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<F> Drop for OwnedTask<F> {
+    fn drop(&mut self) {
+        self.handle.done.store(true, Ordering::Release);
+    }
+}
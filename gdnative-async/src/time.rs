@@ -0,0 +1,76 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use gdnative_bindings::{Engine, SceneTree};
+use gdnative_core::object::TRef;
+
+/// Returns the `SceneTree` main loop.
+///
+/// # Panics
+///
+/// If the main loop is not a `SceneTree`.
+pub(crate) fn scene_tree() -> TRef<'static, SceneTree> {
+    try_scene_tree().expect("the main loop should be a SceneTree to use timers and frame futures")
+}
+
+/// Returns the `SceneTree` main loop, or `None` if the main loop is not a `SceneTree`.
+pub(crate) fn try_scene_tree() -> Option<TRef<'static, SceneTree>> {
+    // SAFETY: the main loop lives as long as the engine is running, and async tasks are only
+    // polled on the thread they are spawned on, which is expected to be the main thread when
+    // accessing the scene tree, as per the global safety assumptions.
+    unsafe {
+        Engine::godot_singleton()
+            .get_main_loop()
+            .and_then(|main_loop| main_loop.assume_safe().cast::<SceneTree>())
+    }
+}
+
+/// Future returned by [`Context::timeout`](crate::Context::timeout). Resolves to the output of
+/// the inner future, or [`Elapsed`] if the time runs out first.
+///
+/// If the time runs out, the inner future is dropped when the `Timeout` is.
+#[must_use = "futures do nothing unless polled"]
+pub struct Timeout<F: Future> {
+    future: Pin<Box<F>>,
+    timer: Pin<Box<dyn Future<Output = ()>>>,
+}
+
+impl<F: Future> Timeout<F> {
+    pub(crate) fn new(future: F, timer: impl Future<Output = ()> + 'static) -> Self {
+        Timeout {
+            future: Box::pin(future),
+            timer: Box::pin(timer),
+        }
+    }
+}
+
+impl<F: Future> Future for Timeout<F> {
+    type Output = Result<F::Output, Elapsed>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Poll::Ready(output) = self.future.as_mut().poll(cx) {
+            return Poll::Ready(Ok(output));
+        }
+
+        match self.timer.as_mut().poll(cx) {
+            Poll::Ready(()) => Poll::Ready(Err(Elapsed)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Error returned by [`Timeout`] when the time runs out before the inner future resolves.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct Elapsed;
+
+impl Display for Elapsed {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "deadline has elapsed")
+    }
+}
+
+impl Error for Elapsed {}
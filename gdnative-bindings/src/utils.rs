@@ -2,7 +2,9 @@
 
 use gdnative_core::core_types::NodePath;
 use gdnative_core::export::NativeClass;
-use gdnative_core::object::{SubClass, TInstance, TRef};
+use gdnative_core::godot_error;
+use gdnative_core::object::memory::ManuallyManaged;
+use gdnative_core::object::{GodotObject, NodeRef, OwnedNode, SubClass, TInstance, TRef};
 
 use super::generated::{Engine, Node, SceneTree};
 
@@ -77,3 +79,74 @@ impl<N: SubClass<Node>, P: Into<NodePath>> NodeResolveExt<P> for N {
         self.upcast().get_node(path)?.assume_safe().cast()
     }
 }
+
+pub trait NodeOwnedChildExt {
+    /// Adds `child` as a child of this node, handing over its ownership to the scene tree.
+    /// See [`OwnedNode`].
+    fn add_owned_child<T>(&self, child: OwnedNode<T>)
+    where
+        T: SubClass<Node> + GodotObject<Memory = ManuallyManaged>;
+
+    /// Adds `child` as a child of this node with a human-readable name if `child` has none,
+    /// handing over its ownership to the scene tree. See [`OwnedNode`].
+    fn add_owned_child_legible<T>(&self, child: OwnedNode<T>)
+    where
+        T: SubClass<Node> + GodotObject<Memory = ManuallyManaged>;
+}
+
+impl<N: SubClass<Node>> NodeOwnedChildExt for N {
+    fn add_owned_child<T>(&self, child: OwnedNode<T>)
+    where
+        T: SubClass<Node> + GodotObject<Memory = ManuallyManaged>,
+    {
+        self.upcast().add_child(child.into_inner(), false);
+    }
+
+    fn add_owned_child_legible<T>(&self, child: OwnedNode<T>)
+    where
+        T: SubClass<Node> + GodotObject<Memory = ManuallyManaged>,
+    {
+        self.upcast().add_child(child.into_inner(), true);
+    }
+}
+
+/// Resolves a `#[node]` field of a `NativeClass`. Used by the derive macro before `_ready`.
+#[doc(hidden)]
+pub fn resolve_node_ref<B, T>(
+    base: &B,
+    path: &str,
+    field: &NodeRef<T>,
+    class_name: &str,
+    field_name: &str,
+) where
+    B: SubClass<Node>,
+    T: SubClass<Node>,
+{
+    field.clear();
+
+    let node = match base.upcast().get_node(path) {
+        Some(node) => unsafe { node.assume_safe() },
+        None => {
+            godot_error!(
+                "{}::{}: node not found at path {:?} (expected {})",
+                class_name,
+                field_name,
+                path,
+                T::class_name(),
+            );
+            return;
+        }
+    };
+
+    match node.cast::<T>() {
+        Some(node) => field.set(node),
+        None => godot_error!(
+            "{}::{}: node at path {:?} is a {}, expected {}",
+            class_name,
+            field_name,
+            path,
+            node.get_class(),
+            T::class_name(),
+        ),
+    }
+}
@@ -0,0 +1,128 @@
+//! Compares the access overhead of the thread-safe user-data wrappers.
+//!
+//! Run with `cargo bench -p gdnative-core --bench user_data`. These benchmarks only exercise the
+//! wrappers themselves, and don't require a running engine.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use gdnative::api::Reference;
+use gdnative::export::user_data::{
+    ArcData, ArcSwapData, Map, MapMut, MutexData, RwLockData, UserData,
+};
+use gdnative::prelude::*;
+
+const BACKGROUND_READERS: usize = 3;
+
+macro_rules! config_class {
+    ($name:ident, $user_data:ident) => {
+        #[derive(Clone, Default)]
+        struct $name {
+            values: [f32; 16],
+        }
+
+        impl NativeClass for $name {
+            type Base = Reference;
+            type UserData = $user_data<Self>;
+        }
+    };
+}
+
+config_class!(MutexConfig, MutexData);
+config_class!(RwLockConfig, RwLockData);
+config_class!(ArcConfig, ArcData);
+config_class!(ArcSwapConfig, ArcSwapData);
+
+/// Runs `f` while `BACKGROUND_READERS` threads continuously read from `data`.
+fn with_background_readers<D, R>(data: &D, f: impl FnOnce() -> R) -> R
+where
+    D: Map + Send + Sync + 'static,
+    D::Target: HasValues,
+{
+    let stop = Arc::new(AtomicBool::new(false));
+    let readers = (0..BACKGROUND_READERS)
+        .map(|_| {
+            let data = data.clone();
+            let stop = Arc::clone(&stop);
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    black_box(data.map(|c| c.values()[0]).unwrap());
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let ret = f();
+
+    stop.store(true, Ordering::Relaxed);
+    for reader in readers {
+        reader.join().unwrap();
+    }
+
+    ret
+}
+
+trait HasValues {
+    fn values(&self) -> &[f32; 16];
+}
+
+macro_rules! impl_has_values {
+    ($($name:ident),*) => {
+        $(impl HasValues for $name {
+            fn values(&self) -> &[f32; 16] {
+                &self.values
+            }
+        })*
+    };
+}
+
+impl_has_values!(MutexConfig, RwLockConfig, ArcConfig, ArcSwapConfig);
+
+fn bench_read<D>(c: &mut Criterion, name: &str, data: D)
+where
+    D: Map + Send + Sync + 'static,
+    D::Target: HasValues,
+{
+    let mut group = c.benchmark_group("read");
+    group.bench_function(BenchmarkId::new(name, "uncontended"), |b| {
+        b.iter(|| data.map(|c| black_box(c.values()[0])).unwrap())
+    });
+    with_background_readers(&data, || {
+        group.bench_function(BenchmarkId::new(name, "concurrent readers"), |b| {
+            b.iter(|| data.map(|c| black_box(c.values()[0])).unwrap())
+        });
+    });
+    group.finish();
+}
+
+fn bench_write<D: MapMut>(c: &mut Criterion, name: &str, data: D) {
+    let mut group = c.benchmark_group("write");
+    group.bench_function(BenchmarkId::new(name, "uncontended"), |b| {
+        b.iter(|| {
+            data.map_mut(|c| {
+                black_box(c);
+            })
+            .unwrap()
+        })
+    });
+    group.finish();
+}
+
+fn read(c: &mut Criterion) {
+    bench_read(c, "MutexData", MutexData::new(MutexConfig::default()));
+    bench_read(c, "RwLockData", RwLockData::new(RwLockConfig::default()));
+    bench_read(c, "ArcData", ArcData::new(ArcConfig::default()));
+    bench_read(c, "ArcSwapData", ArcSwapData::new(ArcSwapConfig::default()));
+}
+
+fn write(c: &mut Criterion) {
+    bench_write(c, "MutexData", MutexData::new(MutexConfig::default()));
+    bench_write(c, "RwLockData", RwLockData::new(RwLockConfig::default()));
+    bench_write(c, "ArcSwapData", ArcSwapData::new(ArcSwapConfig::default()));
+}
+
+criterion_group!(benches, read, write);
+criterion_main!(benches);
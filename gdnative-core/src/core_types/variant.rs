@@ -1,20 +1,43 @@
 use crate::*;
 use std::borrow::Cow;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::default::Default;
 use std::fmt;
-use std::hash::Hash;
+use std::hash::{BuildHasher, Hash};
 use std::mem::{forget, transmute};
+use std::num::{
+    NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU16, NonZeroU32,
+    NonZeroU64, NonZeroU8, NonZeroUsize,
+};
 use std::ptr;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use indexmap::IndexMap;
 
 use crate::core_types::*;
 use crate::object::ownership::*;
 use crate::object::*;
 use crate::private::{get_api, ManuallyManagedClassPlaceholder};
 
+pub mod marshal;
+pub mod text;
+
+mod value;
+
 #[cfg(feature = "serde")]
 mod serialize;
 
+#[cfg(feature = "serde")]
+pub mod serde_format;
+
+#[cfg(feature = "serde_json")]
+mod json;
+
+#[cfg(feature = "serde_json")]
+pub use json::JsonMode;
+
 /// A `Variant` can represent all Godot values (core types or `Object` class instances).
 ///
 /// The underlying data is either stored inline or reference-counted on the heap,
@@ -27,8 +50,10 @@ mod serialize;
 pub struct Variant(pub(crate) sys::godot_variant);
 
 macro_rules! impl_coerce_from_variant_inner {
-    (impl CoerceFromVariant for $type:path = transmute($to_gd_method:ident)) => {
-        impl private::Sealed for $type {}
+    (impl CoerceFromVariant for $type:path : $variant_type:expr => transmute($to_gd_method:ident)) => {
+        impl private::Sealed for $type {
+            const VARIANT_TYPE: VariantType = $variant_type;
+        }
         impl CoerceFromVariant for $type {
             #[inline]
             fn coerce_from_variant(v: &Variant) -> Self {
@@ -39,8 +64,10 @@ macro_rules! impl_coerce_from_variant_inner {
             }
         }
     };
-    (impl CoerceFromVariant for $type:path = from_sys($to_gd_method:ident)) => {
-        impl private::Sealed for $type {}
+    (impl CoerceFromVariant for $type:path : $variant_type:expr => from_sys($to_gd_method:ident)) => {
+        impl private::Sealed for $type {
+            const VARIANT_TYPE: VariantType = $variant_type;
+        }
         impl CoerceFromVariant for $type {
             #[inline]
             fn coerce_from_variant(v: &Variant) -> Self {
@@ -53,11 +80,11 @@ macro_rules! impl_coerce_from_variant_inner {
 macro_rules! impl_coerce_from_variant {
     (
         $(
-            impl CoerceFromVariant for $type:path = $kind:ident ($to_gd_method:ident);
+            impl CoerceFromVariant for $type:path : $variant_type:expr => $kind:ident ($to_gd_method:ident);
         )*
     ) => {
         $(
-            impl_coerce_from_variant_inner!(impl CoerceFromVariant for $type = $kind($to_gd_method));
+            impl_coerce_from_variant_inner!(impl CoerceFromVariant for $type : $variant_type => $kind($to_gd_method));
         )*
     }
 }
@@ -175,6 +202,50 @@ impl VariantType {
         unsafe { transmute(v as u32) }
     }
 
+    /// Returns whether values of this type can be converted to `target` by Godot's non-strict
+    /// conversion rules, excluding `Nil`, which can be converted to most types.
+    pub(crate) fn can_coerce_to(self, target: VariantType) -> bool {
+        use VariantType as VT;
+
+        match (self, target) {
+            (VT::Nil, _) => false,
+            (from, to) if from == to => true,
+            (VT::I64 | VT::F64 | VT::GodotString, VT::Bool) => true,
+            (VT::Bool | VT::F64 | VT::GodotString, VT::I64) => true,
+            (VT::Bool | VT::I64 | VT::GodotString, VT::F64) => true,
+            (VT::Object, VT::GodotString) => false,
+            (_, VT::GodotString) => true,
+            (VT::Transform, VT::Transform2D) => true,
+            (VT::Basis, VT::Quat) => true,
+            (VT::Quat | VT::Vector3, VT::Basis) => true,
+            (VT::Transform2D | VT::Quat | VT::Basis, VT::Transform) => true,
+            (VT::GodotString | VT::I64, VT::Color) => true,
+            (VT::Object, VT::Rid) => true,
+            (VT::GodotString, VT::NodePath) => true,
+            (
+                VT::ByteArray
+                | VT::Int32Array
+                | VT::Float32Array
+                | VT::StringArray
+                | VT::Vector2Array
+                | VT::Vector3Array
+                | VT::ColorArray,
+                VT::VariantArray,
+            ) => true,
+            (
+                VT::VariantArray,
+                VT::ByteArray
+                | VT::Int32Array
+                | VT::Float32Array
+                | VT::StringArray
+                | VT::Vector2Array
+                | VT::Vector3Array
+                | VT::ColorArray,
+            ) => true,
+            _ => false,
+        }
+    }
+
     /// The `stringify!` representation of this variant. Mostly used for serialization.
     #[inline]
     pub const fn name(self) -> &'static str {
@@ -584,32 +655,32 @@ impl Variant {
 }
 
 impl_coerce_from_variant!(
-    impl CoerceFromVariant for Vector2 = transmute(godot_variant_as_vector2);
-    impl CoerceFromVariant for Vector3 = transmute(godot_variant_as_vector3);
-    impl CoerceFromVariant for Quat = transmute(godot_variant_as_quat);
-    impl CoerceFromVariant for Rect2 = transmute(godot_variant_as_rect2);
-    impl CoerceFromVariant for Transform2D = transmute(godot_variant_as_transform2d);
-    impl CoerceFromVariant for f64 = transmute(godot_variant_as_real);
-    impl CoerceFromVariant for i64 = transmute(godot_variant_as_int);
-    impl CoerceFromVariant for u64 = transmute(godot_variant_as_uint);
-    impl CoerceFromVariant for Bool = transmute(godot_variant_as_bool);
-    impl CoerceFromVariant for Plane = from_sys(godot_variant_as_plane);
-    impl CoerceFromVariant for Transform = from_sys(godot_variant_as_transform);
-    impl CoerceFromVariant for Color = from_sys(godot_variant_as_color);
-    impl CoerceFromVariant for Basis = from_sys(godot_variant_as_basis);
-    impl CoerceFromVariant for Aabb = from_sys(godot_variant_as_aabb);
-    impl CoerceFromVariant for NodePath = from_sys(godot_variant_as_node_path);
-    impl CoerceFromVariant for GodotString = from_sys(godot_variant_as_string);
-    impl CoerceFromVariant for Rid = from_sys(godot_variant_as_rid);
-    impl CoerceFromVariant for VariantArray<Shared> = from_sys(godot_variant_as_array);
-    impl CoerceFromVariant for PoolArray<u8> = from_sys(godot_variant_as_pool_byte_array);
-    impl CoerceFromVariant for PoolArray<i32> = from_sys(godot_variant_as_pool_int_array);
-    impl CoerceFromVariant for PoolArray<f32> = from_sys(godot_variant_as_pool_real_array);
-    impl CoerceFromVariant for PoolArray<GodotString> = from_sys(godot_variant_as_pool_string_array);
-    impl CoerceFromVariant for PoolArray<Vector2> = from_sys(godot_variant_as_pool_vector2_array);
-    impl CoerceFromVariant for PoolArray<Vector3> = from_sys(godot_variant_as_pool_vector3_array);
-    impl CoerceFromVariant for PoolArray<Color> = from_sys(godot_variant_as_pool_color_array);
-    impl CoerceFromVariant for Dictionary<Shared> = from_sys(godot_variant_as_dictionary);
+    impl CoerceFromVariant for Vector2 : VariantType::Vector2 => transmute(godot_variant_as_vector2);
+    impl CoerceFromVariant for Vector3 : VariantType::Vector3 => transmute(godot_variant_as_vector3);
+    impl CoerceFromVariant for Quat : VariantType::Quat => transmute(godot_variant_as_quat);
+    impl CoerceFromVariant for Rect2 : VariantType::Rect2 => transmute(godot_variant_as_rect2);
+    impl CoerceFromVariant for Transform2D : VariantType::Transform2D => transmute(godot_variant_as_transform2d);
+    impl CoerceFromVariant for f64 : VariantType::F64 => transmute(godot_variant_as_real);
+    impl CoerceFromVariant for i64 : VariantType::I64 => transmute(godot_variant_as_int);
+    impl CoerceFromVariant for u64 : VariantType::I64 => transmute(godot_variant_as_uint);
+    impl CoerceFromVariant for Bool : VariantType::Bool => transmute(godot_variant_as_bool);
+    impl CoerceFromVariant for Plane : VariantType::Plane => from_sys(godot_variant_as_plane);
+    impl CoerceFromVariant for Transform : VariantType::Transform => from_sys(godot_variant_as_transform);
+    impl CoerceFromVariant for Color : VariantType::Color => from_sys(godot_variant_as_color);
+    impl CoerceFromVariant for Basis : VariantType::Basis => from_sys(godot_variant_as_basis);
+    impl CoerceFromVariant for Aabb : VariantType::Aabb => from_sys(godot_variant_as_aabb);
+    impl CoerceFromVariant for NodePath : VariantType::NodePath => from_sys(godot_variant_as_node_path);
+    impl CoerceFromVariant for GodotString : VariantType::GodotString => from_sys(godot_variant_as_string);
+    impl CoerceFromVariant for Rid : VariantType::Rid => from_sys(godot_variant_as_rid);
+    impl CoerceFromVariant for VariantArray<Shared> : VariantType::VariantArray => from_sys(godot_variant_as_array);
+    impl CoerceFromVariant for PoolArray<u8> : VariantType::ByteArray => from_sys(godot_variant_as_pool_byte_array);
+    impl CoerceFromVariant for PoolArray<i32> : VariantType::Int32Array => from_sys(godot_variant_as_pool_int_array);
+    impl CoerceFromVariant for PoolArray<f32> : VariantType::Float32Array => from_sys(godot_variant_as_pool_real_array);
+    impl CoerceFromVariant for PoolArray<GodotString> : VariantType::StringArray => from_sys(godot_variant_as_pool_string_array);
+    impl CoerceFromVariant for PoolArray<Vector2> : VariantType::Vector2Array => from_sys(godot_variant_as_pool_vector2_array);
+    impl CoerceFromVariant for PoolArray<Vector3> : VariantType::Vector3Array => from_sys(godot_variant_as_pool_vector3_array);
+    impl CoerceFromVariant for PoolArray<Color> : VariantType::ColorArray => from_sys(godot_variant_as_pool_color_array);
+    impl CoerceFromVariant for Dictionary<Shared> : VariantType::Dictionary => from_sys(godot_variant_as_dictionary);
 );
 
 impl_basic_traits_as_sys!(
@@ -723,7 +794,7 @@ godot_test!(
 /// - `Unit` is represented as an empty `Dictionary` (`{}`)
 /// - `Enum::Variant(a, b, c)` is represented as an externally tagged `Dictionary`
 ///   (`{ "Variant": [a, b, c] }`), unless another representation is specified with
-///   `#[variant(enum)]` (see below).
+///   `#[variant(enum)]`, `#[variant(tag)]` or `#[variant(untagged)]` (see below).
 ///
 /// Behavior of the derive macros can be customized using attributes:
 ///
@@ -739,6 +810,48 @@ godot_test!(
 /// Only applicable to field-less enums with a explicit primitive `#[repr]` type. Variants of
 /// types annotated with this attribute are represented as their primitive integral values.
 ///
+/// - `#[variant(tag = "type")]`
+///
+/// Only applicable to enums. Variants are represented as internally tagged `Dictionary`s, with
+/// the variant name stored under the given key alongside the fields, i.e.
+/// `{ "type": "Variant", "a": a, "b": b }` for `Enum::Variant { a, b }`. Unit variants only
+/// contain the tag. The entries of newtype variants are merged like `#[variant(flatten)]` fields,
+/// so only the tag is written if the content isn't represented as a `Dictionary`. Tuple variants
+/// are not allowed.
+///
+/// - `#[variant(tag = "type", content = "data")]`
+///
+/// Only applicable to enums. Variants are represented as adjacently tagged `Dictionary`s, i.e.
+/// `{ "type": "Variant", "data": [a, b, c] }` for `Enum::Variant(a, b, c)`. The content is
+/// omitted for unit variants.
+///
+/// - `#[variant(untagged)]`
+///
+/// Only applicable to enums. Variants are represented as their contents without any tag, with
+/// unit variants represented as `Nil`. When converting from `Variant`, each variant is tried in
+/// declaration order, and the first successful conversion is returned.
+///
+/// - `#[variant(rename_all = "...")]`
+///
+/// Rename all fields of a struct, or all variants of an enum, according to the given case
+/// convention. The possible values are `"lowercase"`, `"UPPERCASE"`, `"PascalCase"`,
+/// `"camelCase"`, `"snake_case"`, `"SCREAMING_SNAKE_CASE"`, `"kebab-case"` and
+/// `"SCREAMING-KEBAB-CASE"`.
+///
+/// - `#[variant(coerce)]`
+///
+/// Same as setting `#[variant(coerce)]` on all fields of the struct, or of all enum variants.
+///
+/// ### Variant attributes
+///
+/// - `#[variant(rename = "name")]`
+///
+/// Use the given name for the variant instead of its Rust name.
+///
+/// - `#[variant(rename_all = "...")]`
+///
+/// Rename all fields of a struct variant according to the given case convention.
+///
 /// ### Field attributes
 ///
 /// - `#[variant(to_variant_with = "path::to::func")]`
@@ -769,6 +882,41 @@ godot_test!(
 /// - `#[variant(skip)]`
 ///
 /// Convenience attribute that sets `skip_to_variant` and `skip_from_variant`.
+///
+/// - `#[variant(rename = "name")]`
+///
+/// Use the given name as the `Dictionary` key of the field, instead of its Rust name. Only
+/// applicable to named fields.
+///
+/// - `#[variant(default)]`
+///
+/// When converting from `Variant`, use `Default::default()` if the key of the field is missing
+/// from the `Dictionary`. Only applicable to named fields.
+///
+/// - `#[variant(default = "path::to::func")]`
+///
+/// Same as `#[variant(default)]`, but the default value is obtained by calling the given
+/// function, with the signature `fn() -> T`. This is also used for skipped fields.
+///
+/// - `#[variant(flatten)]`
+///
+/// Merge the entries of the field, which should be represented as a `Dictionary`, into the
+/// `Dictionary` of the containing struct. When converting from `Variant`, the field is converted
+/// from the entire containing `Dictionary`, and errors are reported as `InvalidField` with the
+/// name of the field. Only applicable to named fields.
+///
+/// Flattened `Option`s are skipped when `None`, and are converted back to `None` if keys of the
+/// inner value are missing from the `Dictionary`. Other values that aren't represented as a
+/// `Dictionary` are reported as errors and skipped when converting to `Variant`.
+///
+/// - `#[variant(coerce)]`
+///
+/// When converting from `Variant`, fall back to Godot's coercion rules if the strict conversion
+/// fails, using [`CoerceFromVariant::try_coerce_from_variant`]. For example, an `int` is accepted
+/// for an `f32` field, and a `String` for a `NodePath` field. Fields of types that do not
+/// implement `CoerceFromVariant`, including generic parameters, are converted strictly. Errors
+/// are still reported with the path to the offending field. Cannot be used together with
+/// `from_variant_with`.
 pub trait ToVariant {
     fn to_variant(&self) -> Variant;
 }
@@ -857,6 +1005,27 @@ pub trait FromVariant: Sized {
 /// [`FromVariant`].
 pub trait CoerceFromVariant: Sized + private::Sealed {
     fn coerce_from_variant(variant: &Variant) -> Self;
+
+    /// Converts from a `Variant` using [`FromVariant`], falling back to coercion if the type of
+    /// the `Variant` can be converted to `Self` according to Godot's rules, e.g. `int` to `float`,
+    /// `String` to `NodePath`, or `Array` to `PoolVector3Array`.
+    ///
+    /// Unlike [`coerce_from_variant`][Self::coerce_from_variant], this never coerces `Nil` or
+    /// incompatible types such as a `Dictionary` to a `Vector3`. The error from the strict
+    /// conversion is returned instead.
+    #[inline]
+    fn try_coerce_from_variant(variant: &Variant) -> Result<Self, FromVariantError>
+    where
+        Self: FromVariant,
+    {
+        Self::from_variant(variant).or_else(|err| {
+            if variant.get_type().can_coerce_to(Self::VARIANT_TYPE) {
+                Ok(Self::coerce_from_variant(variant))
+            } else {
+                Err(err)
+            }
+        })
+    }
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -932,6 +1101,9 @@ pub enum FromVariantError {
 #[non_exhaustive]
 pub enum VariantEnumRepr {
     ExternallyTagged,
+    InternallyTagged,
+    AdjacentlyTagged,
+    Untagged,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
@@ -1198,7 +1370,9 @@ macro_rules! impl_to_variant_for_num {
                 }
             }
 
-            impl private::Sealed for $ty {}
+            impl private::Sealed for $ty {
+                const VARIANT_TYPE: VariantType = <$src_ty as private::Sealed>::VARIANT_TYPE;
+            }
             impl CoerceFromVariant for $ty {
                 #[inline]
                 fn coerce_from_variant(variant: &Variant) -> Self {
@@ -1428,6 +1602,57 @@ impl FromVariant for String {
     }
 }
 
+impl private::Sealed for String {
+    const VARIANT_TYPE: VariantType = VariantType::GodotString;
+}
+impl CoerceFromVariant for String {
+    #[inline]
+    fn coerce_from_variant(variant: &Variant) -> Self {
+        GodotString::coerce_from_variant(variant).to_string()
+    }
+}
+
+impl<'a> ToVariant for Cow<'a, str> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.as_ref().to_variant()
+    }
+}
+impl<'a> ToVariantEq for Cow<'a, str> {}
+
+impl<'a> FromVariant for Cow<'a, str> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        String::from_variant(variant).map(Cow::Owned)
+    }
+}
+
+/// Converts the `char` to a `GodotString` of length 1, wrapped in a `Variant`.
+impl ToVariant for char {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        let mut buf = [0; 4];
+        self.encode_utf8(&mut buf).to_variant()
+    }
+}
+impl ToVariantEq for char {}
+
+/// Expects a `Variant` populated with a `GodotString` containing exactly one character.
+impl FromVariant for char {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let s = String::from_variant(variant)?;
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(FromVariantError::InvalidLength {
+                len: s.chars().count(),
+                expected: 1,
+            }),
+        }
+    }
+}
+
 impl ToVariant for Variant {
     #[inline]
     fn to_variant(&self) -> Variant {
@@ -1731,6 +1956,234 @@ impl<T: FromVariant + Eq + Hash> FromVariant for HashSet<T> {
     }
 }
 
+impl<T: ToVariant, const N: usize> ToVariant for [T; N] {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.as_slice().to_variant()
+    }
+}
+
+/// Expects a `Variant` populated with a `VariantArray` of exactly `N` elements.
+impl<T: FromVariant, const N: usize> FromVariant for [T; N] {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let arr = VariantArray::from_variant(variant)?;
+        let len: usize = arr
+            .len()
+            .try_into()
+            .expect("variant array length should fit in usize");
+        if len != N {
+            return Err(FromVariantError::InvalidLength { len, expected: N });
+        }
+
+        let vec = Vec::<T>::from_variant(variant)?;
+        match vec.try_into() {
+            Ok(array) => Ok(array),
+            Err(_) => unreachable!("length was checked above"),
+        }
+    }
+}
+
+impl<T: ToVariant> ToVariant for VecDeque<T> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        let array = VariantArray::new();
+        for val in self {
+            array.push(&val.to_variant());
+        }
+        array.owned_to_variant()
+    }
+}
+
+impl<T: FromVariant> FromVariant for VecDeque<T> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        Vec::<T>::from_variant(variant).map(VecDeque::from)
+    }
+}
+
+/// Converts the B-tree map to a `Dictionary`, wrapped in a `Variant`. Elements are inserted in
+/// the order of their keys.
+impl<K: ToVariant + ToVariantEq, V: ToVariant> ToVariant for BTreeMap<K, V> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        let dict = Dictionary::new();
+        for (key, value) in self {
+            dict.insert(key.to_variant(), value.to_variant());
+        }
+        dict.owned_to_variant()
+    }
+}
+
+/// Expects a `Variant` populated with a `Dictionary` and tries to convert it into a `BTreeMap`.
+impl<K: FromVariant + Ord, V: FromVariant> FromVariant for BTreeMap<K, V> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let dictionary = Dictionary::from_variant(variant)?;
+        let mut map = BTreeMap::new();
+        for (key, value) in dictionary.iter() {
+            map.insert(K::from_variant(&key)?, V::from_variant(&value)?);
+        }
+        Ok(map)
+    }
+}
+
+/// Converts the B-tree set to a `VariantArray`, wrapped in a `Variant`. Elements are pushed in
+/// ascending order.
+impl<T: ToVariant> ToVariant for BTreeSet<T> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        let array = VariantArray::new();
+        for value in self {
+            array.push(value.to_variant());
+        }
+        array.owned_to_variant()
+    }
+}
+
+/// Expects a `Variant` populated with a `VariantArray` and tries to convert it into a `BTreeSet`.
+impl<T: FromVariant + Ord> FromVariant for BTreeSet<T> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        Vec::<T>::from_variant(variant).map(BTreeSet::from_iter)
+    }
+}
+
+/// Converts the index map to a `Dictionary`, wrapped in a `Variant`.
+///
+/// Unlike `HashMap`, the insertion order of the `IndexMap` is preserved.
+impl<K: ToVariant + ToVariantEq, V: ToVariant, S> ToVariant for IndexMap<K, V, S> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        let dict = Dictionary::new();
+        for (key, value) in self {
+            dict.insert(key.to_variant(), value.to_variant());
+        }
+        dict.owned_to_variant()
+    }
+}
+
+/// Expects a `Variant` populated with a `Dictionary` and tries to convert it into an `IndexMap`.
+///
+/// The resulting map has the same element order as the `Dictionary`.
+impl<K, V, S> FromVariant for IndexMap<K, V, S>
+where
+    K: FromVariant + Hash + Eq,
+    V: FromVariant,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let dictionary = Dictionary::from_variant(variant)?;
+        let len: usize = dictionary
+            .len()
+            .try_into()
+            .expect("Dictionary length should fit in usize");
+
+        let mut map = IndexMap::with_capacity_and_hasher(len, S::default());
+        for (key, value) in dictionary.iter() {
+            map.insert(K::from_variant(&key)?, V::from_variant(&value)?);
+        }
+        Ok(map)
+    }
+}
+
+impl<T: ToVariant + ?Sized> ToVariant for Box<T> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.as_ref().to_variant()
+    }
+}
+impl<T: ToVariantEq + ?Sized> ToVariantEq for Box<T> {}
+
+impl<T: FromVariant> FromVariant for Box<T> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        T::from_variant(variant).map(Box::new)
+    }
+}
+
+impl<T: ToVariant + ?Sized> ToVariant for Rc<T> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.as_ref().to_variant()
+    }
+}
+impl<T: ToVariantEq + ?Sized> ToVariantEq for Rc<T> {}
+
+impl<T: ToVariant + ?Sized> ToVariant for Arc<T> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.as_ref().to_variant()
+    }
+}
+impl<T: ToVariantEq + ?Sized> ToVariantEq for Arc<T> {}
+
+macro_rules! impl_variant_for_non_zero {
+    (
+        $($ty:ty : $int_ty:ty)*
+    ) => {
+        $(
+            impl ToVariant for $ty {
+                #[inline]
+                fn to_variant(&self) -> Variant {
+                    self.get().to_variant()
+                }
+            }
+            impl ToVariantEq for $ty {}
+
+            impl FromVariant for $ty {
+                #[inline]
+                fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+                    let value = <$int_ty>::from_variant(variant)?;
+                    <$ty>::new(value).ok_or_else(|| {
+                        FromVariantError::custom("expected non-zero integer, got 0")
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_variant_for_non_zero!(
+    NonZeroI8: i8
+    NonZeroI16: i16
+    NonZeroI32: i32
+    NonZeroI64: i64
+    NonZeroIsize: isize
+    NonZeroU8: u8
+    NonZeroU16: u16
+    NonZeroU32: u32
+    NonZeroU64: u64
+    NonZeroUsize: usize
+);
+
+/// Converts the `Duration` to its length in seconds as a float, wrapped in a `Variant`.
+///
+/// This is the same unit used by Godot APIs such as `Timer::wait_time`.
+impl ToVariant for Duration {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.as_secs_f64().to_variant()
+    }
+}
+
+/// Expects a `Variant` populated with a float number of seconds.
+///
+/// Negative, non-finite or out-of-range values produce an error instead of panicking.
+impl FromVariant for Duration {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let secs = f64::from_variant(variant)?;
+        if !secs.is_finite() || secs < 0.0 || secs >= u64::MAX as f64 {
+            return Err(FromVariantError::custom(format_args!(
+                "expected a non-negative, finite number of seconds, got {secs}"
+            )));
+        }
+        Ok(Duration::from_secs_f64(secs))
+    }
+}
+
 macro_rules! tuple_length {
     () => { 0usize };
     ($_x:ident, $($xs:ident,)*) => {
@@ -1793,7 +2246,10 @@ macro_rules! impl_variant_for_tuples {
 impl_variant_for_tuples!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12,);
 
 mod private {
-    pub trait Sealed {}
+    pub trait Sealed {
+        /// The type that values are coerced to by Godot.
+        const VARIANT_TYPE: super::VariantType;
+    }
 }
 
 godot_test!(
@@ -1992,6 +2448,101 @@ godot_test!(
         );
     }
 
+    test_variant_btree {
+        let original_map = BTreeMap::from([
+            ("Foo".to_string(), 4u32),
+            ("Bar".to_string(), 2u32),
+        ]);
+        let variant = original_map.to_variant();
+        let dict = variant.try_to::<Dictionary>().expect("should be dictionary");
+        let keys: Vec<String> = dict.keys().iter().map(|k| k.to::<String>().unwrap()).collect();
+        assert_eq!(vec!["Bar".to_string(), "Foo".to_string()], keys);
+        assert_eq!(Ok(original_map), variant.try_to::<BTreeMap<String, u32>>());
+
+        let original_set = BTreeSet::from([3i64, 1, 2]);
+        let variant = original_set.to_variant();
+        assert_eq!(Ok(vec![1i64, 2, 3]), variant.try_to::<Vec<i64>>());
+        assert_eq!(Ok(original_set), variant.try_to::<BTreeSet<i64>>());
+    }
+
+    test_variant_index_map {
+        let mut original_map = IndexMap::new();
+        original_map.insert("Foo".to_string(), 4u32);
+        original_map.insert("Bar".to_string(), 2u32);
+        original_map.insert("Baz".to_string(), 7u32);
+
+        let variant = original_map.to_variant();
+        let dict = variant.try_to::<Dictionary>().expect("should be dictionary");
+        let keys: Vec<String> = dict.keys().iter().map(|k| k.to::<String>().unwrap()).collect();
+        assert_eq!(vec!["Foo".to_string(), "Bar".to_string(), "Baz".to_string()], keys);
+
+        let check_map = variant.try_to::<IndexMap<String, u32>>().expect("should be index map");
+        assert!(original_map.iter().eq(check_map.iter()));
+    }
+
+    test_variant_std_sequences {
+        let variant = [1i64, 2, 3].to_variant();
+        assert_eq!(Ok([1i64, 2, 3]), variant.try_to::<[i64; 3]>());
+        assert_eq!(
+            Err(FromVariantError::InvalidLength { len: 3, expected: 2 }),
+            variant.try_to::<[i64; 2]>(),
+        );
+
+        let deque = VecDeque::from([4i64, 5, 6]);
+        let variant = deque.to_variant();
+        assert_eq!(Ok(vec![4i64, 5, 6]), variant.try_to::<Vec<i64>>());
+        assert_eq!(Ok(deque), variant.try_to::<VecDeque<i64>>());
+    }
+
+    test_variant_std_wrappers {
+        assert_eq!(Ok(42), Box::new(42i64).to_variant().try_to::<i64>());
+        assert_eq!(Ok(Box::new(42i64)), 42i64.to_variant().try_to::<Box<i64>>());
+        assert_eq!(Ok(42), Rc::new(42i64).to_variant().try_to::<i64>());
+        assert_eq!(Ok(42), Arc::new(42i64).to_variant().try_to::<i64>());
+
+        let variant = Cow::Borrowed("foo").to_variant();
+        assert_eq!(Ok(Cow::Borrowed("foo")), variant.try_to::<Cow<'_, str>>());
+
+        let variant = 'ä'.to_variant();
+        assert_eq!(Ok("ä".to_string()), variant.try_to::<String>());
+        assert_eq!(Ok('ä'), variant.try_to::<char>());
+        assert_eq!(
+            Err(FromVariantError::InvalidLength { len: 3, expected: 1 }),
+            "foo".to_variant().try_to::<char>(),
+        );
+
+        let variant = NonZeroU32::new(7).unwrap().to_variant();
+        assert_eq!(Ok(7), variant.try_to::<u32>());
+        assert_eq!(NonZeroU32::new(7), variant.try_to::<NonZeroU32>().ok());
+        assert!(0i64.to_variant().try_to::<NonZeroI64>().is_err());
+
+        let variant = Duration::from_millis(1500).to_variant();
+        assert_eq!(Ok(1.5), variant.try_to::<f64>());
+        assert_eq!(Ok(Duration::from_millis(1500)), variant.try_to::<Duration>());
+        assert!((-1.0f64).to_variant().try_to::<Duration>().is_err());
+        assert!(f64::NAN.to_variant().try_to::<Duration>().is_err());
+    }
+
+    test_variant_try_coerce {
+        assert_eq!(Ok(42.0), f32::try_coerce_from_variant(&42i64.to_variant()));
+        assert_eq!(Ok(42), i64::try_coerce_from_variant(&42i64.to_variant()));
+        assert_eq!(Ok("42".to_string()), String::try_coerce_from_variant(&42i64.to_variant()));
+
+        let path = NodePath::try_coerce_from_variant(&"foo/bar".to_variant())
+            .expect("should coerce to NodePath");
+        assert_eq!("foo/bar", path.to_string());
+
+        let arr = VariantArray::new();
+        arr.push(Vector3::new(1.0, 2.0, 3.0));
+        let pool = PoolArray::<Vector3>::try_coerce_from_variant(&arr.into_shared().to_variant())
+            .expect("should coerce to PoolVector3Array");
+        assert_eq!(1, pool.len());
+        assert_eq!(Vector3::new(1.0, 2.0, 3.0), pool.get(0));
+
+        assert!(f32::try_coerce_from_variant(&Variant::nil()).is_err());
+        assert!(Vector3::try_coerce_from_variant(&Dictionary::new().into_shared().to_variant()).is_err());
+    }
+
     test_variant_tuple {
         let variant = (42i64, 54i64).to_variant();
         let arr = variant.try_to::<VariantArray>().expect("should be array");
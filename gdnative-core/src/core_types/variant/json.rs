@@ -0,0 +1,140 @@
+use super::*;
+use serde::{ser::Error as _, Deserialize};
+use serde_json::{Map, Value as Json};
+
+/// Representations used by [`Variant::to_json_value`] and [`Variant::from_json_value`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum JsonMode {
+    /// Natural JSON representation, for interoperability with other programs.
+    ///
+    /// Nil, bools, numbers, strings, arrays and dictionaries map to their JSON counterparts, and
+    /// node paths to strings. Math types and colors use their `serde` representations, e.g.
+    /// `{"x": 1.0, "y": 2.0}` for a `Vector2`, and pool arrays become arrays. Dictionary keys that
+    /// are not strings are written as JSON text. Non-finite floats become `null`.
+    ///
+    /// Type information is lost, so JSON values are converted back into nil, bools, ints for
+    /// integers that fit into `i64`, floats, strings, arrays and dictionaries only.
+    Plain,
+    /// Representation that tags each value with its [`VariantType`], e.g.
+    /// `{"Vector2": {"x": 1.0, "y": 2.0}}` or `{"I64": 42}`.
+    ///
+    /// This is the same representation used by the `serde` implementation of [`VariantDispatch`],
+    /// and round-trips exactly, except for non-finite floats, which JSON cannot represent.
+    Tagged,
+}
+
+impl Variant {
+    /// Converts this variant into a `serde_json::Value`, using the representation of `mode`.
+    ///
+    /// Unlike [`Dictionary::to_json`], this does not go through Godot's JSON printer, so ints
+    /// stay ints and no values are turned into strings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the variant contains an `Object` or a `Rid`, which have no JSON
+    /// representation.
+    #[inline]
+    pub fn to_json_value(&self, mode: JsonMode) -> Result<Json, serde_json::Error> {
+        match mode {
+            JsonMode::Plain => to_plain_json(self),
+            JsonMode::Tagged => serde_json::to_value(self.dispatch()),
+        }
+    }
+
+    /// Converts a `serde_json::Value` into a variant, using the representation of `mode`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `mode` is [`JsonMode::Tagged`] and `json` is not a valid tagged value.
+    /// Conversion in [`JsonMode::Plain`] never fails.
+    #[inline]
+    pub fn from_json_value(json: &Json, mode: JsonMode) -> Result<Variant, serde_json::Error> {
+        match mode {
+            JsonMode::Plain => Ok(from_plain_json(json)),
+            JsonMode::Tagged => {
+                VariantDispatch::deserialize(json).map(|dispatch| Variant::from(&dispatch))
+            }
+        }
+    }
+}
+
+fn to_plain_json(variant: &Variant) -> Result<Json, serde_json::Error> {
+    let json = match variant.dispatch() {
+        VariantDispatch::Nil => Json::Null,
+        VariantDispatch::Bool(v) => Json::Bool(v),
+        VariantDispatch::I64(v) => Json::from(v),
+        VariantDispatch::F64(v) => Json::from(v),
+        VariantDispatch::GodotString(v) => Json::String(v.to_string()),
+        VariantDispatch::Vector2(v) => serde_json::to_value(v)?,
+        VariantDispatch::Rect2(v) => serde_json::to_value(v)?,
+        VariantDispatch::Vector3(v) => serde_json::to_value(v)?,
+        VariantDispatch::Transform2D(v) => serde_json::to_value(v)?,
+        VariantDispatch::Plane(v) => serde_json::to_value(v)?,
+        VariantDispatch::Quat(v) => serde_json::to_value(v)?,
+        VariantDispatch::Aabb(v) => serde_json::to_value(v)?,
+        VariantDispatch::Basis(v) => serde_json::to_value(v)?,
+        VariantDispatch::Transform(v) => serde_json::to_value(v)?,
+        VariantDispatch::Color(v) => serde_json::to_value(v)?,
+        VariantDispatch::NodePath(v) => Json::String(v.to_string()),
+        VariantDispatch::Rid(_) => {
+            return Err(serde_json::Error::custom(
+                "Conversion of RID's to JSON is not supported",
+            ))
+        }
+        VariantDispatch::Object(_) => {
+            return Err(serde_json::Error::custom(
+                "Conversion of Objects to JSON is not supported",
+            ))
+        }
+        VariantDispatch::Dictionary(v) => {
+            let mut map = Map::new();
+            for (key, value) in v.iter() {
+                let key = match to_plain_json(&key)? {
+                    Json::String(key) => key,
+                    key => key.to_string(),
+                };
+                map.insert(key, to_plain_json(&value)?);
+            }
+            Json::Object(map)
+        }
+        VariantDispatch::VariantArray(v) => Json::Array(
+            v.iter()
+                .map(|value| to_plain_json(&value))
+                .collect::<Result<_, _>>()?,
+        ),
+        VariantDispatch::ByteArray(v) => serde_json::to_value(v)?,
+        VariantDispatch::Int32Array(v) => serde_json::to_value(v)?,
+        VariantDispatch::Float32Array(v) => serde_json::to_value(v)?,
+        VariantDispatch::StringArray(v) => serde_json::to_value(v)?,
+        VariantDispatch::Vector2Array(v) => serde_json::to_value(v)?,
+        VariantDispatch::Vector3Array(v) => serde_json::to_value(v)?,
+        VariantDispatch::ColorArray(v) => serde_json::to_value(v)?,
+    };
+
+    Ok(json)
+}
+
+fn from_plain_json(json: &Json) -> Variant {
+    match json {
+        Json::Null => Variant::nil(),
+        Json::Bool(v) => v.to_variant(),
+        Json::Number(v) => match v.as_i64() {
+            Some(int) => int.to_variant(),
+            None => v.as_f64().to_variant(),
+        },
+        Json::String(v) => v.to_variant(),
+        Json::Array(values) => values
+            .iter()
+            .map(from_plain_json)
+            .collect::<variant_array::VariantArray<Unique>>()
+            .owned_to_variant(),
+        Json::Object(map) => {
+            let dictionary = Dictionary::new();
+            for (key, value) in map {
+                dictionary.insert(key, from_plain_json(value));
+            }
+            dictionary.owned_to_variant()
+        }
+    }
+}
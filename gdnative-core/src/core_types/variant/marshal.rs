@@ -0,0 +1,971 @@
+//! Encoder and decoder for Godot's binary serialization format.
+//!
+//! This is the format produced by GDScript's `var2bytes` and consumed by `bytes2var`, and also
+//! used by `PacketPeer`, `StreamPeer::put_var` and `File::store_var`. The latter two prefix the
+//! encoded value with its length as a 32-bit integer, which is not part of the value itself.
+//!
+//! Unlike [`Variant`][crate::core_types::Variant], [`Value`] is plain Rust data, so values can be
+//! encoded and decoded without a running engine, e.g. in dedicated servers, tools or tests.
+//! Only the Godot 3 format is supported.
+//!
+//! ```
+//! use gdnative::core_types::marshal::{self, Value};
+//!
+//! let bytes = marshal::encode(&Value::I64(42));
+//! assert_eq!(&[2, 0, 0, 0, 42, 0, 0, 0], bytes.as_slice());
+//! assert_eq!(Ok(Value::I64(42)), marshal::decode(&bytes, false));
+//! ```
+
+use std::fmt;
+
+use crate::core_types::{
+    Aabb, Basis, Color, Plane, Quat, Rect2, Transform, Transform2D, VariantType, Vector2, Vector3,
+};
+
+pub use super::value::{NodePathValue, ObjectValue, Value};
+
+const ENCODE_MASK: u32 = 0xff;
+const ENCODE_FLAG_64: u32 = 1 << 16;
+const ENCODE_FLAG_OBJECT_AS_ID: u32 = 1 << 16;
+
+/// Flag set on the name count of node paths, distinguishing them from the obsolete format.
+const NODE_PATH_NEW_FORMAT: u32 = 0x8000_0000;
+const NODE_PATH_FLAG_ABSOLUTE: u32 = 1;
+/// Obsolete flag indicating that the property is stored separately from the subnames.
+const NODE_PATH_FLAG_PROPERTY: u32 = 2;
+
+/// Maximum nesting depth of collections and objects accepted by the decoder.
+const MAX_DEPTH: usize = 128;
+
+/// All variant types, indexed by their numeric values in the binary format.
+const VARIANT_TYPES: [VariantType; 27] = [
+    VariantType::Nil,
+    VariantType::Bool,
+    VariantType::I64,
+    VariantType::F64,
+    VariantType::GodotString,
+    VariantType::Vector2,
+    VariantType::Rect2,
+    VariantType::Vector3,
+    VariantType::Transform2D,
+    VariantType::Plane,
+    VariantType::Quat,
+    VariantType::Aabb,
+    VariantType::Basis,
+    VariantType::Transform,
+    VariantType::Color,
+    VariantType::NodePath,
+    VariantType::Rid,
+    VariantType::Object,
+    VariantType::Dictionary,
+    VariantType::VariantArray,
+    VariantType::ByteArray,
+    VariantType::Int32Array,
+    VariantType::Float32Array,
+    VariantType::StringArray,
+    VariantType::Vector2Array,
+    VariantType::Vector3Array,
+    VariantType::ColorArray,
+];
+
+/// Error returned by [`decode`] and [`decode_prefix`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The input ended before the value was fully read.
+    UnexpectedEof,
+    /// The header of a value contains an unknown type.
+    UnknownType(u32),
+    /// A string is not valid UTF-8.
+    InvalidUtf8,
+    /// A node path is stored in the obsolete format, which is no longer supported by Godot.
+    ObsoleteNodePath,
+    /// A full object was found while decoding with `allow_objects` set to `false`.
+    ObjectsNotAllowed,
+    /// Collections or objects are nested too deeply.
+    DepthLimitExceeded,
+}
+
+impl fmt::Display for DecodeError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEof => write!(f, "unexpected end of input"),
+            DecodeError::UnknownType(ty) => write!(f, "unknown variant type {ty}"),
+            DecodeError::InvalidUtf8 => write!(f, "string is not valid UTF-8"),
+            DecodeError::ObsoleteNodePath => write!(f, "node path uses the obsolete format"),
+            DecodeError::ObjectsNotAllowed => write!(f, "decoding objects is not allowed"),
+            DecodeError::DepthLimitExceeded => {
+                write!(f, "nesting depth exceeds the limit of {MAX_DEPTH}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Encodes a value in Godot's binary serialization format, like `var2bytes`.
+///
+/// Objects are encoded in full if they are [`Value::Object`], or as their instance IDs if they
+/// are [`Value::ObjectId`].
+///
+/// # Panics
+///
+/// If a string or collection is longer than `i32::MAX`.
+#[inline]
+pub fn encode(value: &Value) -> Vec<u8> {
+    let mut buf = Vec::new();
+    encode_into(value, &mut buf);
+    buf
+}
+
+/// Appends the encoding of a value to `buf`. See [`encode`].
+///
+/// # Panics
+///
+/// If a string or collection is longer than `i32::MAX`.
+#[inline]
+pub fn encode_into(value: &Value, buf: &mut Vec<u8>) {
+    Writer { buf }.value(value);
+}
+
+/// Decodes a value from Godot's binary serialization format, like `bytes2var`. Any bytes after
+/// the value are ignored.
+///
+/// If `allow_objects` is `false`, objects encoded in full are rejected, as in Godot. Objects
+/// encoded as instance IDs are always allowed.
+#[inline]
+pub fn decode(bytes: &[u8], allow_objects: bool) -> Result<Value, DecodeError> {
+    decode_prefix(bytes, allow_objects).map(|(value, _)| value)
+}
+
+/// Decodes a value from the start of `bytes`, returning it along with the number of bytes read.
+/// See [`decode`].
+#[inline]
+pub fn decode_prefix(bytes: &[u8], allow_objects: bool) -> Result<(Value, usize), DecodeError> {
+    let mut reader = Reader {
+        bytes,
+        pos: 0,
+        allow_objects,
+        depth: 0,
+    };
+    let value = reader.value()?;
+    Ok((value, reader.pos))
+}
+
+/// Returns the number of padding bytes after `len` bytes of data.
+fn padding(len: usize) -> usize {
+    (4 - len % 4) % 4
+}
+
+struct Writer<'a> {
+    buf: &'a mut Vec<u8>,
+}
+
+impl Writer<'_> {
+    fn value(&mut self, value: &Value) {
+        let ty = value.get_type() as u32;
+
+        match value {
+            Value::Nil => self.u32(ty),
+            Value::Bool(b) => {
+                self.u32(ty);
+                self.u32(*b as u32);
+            }
+            Value::I64(i) => match i32::try_from(*i) {
+                Ok(i) => {
+                    self.u32(ty);
+                    self.buf.extend_from_slice(&i.to_le_bytes());
+                }
+                Err(_) => {
+                    self.u32(ty | ENCODE_FLAG_64);
+                    self.buf.extend_from_slice(&i.to_le_bytes());
+                }
+            },
+            Value::F64(f) => {
+                if (*f as f32) as f64 == *f {
+                    self.u32(ty);
+                    self.f32(*f as f32);
+                } else {
+                    self.u32(ty | ENCODE_FLAG_64);
+                    self.buf.extend_from_slice(&f.to_le_bytes());
+                }
+            }
+            Value::GodotString(s) => {
+                self.u32(ty);
+                self.string(s);
+            }
+            Value::Vector2(v) => {
+                self.u32(ty);
+                self.vector2(v);
+            }
+            Value::Rect2(r) => {
+                self.u32(ty);
+                self.vector2(&r.position);
+                self.vector2(&r.size);
+            }
+            Value::Vector3(v) => {
+                self.u32(ty);
+                self.vector3(v);
+            }
+            Value::Transform2D(t) => {
+                self.u32(ty);
+                self.vector2(&t.a);
+                self.vector2(&t.b);
+                self.vector2(&t.origin);
+            }
+            Value::Plane(p) => {
+                self.u32(ty);
+                self.vector3(&p.normal);
+                self.f32(p.d);
+            }
+            Value::Quat(q) => {
+                self.u32(ty);
+                for f in [q.x, q.y, q.z, q.w] {
+                    self.f32(f);
+                }
+            }
+            Value::Aabb(a) => {
+                self.u32(ty);
+                self.vector3(&a.position);
+                self.vector3(&a.size);
+            }
+            Value::Basis(b) => {
+                self.u32(ty);
+                self.basis(b);
+            }
+            Value::Transform(t) => {
+                self.u32(ty);
+                self.basis(&t.basis);
+                self.vector3(&t.origin);
+            }
+            Value::Color(c) => {
+                self.u32(ty);
+                self.color(c);
+            }
+            Value::NodePath(path) => {
+                self.u32(ty);
+                self.u32(self.len(path.names.len()) | NODE_PATH_NEW_FORMAT);
+                self.u32(self.len(path.subnames.len()));
+                self.u32(if path.absolute {
+                    NODE_PATH_FLAG_ABSOLUTE
+                } else {
+                    0
+                });
+                for s in path.names.iter().chain(&path.subnames) {
+                    self.string(s);
+                }
+            }
+            Value::Rid => self.u32(ty),
+            Value::Object(None) => {
+                self.u32(ty);
+                self.u32(0);
+            }
+            Value::Object(Some(object)) => {
+                self.u32(ty);
+                self.string(&object.class);
+                self.u32(self.len(object.properties.len()));
+                for (name, value) in &object.properties {
+                    self.string(name);
+                    self.value(value);
+                }
+            }
+            Value::ObjectId(id) => {
+                self.u32(ty | ENCODE_FLAG_OBJECT_AS_ID);
+                self.buf.extend_from_slice(&id.to_le_bytes());
+            }
+            Value::Dictionary(entries) => {
+                self.u32(ty);
+                self.u32(self.len(entries.len()));
+                for (key, value) in entries {
+                    self.value(key);
+                    self.value(value);
+                }
+            }
+            Value::VariantArray(items) => {
+                self.u32(ty);
+                self.u32(self.len(items.len()));
+                for item in items {
+                    self.value(item);
+                }
+            }
+            Value::ByteArray(bytes) => {
+                self.u32(ty);
+                self.u32(self.len(bytes.len()));
+                self.buf.extend_from_slice(bytes);
+                self.pad(bytes.len());
+            }
+            Value::Int32Array(ints) => {
+                self.u32(ty);
+                self.u32(self.len(ints.len()));
+                for i in ints {
+                    self.buf.extend_from_slice(&i.to_le_bytes());
+                }
+            }
+            Value::Float32Array(floats) => {
+                self.u32(ty);
+                self.u32(self.len(floats.len()));
+                for f in floats {
+                    self.f32(*f);
+                }
+            }
+            Value::StringArray(strings) => {
+                self.u32(ty);
+                self.u32(self.len(strings.len()));
+                for s in strings {
+                    // Strings in arrays are stored with a null terminator, unlike other strings.
+                    self.u32(self.len(s.len() + 1));
+                    self.buf.extend_from_slice(s.as_bytes());
+                    self.buf.push(0);
+                    self.pad(s.len() + 1);
+                }
+            }
+            Value::Vector2Array(vectors) => {
+                self.u32(ty);
+                self.u32(self.len(vectors.len()));
+                for v in vectors {
+                    self.vector2(v);
+                }
+            }
+            Value::Vector3Array(vectors) => {
+                self.u32(ty);
+                self.u32(self.len(vectors.len()));
+                for v in vectors {
+                    self.vector3(v);
+                }
+            }
+            Value::ColorArray(colors) => {
+                self.u32(ty);
+                self.u32(self.len(colors.len()));
+                for c in colors {
+                    self.color(c);
+                }
+            }
+        }
+    }
+
+    fn len(&self, len: usize) -> u32 {
+        assert!(
+            len <= i32::MAX as usize,
+            "length {len} is too large to be encoded"
+        );
+        len as u32
+    }
+
+    fn pad(&mut self, len: usize) {
+        self.buf.resize(self.buf.len() + padding(len), 0);
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn f32(&mut self, value: f32) {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn string(&mut self, s: &str) {
+        self.u32(self.len(s.len()));
+        self.buf.extend_from_slice(s.as_bytes());
+        self.pad(s.len());
+    }
+
+    fn vector2(&mut self, v: &Vector2) {
+        self.f32(v.x);
+        self.f32(v.y);
+    }
+
+    fn vector3(&mut self, v: &Vector3) {
+        self.f32(v.x);
+        self.f32(v.y);
+        self.f32(v.z);
+    }
+
+    fn basis(&mut self, b: &Basis) {
+        for row in &b.elements {
+            self.vector3(row);
+        }
+    }
+
+    fn color(&mut self, c: &Color) {
+        for f in [c.r, c.g, c.b, c.a] {
+            self.f32(f);
+        }
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    allow_objects: bool,
+    depth: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn value(&mut self) -> Result<Value, DecodeError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(DecodeError::DepthLimitExceeded);
+        }
+
+        self.depth += 1;
+        let value = self.value_inner();
+        self.depth -= 1;
+        value
+    }
+
+    fn value_inner(&mut self) -> Result<Value, DecodeError> {
+        let header = self.u32()?;
+        let ty = header & ENCODE_MASK;
+        let ty = *VARIANT_TYPES
+            .get(ty as usize)
+            .ok_or(DecodeError::UnknownType(ty))?;
+
+        let value = match ty {
+            VariantType::Nil => Value::Nil,
+            VariantType::Bool => Value::Bool(self.u32()? != 0),
+            VariantType::I64 => {
+                if header & ENCODE_FLAG_64 != 0 {
+                    Value::I64(i64::from_le_bytes(self.array()?))
+                } else {
+                    Value::I64(i32::from_le_bytes(self.array()?).into())
+                }
+            }
+            VariantType::F64 => {
+                if header & ENCODE_FLAG_64 != 0 {
+                    Value::F64(f64::from_le_bytes(self.array()?))
+                } else {
+                    Value::F64(self.f32()?.into())
+                }
+            }
+            VariantType::GodotString => Value::GodotString(self.string()?),
+            VariantType::Vector2 => Value::Vector2(self.vector2()?),
+            VariantType::Rect2 => Value::Rect2(Rect2 {
+                position: self.vector2()?,
+                size: self.vector2()?,
+            }),
+            VariantType::Vector3 => Value::Vector3(self.vector3()?),
+            VariantType::Transform2D => Value::Transform2D(Transform2D {
+                a: self.vector2()?,
+                b: self.vector2()?,
+                origin: self.vector2()?,
+            }),
+            VariantType::Plane => Value::Plane(Plane {
+                normal: self.vector3()?,
+                d: self.f32()?,
+            }),
+            VariantType::Quat => Value::Quat(Quat {
+                x: self.f32()?,
+                y: self.f32()?,
+                z: self.f32()?,
+                w: self.f32()?,
+            }),
+            VariantType::Aabb => Value::Aabb(Aabb {
+                position: self.vector3()?,
+                size: self.vector3()?,
+            }),
+            VariantType::Basis => Value::Basis(self.basis()?),
+            VariantType::Transform => Value::Transform(Transform {
+                basis: self.basis()?,
+                origin: self.vector3()?,
+            }),
+            VariantType::Color => Value::Color(self.color()?),
+            VariantType::NodePath => Value::NodePath(self.node_path()?),
+            VariantType::Rid => Value::Rid,
+            VariantType::Object => self.object(header)?,
+            VariantType::Dictionary => {
+                let count = self.count()?;
+                let mut entries = Vec::with_capacity(self.capacity(count, 8));
+                for _ in 0..count {
+                    let key = self.value()?;
+                    let value = self.value()?;
+                    entries.push((key, value));
+                }
+                Value::Dictionary(entries)
+            }
+            VariantType::VariantArray => {
+                let count = self.count()?;
+                let mut items = Vec::with_capacity(self.capacity(count, 4));
+                for _ in 0..count {
+                    items.push(self.value()?);
+                }
+                Value::VariantArray(items)
+            }
+            VariantType::ByteArray => {
+                let len = self.u32()? as usize;
+                let bytes = self.take(len)?.to_vec();
+                // Godot does not require the padding after the last byte array in the input.
+                self.pos = (self.pos + padding(len)).min(self.bytes.len());
+                Value::ByteArray(bytes)
+            }
+            VariantType::Int32Array => {
+                Value::Int32Array(self.pool(4, |r| Ok(i32::from_le_bytes(r.array()?)))?)
+            }
+            VariantType::Float32Array => Value::Float32Array(self.pool(4, Self::f32)?),
+            VariantType::StringArray => Value::StringArray(self.pool(4, Self::string)?),
+            VariantType::Vector2Array => Value::Vector2Array(self.pool(8, Self::vector2)?),
+            VariantType::Vector3Array => Value::Vector3Array(self.pool(12, Self::vector3)?),
+            VariantType::ColorArray => Value::ColorArray(self.pool(16, Self::color)?),
+        };
+
+        Ok(value)
+    }
+
+    fn object(&mut self, header: u32) -> Result<Value, DecodeError> {
+        if header & ENCODE_FLAG_OBJECT_AS_ID != 0 {
+            return Ok(Value::ObjectId(u64::from_le_bytes(self.array()?)));
+        }
+
+        if !self.allow_objects {
+            return Err(DecodeError::ObjectsNotAllowed);
+        }
+
+        let class = self.string()?;
+        if class.is_empty() {
+            return Ok(Value::Object(None));
+        }
+
+        let count = self.u32()? as usize;
+        let mut properties = Vec::with_capacity(self.capacity(count, 8));
+        for _ in 0..count {
+            let name = self.string()?;
+            let value = self.value()?;
+            properties.push((name, value));
+        }
+
+        Ok(Value::Object(Some(Box::new(ObjectValue {
+            class,
+            properties,
+        }))))
+    }
+
+    fn node_path(&mut self) -> Result<NodePathValue, DecodeError> {
+        let name_count = self.u32()?;
+        if name_count & NODE_PATH_NEW_FORMAT == 0 {
+            return Err(DecodeError::ObsoleteNodePath);
+        }
+
+        let name_count = (name_count & !NODE_PATH_NEW_FORMAT) as usize;
+        let mut subname_count = self.u32()? as usize;
+        let flags = self.u32()?;
+        if flags & NODE_PATH_FLAG_PROPERTY != 0 {
+            subname_count += 1;
+        }
+
+        let mut names = Vec::with_capacity(self.capacity(name_count, 4));
+        for _ in 0..name_count {
+            names.push(self.string()?);
+        }
+
+        let mut subnames = Vec::with_capacity(self.capacity(subname_count, 4));
+        for _ in 0..subname_count {
+            subnames.push(self.string()?);
+        }
+
+        Ok(NodePathValue {
+            names,
+            subnames,
+            absolute: flags & NODE_PATH_FLAG_ABSOLUTE != 0,
+        })
+    }
+
+    /// Reads a count of elements, followed by the elements themselves.
+    fn pool<T>(
+        &mut self,
+        min_size: usize,
+        mut read: impl FnMut(&mut Self) -> Result<T, DecodeError>,
+    ) -> Result<Vec<T>, DecodeError> {
+        let count = self.u32()? as usize;
+        let mut items = Vec::with_capacity(self.capacity(count, min_size));
+        for _ in 0..count {
+            items.push(read(self)?);
+        }
+        Ok(items)
+    }
+
+    /// Returns a capacity for `count` elements, limited by the remaining input so that malformed
+    /// counts can't cause large allocations.
+    fn capacity(&self, count: usize, min_size: usize) -> usize {
+        count.min((self.bytes.len() - self.pos) / min_size)
+    }
+
+    /// Reads the number of elements in a `Dictionary` or `VariantArray`. The highest bit is
+    /// ignored, as it was once used to mark shared collections.
+    fn count(&mut self) -> Result<usize, DecodeError> {
+        Ok((self.u32()? & 0x7fff_ffff) as usize)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or(DecodeError::UnexpectedEof)?;
+        let bytes = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u32(&mut self) -> Result<u32, DecodeError> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    fn f32(&mut self) -> Result<f32, DecodeError> {
+        self.array().map(f32::from_le_bytes)
+    }
+
+    fn string(&mut self) -> Result<String, DecodeError> {
+        let len = self.u32()? as usize;
+        let bytes = self.take(len)?;
+        self.take(padding(len))?;
+
+        // Godot stops reading strings at the first null byte, which terminates strings in arrays.
+        let bytes = bytes.split(|&b| b == 0).next().unwrap_or_default();
+        String::from_utf8(bytes.to_vec()).map_err(|_| DecodeError::InvalidUtf8)
+    }
+
+    fn vector2(&mut self) -> Result<Vector2, DecodeError> {
+        Ok(Vector2::new(self.f32()?, self.f32()?))
+    }
+
+    fn vector3(&mut self) -> Result<Vector3, DecodeError> {
+        Ok(Vector3::new(self.f32()?, self.f32()?, self.f32()?))
+    }
+
+    fn basis(&mut self) -> Result<Basis, DecodeError> {
+        Ok(Basis {
+            elements: [self.vector3()?, self.vector3()?, self.vector3()?],
+        })
+    }
+
+    fn color(&mut self) -> Result<Color, DecodeError> {
+        Ok(Color::from_rgba(
+            self.f32()?,
+            self.f32()?,
+            self.f32()?,
+            self.f32()?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE: [u8; 4] = [0x00, 0x00, 0x80, 0x3f];
+    const TWO: [u8; 4] = [0x00, 0x00, 0x00, 0x40];
+    const THREE: [u8; 4] = [0x00, 0x00, 0x40, 0x40];
+    const ZERO: [u8; 4] = [0x00, 0x00, 0x00, 0x00];
+
+    /// Values and their encodings, as produced by `var2bytes` in Godot 3.
+    fn fixtures() -> Vec<(Value, Vec<u8>)> {
+        vec![
+            (Value::Nil, vec![0, 0, 0, 0]),
+            (Value::Bool(true), vec![1, 0, 0, 0, 1, 0, 0, 0]),
+            (Value::I64(42), vec![2, 0, 0, 0, 42, 0, 0, 0]),
+            (Value::I64(-2), vec![2, 0, 0, 0, 0xfe, 0xff, 0xff, 0xff]),
+            (
+                Value::I64(1 << 40),
+                vec![2, 0, 1, 0, 0, 0, 0, 0, 0, 1, 0, 0],
+            ),
+            (Value::F64(1.5), vec![3, 0, 0, 0, 0x00, 0x00, 0xc0, 0x3f]),
+            (
+                Value::F64(0.1),
+                vec![3, 0, 1, 0, 0x9a, 0x99, 0x99, 0x99, 0x99, 0x99, 0xb9, 0x3f],
+            ),
+            (
+                Value::GodotString("hi".into()),
+                vec![4, 0, 0, 0, 2, 0, 0, 0, b'h', b'i', 0, 0],
+            ),
+            (
+                Value::GodotString("héllo".into()),
+                vec![
+                    4, 0, 0, 0, 6, 0, 0, 0, b'h', 0xc3, 0xa9, b'l', b'l', b'o', 0, 0,
+                ],
+            ),
+            (
+                Value::Vector2(Vector2::new(1.0, 2.0)),
+                [&[5, 0, 0, 0][..], &ONE, &TWO].concat(),
+            ),
+            (
+                Value::Rect2(Rect2 {
+                    position: Vector2::new(0.0, 1.0),
+                    size: Vector2::new(2.0, 3.0),
+                }),
+                [&[6, 0, 0, 0][..], &ZERO, &ONE, &TWO, &THREE].concat(),
+            ),
+            (
+                Value::Vector3(Vector3::new(1.0, 2.0, 3.0)),
+                [&[7, 0, 0, 0][..], &ONE, &TWO, &THREE].concat(),
+            ),
+            (
+                Value::Transform2D(Transform2D {
+                    a: Vector2::new(1.0, 0.0),
+                    b: Vector2::new(0.0, 1.0),
+                    origin: Vector2::new(2.0, 3.0),
+                }),
+                [&[8, 0, 0, 0][..], &ONE, &ZERO, &ZERO, &ONE, &TWO, &THREE].concat(),
+            ),
+            (
+                Value::Plane(Plane {
+                    normal: Vector3::new(0.0, 1.0, 0.0),
+                    d: 2.0,
+                }),
+                [&[9, 0, 0, 0][..], &ZERO, &ONE, &ZERO, &TWO].concat(),
+            ),
+            (
+                Value::Quat(Quat {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 0.0,
+                    w: 1.0,
+                }),
+                [&[10, 0, 0, 0][..], &ZERO, &ZERO, &ZERO, &ONE].concat(),
+            ),
+            (
+                Value::Aabb(Aabb {
+                    position: Vector3::new(0.0, 0.0, 0.0),
+                    size: Vector3::new(1.0, 2.0, 3.0),
+                }),
+                [&[11, 0, 0, 0][..], &ZERO, &ZERO, &ZERO, &ONE, &TWO, &THREE].concat(),
+            ),
+            (
+                Value::Basis(Basis {
+                    elements: [
+                        Vector3::new(1.0, 0.0, 0.0),
+                        Vector3::new(0.0, 2.0, 0.0),
+                        Vector3::new(0.0, 0.0, 3.0),
+                    ],
+                }),
+                [
+                    &[12, 0, 0, 0][..],
+                    &ONE,
+                    &ZERO,
+                    &ZERO,
+                    &ZERO,
+                    &TWO,
+                    &ZERO,
+                    &ZERO,
+                    &ZERO,
+                    &THREE,
+                ]
+                .concat(),
+            ),
+            (
+                Value::Transform(Transform {
+                    basis: Basis {
+                        elements: [
+                            Vector3::new(1.0, 0.0, 0.0),
+                            Vector3::new(0.0, 1.0, 0.0),
+                            Vector3::new(0.0, 0.0, 1.0),
+                        ],
+                    },
+                    origin: Vector3::new(1.0, 2.0, 3.0),
+                }),
+                [
+                    &[13, 0, 0, 0][..],
+                    &ONE,
+                    &ZERO,
+                    &ZERO,
+                    &ZERO,
+                    &ONE,
+                    &ZERO,
+                    &ZERO,
+                    &ZERO,
+                    &ONE,
+                    &ONE,
+                    &TWO,
+                    &THREE,
+                ]
+                .concat(),
+            ),
+            (
+                Value::Color(Color::from_rgba(1.0, 0.0, 0.0, 1.0)),
+                [&[14, 0, 0, 0][..], &ONE, &ZERO, &ZERO, &ONE].concat(),
+            ),
+            (
+                Value::NodePath("/root/Player:position:x".into()),
+                [
+                    &[15, 0, 0, 0, 2, 0, 0, 0x80, 2, 0, 0, 0, 1, 0, 0, 0][..],
+                    &[4, 0, 0, 0],
+                    b"root",
+                    &[6, 0, 0, 0],
+                    b"Player\0\0",
+                    &[8, 0, 0, 0],
+                    b"position",
+                    &[1, 0, 0, 0],
+                    b"x\0\0\0",
+                ]
+                .concat(),
+            ),
+            (Value::Rid, vec![16, 0, 0, 0]),
+            (
+                Value::ObjectId(1234),
+                vec![17, 0, 1, 0, 0xd2, 0x04, 0, 0, 0, 0, 0, 0],
+            ),
+            (
+                Value::ObjectId(0),
+                vec![17, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+            ),
+            (Value::Object(None), vec![17, 0, 0, 0, 0, 0, 0, 0]),
+            (
+                Value::Dictionary(vec![(Value::GodotString("a".into()), Value::I64(1))]),
+                vec![
+                    18, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 1, 0, 0, 0, b'a', 0, 0, 0, 2, 0, 0, 0, 1,
+                    0, 0, 0,
+                ],
+            ),
+            (
+                Value::VariantArray(vec![Value::I64(1), Value::GodotString("a".into())]),
+                vec![
+                    19, 0, 0, 0, 2, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 1, 0, 0, 0, b'a',
+                    0, 0, 0,
+                ],
+            ),
+            (
+                Value::ByteArray(vec![1, 2, 3]),
+                vec![20, 0, 0, 0, 3, 0, 0, 0, 1, 2, 3, 0],
+            ),
+            (
+                Value::Int32Array(vec![1, -1]),
+                vec![21, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 0xff, 0xff, 0xff, 0xff],
+            ),
+            (
+                Value::Float32Array(vec![1.0, 2.0]),
+                [&[22, 0, 0, 0, 2, 0, 0, 0][..], &ONE, &TWO].concat(),
+            ),
+            (
+                Value::StringArray(vec!["a".into(), "bcd".into()]),
+                [
+                    &[23, 0, 0, 0, 2, 0, 0, 0][..],
+                    &[2, 0, 0, 0],
+                    b"a\0\0\0",
+                    &[4, 0, 0, 0],
+                    b"bcd\0",
+                ]
+                .concat(),
+            ),
+            (
+                Value::Vector2Array(vec![Vector2::new(1.0, 2.0)]),
+                [&[24, 0, 0, 0, 1, 0, 0, 0][..], &ONE, &TWO].concat(),
+            ),
+            (
+                Value::Vector3Array(vec![Vector3::new(1.0, 2.0, 3.0)]),
+                [&[25, 0, 0, 0, 1, 0, 0, 0][..], &ONE, &TWO, &THREE].concat(),
+            ),
+            (
+                Value::ColorArray(vec![Color::from_rgba(0.0, 1.0, 2.0, 3.0)]),
+                [&[26, 0, 0, 0, 1, 0, 0, 0][..], &ZERO, &ONE, &TWO, &THREE].concat(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn variant_types() {
+        for (i, ty) in VARIANT_TYPES.iter().enumerate() {
+            assert_eq!(i as u32, *ty as u32, "{ty:?}");
+        }
+    }
+
+    #[test]
+    fn encode_fixtures() {
+        for (value, bytes) in fixtures() {
+            assert_eq!(bytes, encode(&value), "{value:?}");
+        }
+    }
+
+    #[test]
+    fn decode_fixtures() {
+        for (value, bytes) in fixtures() {
+            assert_eq!(Ok((value, bytes.len())), decode_prefix(&bytes, true));
+        }
+    }
+
+    #[test]
+    fn round_trip_object() {
+        let object = Value::Object(Some(Box::new(ObjectValue {
+            class: "Resource".into(),
+            properties: vec![
+                ("resource_name".into(), Value::GodotString("foo".into())),
+                ("script".into(), Value::Object(None)),
+            ],
+        })));
+
+        let bytes = encode(&object);
+        assert_eq!(
+            &[17, 0, 0, 0, 8, 0, 0, 0][..],
+            &bytes[..8],
+            "objects should be encoded in full"
+        );
+        assert_eq!(Ok(object), decode(&bytes, true));
+        assert_eq!(Err(DecodeError::ObjectsNotAllowed), decode(&bytes, false));
+
+        let null = encode(&Value::Object(None));
+        assert_eq!(Err(DecodeError::ObjectsNotAllowed), decode(&null, false));
+        assert_eq!(
+            Ok(Value::ObjectId(0)),
+            decode(&encode(&Value::ObjectId(0)), false)
+        );
+    }
+
+    #[test]
+    fn decode_trailing_bytes() {
+        let bytes = [2, 0, 0, 0, 42, 0, 0, 0, 0xff];
+        assert_eq!(Ok(Value::I64(42)), decode(&bytes, false));
+        assert_eq!(Ok((Value::I64(42), 8)), decode_prefix(&bytes, false));
+    }
+
+    #[test]
+    fn decode_malformed() {
+        for (value, bytes) in fixtures() {
+            assert_eq!(
+                Err(DecodeError::UnexpectedEof),
+                decode(&bytes[..bytes.len() - 4], true),
+                "{value:?}"
+            );
+        }
+
+        assert_eq!(
+            Err(DecodeError::UnknownType(27)),
+            decode(&[27, 0, 0, 0], false)
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidUtf8),
+            decode(&[4, 0, 0, 0, 1, 0, 0, 0, 0xff, 0, 0, 0], false)
+        );
+        assert_eq!(
+            Err(DecodeError::ObsoleteNodePath),
+            decode(&[15, 0, 0, 0, 1, 0, 0, 0, b'a', 0, 0, 0], false)
+        );
+        assert_eq!(
+            Err(DecodeError::UnexpectedEof),
+            decode(&[19, 0, 0, 0, 0xff, 0xff, 0xff, 0x7f], false)
+        );
+
+        let nested = [19, 0, 0, 0, 1, 0, 0, 0].repeat(MAX_DEPTH + 1);
+        assert_eq!(Err(DecodeError::DepthLimitExceeded), decode(&nested, false));
+    }
+
+    #[test]
+    fn node_path_strings() {
+        for path in [
+            "/root/Player:position:x",
+            "Player",
+            ":position",
+            "../a/b",
+            "",
+        ] {
+            assert_eq!(path, NodePathValue::from(path).to_string());
+        }
+
+        let path = NodePathValue::from("a//b:c");
+        assert_eq!(vec!["a", "b"], path.names);
+        assert_eq!(vec!["c"], path.subnames);
+        assert!(!path.absolute);
+    }
+}
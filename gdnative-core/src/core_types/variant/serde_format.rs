@@ -0,0 +1,105 @@
+//! `serde` data format that converts between `Variant`s and any types implementing `Serialize`
+//! and `Deserialize`.
+//!
+//! This allows types that already support `serde` to cross the GDNative boundary without
+//! implementing [`ToVariant`][crate::core_types::ToVariant] and
+//! [`FromVariant`][crate::core_types::FromVariant]. The `serde` data model is mapped as follows:
+//!
+//! - Structs and maps become `Dictionary`s. Struct field names become string keys.
+//! - Sequences and tuples become `VariantArray`s, and byte buffers `PoolByteArray`s.
+//! - Unit, unit structs and `None` become nil.
+//! - Enums are externally tagged: unit variants become their names as strings, and other
+//!   variants single-entry `Dictionary`s mapping their names to their contents.
+//!
+//! Core types such as `Vector2` or `PoolArray`s are serialized using their own `serde`
+//! representations, e.g. `Vector2` becomes a `Dictionary` with the keys `"x"` and `"y"`. When
+//! deserializing, both these representations and the actual core types are accepted, so values
+//! converted with `ToVariant` can be deserialized as well.
+//!
+//! ```ignore
+//! use gdnative::prelude::*;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Config {
+//!     name: String,
+//!     volume: f32,
+//! }
+//!
+//! let variant = gdnative::serde::to_variant(&Config { name: "default".into(), volume: 0.8 })?;
+//! let config: Config = gdnative::serde::from_variant(&variant)?;
+//! ```
+
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::{de, ser, Serialize};
+
+use crate::core_types::Variant;
+
+mod deserializer;
+mod serializer;
+
+use deserializer::Deserializer;
+use serializer::Serializer;
+
+/// Converts a value into a `Variant` using its `Serialize` implementation.
+///
+/// # Errors
+///
+/// Returns an error if the `Serialize` implementation fails, or the value contains an unsigned
+/// integer that does not fit into `i64`.
+#[inline]
+pub fn to_variant<T>(value: &T) -> Result<Variant, Error>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(Serializer)
+}
+
+/// Converts a `Variant` into a value using its `Deserialize` implementation.
+///
+/// # Errors
+///
+/// Returns an error if the variant does not match the structure expected by `T`, or contains
+/// an `Object` or a `Rid`.
+#[inline]
+pub fn from_variant<T>(variant: &Variant) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(Deserializer::new(variant.clone()))
+}
+
+/// Error that can occur when converting between `Variant`s and `serde` types.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Error {
+    message: String,
+}
+
+impl fmt::Display for Error {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    #[inline]
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error {
+            message: msg.to_string(),
+        }
+    }
+}
+
+impl de::Error for Error {
+    #[inline]
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error {
+            message: msg.to_string(),
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use std::vec;
+
+use serde::de::{self, DeserializeSeed, Error as _, IntoDeserializer, Visitor};
+use serde::{forward_to_deserialize_any, Deserialize, Serialize};
+
+use crate::core_types::{Variant, VariantDispatch};
+
+use super::{to_variant, Error};
+
+/// Deserializer reading from a `Variant`.
+pub(super) struct Deserializer {
+    variant: Variant,
+}
+
+impl Deserializer {
+    pub(super) fn new(variant: Variant) -> Self {
+        Deserializer { variant }
+    }
+}
+
+/// Deserializes a core type through its `serde` representation, as produced by `to_variant`.
+fn deserialize_via_serde<'de, T, V>(value: &T, visitor: V) -> Result<V::Value, Error>
+where
+    T: Serialize,
+    V: Visitor<'de>,
+{
+    de::Deserializer::deserialize_any(Deserializer::new(to_variant(value)?), visitor)
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.variant.dispatch() {
+            VariantDispatch::Nil => visitor.visit_unit(),
+            VariantDispatch::Bool(v) => visitor.visit_bool(v),
+            VariantDispatch::I64(v) => visitor.visit_i64(v),
+            VariantDispatch::F64(v) => visitor.visit_f64(v),
+            VariantDispatch::GodotString(v) => visitor.visit_string(v.to_string()),
+            VariantDispatch::Vector2(v) => deserialize_via_serde(&v, visitor),
+            VariantDispatch::Rect2(v) => deserialize_via_serde(&v, visitor),
+            VariantDispatch::Vector3(v) => deserialize_via_serde(&v, visitor),
+            VariantDispatch::Transform2D(v) => deserialize_via_serde(&v, visitor),
+            VariantDispatch::Plane(v) => deserialize_via_serde(&v, visitor),
+            VariantDispatch::Quat(v) => deserialize_via_serde(&v, visitor),
+            VariantDispatch::Aabb(v) => deserialize_via_serde(&v, visitor),
+            VariantDispatch::Basis(v) => deserialize_via_serde(&v, visitor),
+            VariantDispatch::Transform(v) => deserialize_via_serde(&v, visitor),
+            VariantDispatch::Color(v) => deserialize_via_serde(&v, visitor),
+            VariantDispatch::NodePath(v) => visitor.visit_string(v.to_string()),
+            VariantDispatch::Rid(_) => {
+                Err(Error::custom("Deserialization of RID's is not supported"))
+            }
+            VariantDispatch::Object(_) => {
+                Err(Error::custom("Deserialization of Objects is not supported"))
+            }
+            VariantDispatch::Dictionary(v) => visitor.visit_map(MapAccess {
+                entries: v.iter().collect::<Vec<_>>().into_iter(),
+                value: None,
+            }),
+            VariantDispatch::VariantArray(v) => visitor.visit_seq(SeqAccess {
+                elements: v.iter().collect::<Vec<_>>().into_iter(),
+            }),
+            VariantDispatch::ByteArray(v) => deserialize_via_serde(&v, visitor),
+            VariantDispatch::Int32Array(v) => deserialize_via_serde(&v, visitor),
+            VariantDispatch::Float32Array(v) => deserialize_via_serde(&v, visitor),
+            VariantDispatch::StringArray(v) => deserialize_via_serde(&v, visitor),
+            VariantDispatch::Vector2Array(v) => deserialize_via_serde(&v, visitor),
+            VariantDispatch::Vector3Array(v) => deserialize_via_serde(&v, visitor),
+            VariantDispatch::ColorArray(v) => deserialize_via_serde(&v, visitor),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.variant.is_nil() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.variant.dispatch() {
+            VariantDispatch::ByteArray(v) => visitor.visit_byte_buf(v.to_vec()),
+            _ => de::Deserializer::deserialize_any(self, visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_bytes(self, visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.variant.dispatch() {
+            VariantDispatch::GodotString(variant) => visitor.visit_enum(
+                IntoDeserializer::<Error>::into_deserializer(variant.to_string()),
+            ),
+            VariantDispatch::Dictionary(dictionary) if dictionary.len() == 1 => {
+                let (variant, value) = dictionary
+                    .iter()
+                    .next()
+                    .expect("dictionary should have one entry");
+                let variant = variant.try_to::<String>().map_err(|_| {
+                    Error::custom("expected a string as the name of an enum variant")
+                })?;
+                visitor.visit_enum(EnumAccess { variant, value })
+            }
+            _ => Err(Error::custom(
+                "expected a string or a Dictionary with a single entry for an enum",
+            )),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct SeqAccess {
+    elements: vec::IntoIter<Variant>,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.elements
+            .next()
+            .map(|element| seed.deserialize(Deserializer::new(element)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.elements.len())
+    }
+}
+
+struct MapAccess {
+    entries: vec::IntoIter<(Variant, Variant)>,
+    /// Value of the entry whose key was returned last.
+    value: Option<Variant>,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(Deserializer::new(key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| Error::custom("next_value_seed called before next_key_seed"))?;
+        seed.deserialize(Deserializer::new(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+struct EnumAccess {
+    variant: String,
+    value: Variant,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess {
+    type Error = Error;
+    type Variant = VariantAccess;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, VariantAccess), Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant =
+            seed.deserialize(IntoDeserializer::<Error>::into_deserializer(self.variant))?;
+        Ok((variant, VariantAccess { value: self.value }))
+    }
+}
+
+struct VariantAccess {
+    value: Variant,
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        <()>::deserialize(Deserializer::new(self.value))
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(Deserializer::new(self.value))
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(Deserializer::new(self.value), visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_map(Deserializer::new(self.value), visitor)
+    }
+}
@@ -0,0 +1,343 @@
+use serde::ser::{self, Error as _, Serialize};
+
+use crate::core_types::{Dictionary, OwnedToVariant, PoolArray, ToVariant, Variant, VariantArray};
+use crate::object::ownership::Unique;
+
+use super::Error;
+
+/// Serializer producing `Variant`s.
+pub(super) struct Serializer;
+
+/// Wraps `value` in a single-entry dictionary keyed by the name of an enum variant.
+fn tagged(variant: &'static str, value: Variant) -> Variant {
+    let dictionary = Dictionary::new();
+    dictionary.insert(variant, value);
+    dictionary.owned_to_variant()
+}
+
+impl ser::Serializer for Serializer {
+    type Ok = Variant;
+    type Error = Error;
+
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeTupleVariant;
+    type SerializeMap = SerializeDictionary;
+    type SerializeStruct = SerializeDictionary;
+    type SerializeStructVariant = SerializeStructVariant;
+
+    fn serialize_bool(self, v: bool) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Variant, Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Variant, Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Variant, Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Variant, Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Variant, Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Variant, Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Variant, Error> {
+        let v = i64::try_from(v)
+            .map_err(|_| Error::custom(format!("u64 value {v} does not fit into i64")))?;
+        self.serialize_i64(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Variant, Error> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_char(self, v: char) -> Result<Variant, Error> {
+        Ok(v.to_string().to_variant())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Variant, Error> {
+        Ok(PoolArray::from_slice(v).to_variant())
+    }
+
+    fn serialize_none(self) -> Result<Variant, Error> {
+        Ok(Variant::nil())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Variant, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Variant, Error> {
+        Ok(Variant::nil())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Variant, Error> {
+        Ok(Variant::nil())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Variant, Error> {
+        Ok(variant.to_variant())
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Variant, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Variant, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Ok(tagged(variant, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<SerializeArray, Error> {
+        Ok(SerializeArray {
+            array: VariantArray::new(),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SerializeTupleVariant, Error> {
+        Ok(SerializeTupleVariant {
+            variant,
+            array: VariantArray::new(),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeDictionary, Error> {
+        Ok(SerializeDictionary {
+            dictionary: Dictionary::new(),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeDictionary, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SerializeStructVariant, Error> {
+        Ok(SerializeStructVariant {
+            variant,
+            dictionary: Dictionary::new(),
+        })
+    }
+}
+
+pub(super) struct SerializeArray {
+    array: VariantArray<Unique>,
+}
+
+impl ser::SerializeSeq for SerializeArray {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.array.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        Ok(self.array.owned_to_variant())
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+pub(super) struct SerializeTupleVariant {
+    variant: &'static str,
+    array: VariantArray<Unique>,
+}
+
+impl ser::SerializeTupleVariant for SerializeTupleVariant {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.array.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        Ok(tagged(self.variant, self.array.owned_to_variant()))
+    }
+}
+
+pub(super) struct SerializeDictionary {
+    dictionary: Dictionary<Unique>,
+    /// Key passed to `serialize_key`, waiting for its value.
+    key: Option<Variant>,
+}
+
+impl ser::SerializeMap for SerializeDictionary {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.key = Some(key.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::custom("serialize_value called before serialize_key"))?;
+        self.dictionary.insert(key, value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        Ok(self.dictionary.owned_to_variant())
+    }
+}
+
+impl ser::SerializeStruct for SerializeDictionary {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.dictionary.insert(key, value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+pub(super) struct SerializeStructVariant {
+    variant: &'static str,
+    dictionary: Dictionary<Unique>,
+}
+
+impl ser::SerializeStructVariant for SerializeStructVariant {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.dictionary.insert(key, value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        Ok(tagged(self.variant, self.dictionary.owned_to_variant()))
+    }
+}
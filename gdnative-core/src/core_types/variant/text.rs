@@ -0,0 +1,1227 @@
+//! Parser and printer for Godot's text serialization format.
+//!
+//! This is the format produced by GDScript's `var2str` and consumed by `str2var`, and also used
+//! for values in `ConfigFile`s, `project.godot`, and text scenes and resources.
+//!
+//! Like [`marshal`][super::marshal], this works on plain [`Value`]s, so text can be parsed and
+//! printed without a running engine, e.g. to validate configuration in build scripts or other
+//! tools. Only the Godot 3 format is supported. Resource references such as `ExtResource(1)` are
+//! only meaningful within scene files, and are rejected by the parser.
+//!
+//! ```
+//! use gdnative::core_types::text::{self, Value};
+//! use gdnative::core_types::Vector2;
+//!
+//! let value = text::parse("[ 1, Vector2( 0.5, 2 ) ]").unwrap();
+//! assert_eq!(
+//!     Value::VariantArray(vec![Value::I64(1), Value::Vector2(Vector2::new(0.5, 2.0))]),
+//!     value,
+//! );
+//! assert_eq!("[ 1, Vector2( 0.5, 2 ) ]", text::to_string(&value));
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt::{self, Write};
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::core_types::{
+    Aabb, Basis, Color, Plane, Quat, Rect2, Transform, Transform2D, Vector2, Vector3,
+};
+
+pub use super::value::{NodePathValue, ObjectValue, Value};
+
+/// Maximum nesting depth of collections and objects accepted by the parser.
+const MAX_DEPTH: usize = 128;
+
+/// Error returned by [`parse`], with the position where it occurred.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    kind: ParseErrorKind,
+}
+
+impl ParseError {
+    /// Returns the line where the error occurred, starting from 1.
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the column where the error occurred, in characters, starting from 1.
+    #[inline]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the kind of the error.
+    #[inline]
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ParseError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Kinds of errors that can occur when parsing text.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    UnexpectedEof,
+    UnexpectedChar(char),
+    /// A token other than the one expected was found.
+    UnexpectedToken {
+        expected: &'static str,
+        found: String,
+    },
+    UnknownIdentifier(String),
+    /// A number could not be parsed, or does not fit into the expected type.
+    InvalidNumber(String),
+    InvalidColor(String),
+    /// A `\u` escape sequence is not followed by four hexadecimal digits.
+    InvalidEscape,
+    InvalidArgumentCount {
+        constructor: &'static str,
+        found: usize,
+    },
+    /// The value refers to a resource, which is only meaningful within scene files.
+    Resource(String),
+    DepthLimitExceeded,
+    /// The input contains more than one value.
+    TrailingCharacters,
+}
+
+impl fmt::Display for ParseErrorKind {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseErrorKind::UnexpectedEof => write!(f, "unexpected end of input"),
+            ParseErrorKind::UnexpectedChar(c) => write!(f, "unexpected character {c:?}"),
+            ParseErrorKind::UnexpectedToken { expected, found } => {
+                write!(f, "expected {expected}, found {found}")
+            }
+            ParseErrorKind::UnknownIdentifier(ident) => write!(f, "unknown identifier `{ident}`"),
+            ParseErrorKind::InvalidNumber(number) => write!(f, "invalid number `{number}`"),
+            ParseErrorKind::InvalidColor(color) => write!(f, "invalid color `{color}`"),
+            ParseErrorKind::InvalidEscape => write!(f, "invalid unicode escape sequence"),
+            ParseErrorKind::InvalidArgumentCount { constructor, found } => {
+                write!(f, "invalid number of arguments for {constructor}: {found}")
+            }
+            ParseErrorKind::Resource(ident) => {
+                write!(f, "resource references such as `{ident}` are not supported")
+            }
+            ParseErrorKind::DepthLimitExceeded => write!(f, "nesting depth limit exceeded"),
+            ParseErrorKind::TrailingCharacters => write!(f, "trailing characters after value"),
+        }
+    }
+}
+
+/// Parses a single value from text, like `str2var`. Whitespace and `;` comments are allowed
+/// around the value.
+#[inline]
+pub fn parse(text: &str) -> Result<Value, ParseError> {
+    let mut parser = Parser::new(text);
+    let value = parser.value()?;
+    match parser.next_token()? {
+        Token::Eof => Ok(value),
+        _ => Err(parser.error(ParseErrorKind::TrailingCharacters)),
+    }
+}
+
+/// Prints a value as text, like `var2str`.
+///
+/// Output matches that of Godot 3. In particular, floats are printed with 6 significant digits,
+/// and dictionary keys are sorted. Object IDs and resource IDs cannot be represented in text, and
+/// are printed as `null`.
+#[inline]
+pub fn to_string(value: &Value) -> String {
+    let mut out = String::new();
+    write(&mut out, value).expect("writing to a String should never fail");
+    out
+}
+
+/// Prints a value as text into `out`, like `var2str`. See [`to_string`] for details.
+#[inline]
+pub fn write<W: Write>(out: &mut W, value: &Value) -> fmt::Result {
+    match value {
+        Value::Nil | Value::Rid | Value::Object(None) | Value::ObjectId(_) => out.write_str("null"),
+        Value::Bool(v) => write!(out, "{v}"),
+        Value::I64(v) => write!(out, "{v}"),
+        Value::F64(v) => {
+            let s = format_float(*v);
+            out.write_str(&s)?;
+            if v.is_finite() && !s.contains(['.', 'e']) {
+                out.write_str(".0")?;
+            }
+            Ok(())
+        }
+        Value::GodotString(v) => write!(out, "\"{}\"", escape_multiline(v)),
+        Value::Vector2(v) => write_construct(out, "Vector2", &[v.x, v.y]),
+        Value::Rect2(v) => write_construct(
+            out,
+            "Rect2",
+            &[v.position.x, v.position.y, v.size.x, v.size.y],
+        ),
+        Value::Vector3(v) => write_construct(out, "Vector3", &[v.x, v.y, v.z]),
+        Value::Transform2D(v) => write_construct(
+            out,
+            "Transform2D",
+            &[v.a.x, v.a.y, v.b.x, v.b.y, v.origin.x, v.origin.y],
+        ),
+        Value::Plane(v) => {
+            write_construct(out, "Plane", &[v.normal.x, v.normal.y, v.normal.z, v.d])
+        }
+        Value::Quat(v) => write_construct(out, "Quat", &[v.x, v.y, v.z, v.w]),
+        Value::Aabb(v) => write_construct(
+            out,
+            "AABB",
+            &[
+                v.position.x,
+                v.position.y,
+                v.position.z,
+                v.size.x,
+                v.size.y,
+                v.size.z,
+            ],
+        ),
+        Value::Basis(v) => write_construct(out, "Basis", &basis_components(v)),
+        Value::Transform(v) => {
+            let mut components = basis_components(&v.basis).to_vec();
+            components.extend([v.origin.x, v.origin.y, v.origin.z]);
+            write_construct(out, "Transform", &components)
+        }
+        Value::Color(v) => write_construct(out, "Color", &[v.r, v.g, v.b, v.a]),
+        Value::NodePath(v) => write!(out, "NodePath(\"{}\")", escape(&v.to_string())),
+        Value::Object(Some(object)) => {
+            write!(out, "Object({},", object.class)?;
+            for (i, (name, value)) in object.properties.iter().enumerate() {
+                if i > 0 {
+                    out.write_str(",")?;
+                }
+                write!(out, "\"{}\":", escape_multiline(name))?;
+                write(out, value)?;
+            }
+            out.write_str(")\n")
+        }
+        Value::Dictionary(entries) => {
+            let mut entries = entries.iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| compare_keys(a, b));
+
+            out.write_str("{\n")?;
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.write_str(",\n")?;
+                }
+                write(out, key)?;
+                out.write_str(": ")?;
+                write(out, value)?;
+            }
+            out.write_str("\n}")
+        }
+        Value::VariantArray(values) => {
+            out.write_str("[ ")?;
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    out.write_str(", ")?;
+                }
+                write(out, value)?;
+            }
+            out.write_str(" ]")
+        }
+        Value::ByteArray(v) => write_pool(out, "PoolByteArray", v, |out, v| write!(out, "{v}")),
+        Value::Int32Array(v) => write_pool(out, "PoolIntArray", v, |out, v| write!(out, "{v}")),
+        Value::Float32Array(v) => write_pool(out, "PoolRealArray", v, |out, v| {
+            out.write_str(&format_float(*v as f64))
+        }),
+        Value::StringArray(v) => write_pool(out, "PoolStringArray", v, |out, v| {
+            write!(out, "\"{}\"", escape(v))
+        }),
+        Value::Vector2Array(v) => write_pool(out, "PoolVector2Array", v, |out, v| {
+            write_floats(out, &[v.x, v.y])
+        }),
+        Value::Vector3Array(v) => write_pool(out, "PoolVector3Array", v, |out, v| {
+            write_floats(out, &[v.x, v.y, v.z])
+        }),
+        Value::ColorArray(v) => write_pool(out, "PoolColorArray", v, |out, v| {
+            write_floats(out, &[v.r, v.g, v.b, v.a])
+        }),
+    }
+}
+
+fn basis_components(basis: &Basis) -> [f32; 9] {
+    let [x, y, z] = basis.elements;
+    [x.x, x.y, x.z, y.x, y.y, y.z, z.x, z.y, z.z]
+}
+
+fn write_floats<W: Write>(out: &mut W, components: &[f32]) -> fmt::Result {
+    for (i, component) in components.iter().enumerate() {
+        if i > 0 {
+            out.write_str(", ")?;
+        }
+        out.write_str(&format_float(*component as f64))?;
+    }
+    Ok(())
+}
+
+fn write_construct<W: Write>(out: &mut W, name: &str, components: &[f32]) -> fmt::Result {
+    write!(out, "{name}( ")?;
+    write_floats(out, components)?;
+    out.write_str(" )")
+}
+
+fn write_pool<W, T, F>(out: &mut W, name: &str, elements: &[T], mut write_element: F) -> fmt::Result
+where
+    W: Write,
+    F: FnMut(&mut W, &T) -> fmt::Result,
+{
+    write!(out, "{name}( ")?;
+    for (i, element) in elements.iter().enumerate() {
+        if i > 0 {
+            out.write_str(", ")?;
+        }
+        write_element(out, element)?;
+    }
+    out.write_str(" )")
+}
+
+/// Formats a float like C's `%lg`, which Godot uses with the default precision of 6 significant
+/// digits. Zero is always printed as `0`, avoiding `-0`.
+fn format_float(value: f64) -> String {
+    const PRECISION: i32 = 6;
+
+    if value == 0.0 {
+        return "0".into();
+    }
+    if value.is_nan() {
+        return "nan".into();
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { "inf" } else { "-inf" }.into();
+    }
+
+    // The exponent after rounding to the precision decides between fixed and scientific notation.
+    let scientific = format!("{:.*e}", (PRECISION - 1) as usize, value);
+    let (mantissa, exponent) = scientific
+        .split_once('e')
+        .expect("scientific notation should contain an exponent");
+    let exponent: i32 = exponent.parse().expect("exponent should be an integer");
+
+    if (-4..PRECISION).contains(&exponent) {
+        let fixed = format!("{:.*}", (PRECISION - 1 - exponent) as usize, value);
+        trim_fraction(&fixed).into()
+    } else {
+        let sign = if exponent < 0 { '-' } else { '+' };
+        format!("{}e{sign}{:02}", trim_fraction(mantissa), exponent.abs())
+    }
+}
+
+/// Removes trailing zeros in the fractional part of a number, and the decimal point if nothing
+/// remains after it.
+fn trim_fraction(number: &str) -> &str {
+    if number.contains('.') {
+        number.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        number
+    }
+}
+
+/// Escapes a string like `String::c_escape_multiline`, which is used for string values.
+fn escape_multiline(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Escapes a string like `String::c_escape`, which is used for node paths and string arrays.
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\u{7}' => escaped.push_str("\\a"),
+            '\u{8}' => escaped.push_str("\\b"),
+            '\u{c}' => escaped.push_str("\\f"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\u{b}' => escaped.push_str("\\v"),
+            '\'' => escaped.push_str("\\'"),
+            '?' => escaped.push_str("\\?"),
+            '"' => escaped.push_str("\\\""),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Compares dictionary keys like Godot's `Variant::operator<`, which orders values by type
+/// first. Values of types without a defined order compare as equal, keeping insertion order.
+fn compare_keys(a: &Value, b: &Value) -> Ordering {
+    let type_order = (a.get_type() as u32).cmp(&(b.get_type() as u32));
+    if type_order != Ordering::Equal {
+        return type_order;
+    }
+
+    let compare_floats = |a: &[f32], b: &[f32]| {
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    };
+
+    match (a, b) {
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::I64(a), Value::I64(b)) => a.cmp(b),
+        (Value::F64(a), Value::F64(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+        (Value::GodotString(a), Value::GodotString(b)) => a.cmp(b),
+        (Value::Vector2(a), Value::Vector2(b)) => compare_floats(&[a.x, a.y], &[b.x, b.y]),
+        (Value::Vector3(a), Value::Vector3(b)) => {
+            compare_floats(&[a.x, a.y, a.z], &[b.x, b.y, b.z])
+        }
+        _ => Ordering::Equal,
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Token {
+    CurlyOpen,
+    CurlyClose,
+    BracketOpen,
+    BracketClose,
+    ParenOpen,
+    ParenClose,
+    Colon,
+    Comma,
+    Identifier(String),
+    String(String),
+    Number(Number),
+    Color(Color),
+    Eof,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::CurlyOpen => "`{`".into(),
+            Token::CurlyClose => "`}`".into(),
+            Token::BracketOpen => "`[`".into(),
+            Token::BracketClose => "`]`".into(),
+            Token::ParenOpen => "`(`".into(),
+            Token::ParenClose => "`)`".into(),
+            Token::Colon => "`:`".into(),
+            Token::Comma => "`,`".into(),
+            Token::Identifier(ident) => format!("identifier `{ident}`"),
+            Token::String(_) => "string".into(),
+            Token::Number(_) => "number".into(),
+            Token::Color(_) => "color".into(),
+            Token::Eof => "end of input".into(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+impl Number {
+    fn to_f64(self) -> f64 {
+        match self {
+            Number::Int(i) => i as f64,
+            Number::Float(f) => f,
+        }
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+    /// Position of the last token returned by `next_token`.
+    token_start: (usize, usize),
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        Parser {
+            chars: text.chars().peekable(),
+            line: 1,
+            column: 1,
+            token_start: (1, 1),
+            depth: 0,
+        }
+    }
+
+    /// Creates an error at the start of the last token.
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        let (line, column) = self.token_start;
+        ParseError { line, column, kind }
+    }
+
+    fn unexpected(&self, expected: &'static str, found: &Token) -> ParseError {
+        self.error(ParseErrorKind::UnexpectedToken {
+            expected,
+            found: found.describe(),
+        })
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn next_char_or_eof(&mut self) -> Result<char, ParseError> {
+        self.next_char()
+            .ok_or_else(|| self.error(ParseErrorKind::UnexpectedEof))
+    }
+
+    fn next_token(&mut self) -> Result<Token, ParseError> {
+        loop {
+            match self.chars.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.next_char();
+                }
+                Some(';') => while !matches!(self.next_char(), Some('\n') | None) {},
+                _ => break,
+            }
+        }
+
+        self.token_start = (self.line, self.column);
+        let c = match self.next_char() {
+            Some(c) => c,
+            None => return Ok(Token::Eof),
+        };
+
+        let token = match c {
+            '{' => Token::CurlyOpen,
+            '}' => Token::CurlyClose,
+            '[' => Token::BracketOpen,
+            ']' => Token::BracketClose,
+            '(' => Token::ParenOpen,
+            ')' => Token::ParenClose,
+            ':' => Token::Colon,
+            ',' => Token::Comma,
+            '"' => Token::String(self.string()?),
+            // StringNames are written as `@"name"` by later versions.
+            '@' => match self.next_char() {
+                Some('"') => Token::String(self.string()?),
+                _ => return Err(self.error(ParseErrorKind::UnexpectedChar('@'))),
+            },
+            '#' => {
+                let hex = self.take_while(|c| c.is_ascii_hexdigit());
+                let color = parse_html_color(&hex)
+                    .ok_or_else(|| self.error(ParseErrorKind::InvalidColor(format!("#{hex}"))))?;
+                Token::Color(color)
+            }
+            '-' if self.chars.peek() == Some(&'i') => {
+                match self
+                    .take_while(|c| c.is_alphanumeric() || c == '_')
+                    .as_str()
+                {
+                    "inf" => Token::Number(Number::Float(f64::NEG_INFINITY)),
+                    other => {
+                        return Err(self.error(ParseErrorKind::InvalidNumber(format!("-{other}"))))
+                    }
+                }
+            }
+            '-' | '0'..='9' => Token::Number(self.number(c)?),
+            c if c.is_alphabetic() || c == '_' => {
+                let mut ident = c.to_string();
+                ident.push_str(&self.take_while(|c| c.is_alphanumeric() || c == '_'));
+                Token::Identifier(ident)
+            }
+            c => return Err(self.error(ParseErrorKind::UnexpectedChar(c))),
+        };
+
+        Ok(token)
+    }
+
+    fn take_while(&mut self, mut predicate: impl FnMut(char) -> bool) -> String {
+        let mut taken = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if !predicate(c) {
+                break;
+            }
+            taken.push(c);
+            self.next_char();
+        }
+        taken
+    }
+
+    /// Reads the rest of a string after the opening quote.
+    fn string(&mut self) -> Result<String, ParseError> {
+        let mut s = String::new();
+        loop {
+            match self.next_char_or_eof()? {
+                '"' => return Ok(s),
+                '\\' => {
+                    let c = match self.next_char_or_eof()? {
+                        'a' => '\u{7}',
+                        'b' => '\u{8}',
+                        't' => '\t',
+                        'n' => '\n',
+                        'f' => '\u{c}',
+                        'r' => '\r',
+                        'v' => '\u{b}',
+                        'u' => {
+                            let mut code = 0;
+                            for _ in 0..4 {
+                                let digit = self
+                                    .next_char_or_eof()?
+                                    .to_digit(16)
+                                    .ok_or_else(|| self.error(ParseErrorKind::InvalidEscape))?;
+                                code = code * 16 + digit;
+                            }
+                            char::from_u32(code)
+                                .ok_or_else(|| self.error(ParseErrorKind::InvalidEscape))?
+                        }
+                        // Other escaped characters, including quotes, stand for themselves.
+                        c => c,
+                    };
+                    s.push(c);
+                }
+                c => s.push(c),
+            }
+        }
+    }
+
+    /// Reads the rest of a number after its first character. Numbers with a fractional part or
+    /// an exponent are floats.
+    fn number(&mut self, first: char) -> Result<Number, ParseError> {
+        let mut number = first.to_string();
+        let mut is_float = false;
+        let mut in_exponent = false;
+
+        while let Some(&c) = self.chars.peek() {
+            match c {
+                '0'..='9' => {}
+                '.' if !is_float => is_float = true,
+                'e' | 'E' if !in_exponent => {
+                    is_float = true;
+                    in_exponent = true;
+                }
+                '+' | '-' if in_exponent && number.ends_with(['e', 'E']) => {}
+                _ => break,
+            }
+            number.push(c);
+            self.next_char();
+        }
+
+        let parsed = if is_float {
+            number.parse().ok().map(Number::Float)
+        } else {
+            number.parse().ok().map(Number::Int)
+        };
+        parsed.ok_or_else(|| self.error(ParseErrorKind::InvalidNumber(number)))
+    }
+
+    fn expect(&mut self, expected: Token, description: &'static str) -> Result<(), ParseError> {
+        let token = self.next_token()?;
+        if token == expected {
+            Ok(())
+        } else {
+            Err(self.unexpected(description, &token))
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, ParseError> {
+        let token = self.next_token()?;
+        self.value_from(token)
+    }
+
+    fn value_from(&mut self, token: Token) -> Result<Value, ParseError> {
+        let value = match token {
+            Token::CurlyOpen => self.nested(Parser::dictionary)?,
+            Token::BracketOpen => self.nested(Parser::array)?,
+            Token::Identifier(ident) => self.identifier(ident)?,
+            Token::String(s) => Value::GodotString(s),
+            Token::Number(Number::Int(i)) => Value::I64(i),
+            Token::Number(Number::Float(f)) => Value::F64(f),
+            Token::Color(color) => Value::Color(color),
+            Token::Eof => return Err(self.error(ParseErrorKind::UnexpectedEof)),
+            token => return Err(self.unexpected("value", &token)),
+        };
+
+        Ok(value)
+    }
+
+    fn nested(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<Value, ParseError>,
+    ) -> Result<Value, ParseError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(self.error(ParseErrorKind::DepthLimitExceeded));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    /// Parses the rest of a dictionary after the opening brace.
+    fn dictionary(&mut self) -> Result<Value, ParseError> {
+        let mut entries = Vec::new();
+        loop {
+            let token = self.next_token()?;
+            if token == Token::CurlyClose {
+                return Ok(Value::Dictionary(entries));
+            }
+
+            let key = self.value_from(token)?;
+            self.expect(Token::Colon, "`:`")?;
+            let value = self.value()?;
+            entries.push((key, value));
+
+            match self.next_token()? {
+                Token::Comma => {}
+                Token::CurlyClose => return Ok(Value::Dictionary(entries)),
+                token => return Err(self.unexpected("`,` or `}`", &token)),
+            }
+        }
+    }
+
+    /// Parses the rest of an array after the opening bracket.
+    fn array(&mut self) -> Result<Value, ParseError> {
+        let mut values = Vec::new();
+        loop {
+            let token = self.next_token()?;
+            if token == Token::BracketClose {
+                return Ok(Value::VariantArray(values));
+            }
+
+            values.push(self.value_from(token)?);
+
+            match self.next_token()? {
+                Token::Comma => {}
+                Token::BracketClose => return Ok(Value::VariantArray(values)),
+                token => return Err(self.unexpected("`,` or `]`", &token)),
+            }
+        }
+    }
+
+    fn identifier(&mut self, ident: String) -> Result<Value, ParseError> {
+        let value = match ident.as_str() {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            "null" | "nil" => Value::Nil,
+            "inf" => Value::F64(f64::INFINITY),
+            "inf_neg" => Value::F64(f64::NEG_INFINITY),
+            "nan" => Value::F64(f64::NAN),
+
+            "Vector2" => {
+                let [x, y] = self.floats("Vector2")?;
+                Value::Vector2(Vector2::new(x, y))
+            }
+            "Rect2" => {
+                let [x, y, w, h] = self.floats("Rect2")?;
+                Value::Rect2(Rect2::new(Vector2::new(x, y), Vector2::new(w, h)))
+            }
+            "Vector3" => {
+                let [x, y, z] = self.floats("Vector3")?;
+                Value::Vector3(Vector3::new(x, y, z))
+            }
+            "Transform2D" | "Matrix32" => {
+                let [ax, ay, bx, by, ox, oy] = self.floats("Transform2D")?;
+                Value::Transform2D(Transform2D {
+                    a: Vector2::new(ax, ay),
+                    b: Vector2::new(bx, by),
+                    origin: Vector2::new(ox, oy),
+                })
+            }
+            "Plane" => {
+                let [x, y, z, d] = self.floats("Plane")?;
+                Value::Plane(Plane::new(Vector3::new(x, y, z), d))
+            }
+            "Quat" => {
+                let [x, y, z, w] = self.floats("Quat")?;
+                Value::Quat(Quat::new(x, y, z, w))
+            }
+            "AABB" | "Rect3" => {
+                let [x, y, z, w, h, d] = self.floats("AABB")?;
+                Value::Aabb(Aabb::new(Vector3::new(x, y, z), Vector3::new(w, h, d)))
+            }
+            "Basis" | "Matrix3" => {
+                let components: [f32; 9] = self.floats("Basis")?;
+                Value::Basis(basis_from_components(&components))
+            }
+            "Transform" => {
+                let components: [f32; 12] = self.floats("Transform")?;
+                Value::Transform(Transform {
+                    basis: basis_from_components(&components[..9]),
+                    origin: Vector3::new(components[9], components[10], components[11]),
+                })
+            }
+            "Color" => {
+                let [r, g, b, a] = self.floats("Color")?;
+                Value::Color(Color::from_rgba(r, g, b, a))
+            }
+            "NodePath" => {
+                self.expect(Token::ParenOpen, "`(`")?;
+                let path = match self.next_token()? {
+                    Token::String(path) => path,
+                    token => return Err(self.unexpected("string", &token)),
+                };
+                self.expect(Token::ParenClose, "`)`")?;
+                Value::NodePath(path.as_str().into())
+            }
+            "Object" => self.nested(Parser::object)?,
+
+            "PoolByteArray" | "ByteArray" => Value::ByteArray(self.ints()?),
+            "PoolIntArray" | "IntArray" => Value::Int32Array(self.ints()?),
+            "PoolRealArray" | "RealArray" | "FloatArray" => {
+                Value::Float32Array(self.construct_floats()?)
+            }
+            "PoolStringArray" | "StringArray" => Value::StringArray(self.strings()?),
+            "PoolVector2Array" | "Vector2Array" => Value::Vector2Array(
+                self.float_chunks::<2>("PoolVector2Array")?
+                    .into_iter()
+                    .map(|[x, y]| Vector2::new(x, y))
+                    .collect(),
+            ),
+            "PoolVector3Array" | "Vector3Array" => Value::Vector3Array(
+                self.float_chunks::<3>("PoolVector3Array")?
+                    .into_iter()
+                    .map(|[x, y, z]| Vector3::new(x, y, z))
+                    .collect(),
+            ),
+            "PoolColorArray" | "ColorArray" => Value::ColorArray(
+                self.float_chunks::<4>("PoolColorArray")?
+                    .into_iter()
+                    .map(|[r, g, b, a]| Color::from_rgba(r, g, b, a))
+                    .collect(),
+            ),
+
+            "Resource" | "SubResource" | "ExtResource" => {
+                return Err(self.error(ParseErrorKind::Resource(ident)))
+            }
+            _ => return Err(self.error(ParseErrorKind::UnknownIdentifier(ident))),
+        };
+
+        Ok(value)
+    }
+
+    /// Parses the arguments of a constructor, e.g. `( 1, 2.5 )`, using `parse_arg` to parse each
+    /// argument from its token.
+    fn construct<T>(
+        &mut self,
+        mut parse_arg: impl FnMut(&mut Self, Token) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        self.expect(Token::ParenOpen, "`(`")?;
+
+        let mut args = Vec::new();
+        let mut token = self.next_token()?;
+        if token == Token::ParenClose {
+            return Ok(args);
+        }
+
+        loop {
+            args.push(parse_arg(self, token)?);
+            match self.next_token()? {
+                Token::Comma => token = self.next_token()?,
+                Token::ParenClose => return Ok(args),
+                token => return Err(self.unexpected("`,` or `)`", &token)),
+            }
+        }
+    }
+
+    fn construct_floats(&mut self) -> Result<Vec<f32>, ParseError> {
+        self.construct(|parser, token| match token {
+            Token::Number(number) => Ok(number.to_f64() as f32),
+            Token::Identifier(ident) => match ident.as_str() {
+                "inf" => Ok(f32::INFINITY),
+                "inf_neg" => Ok(f32::NEG_INFINITY),
+                "nan" => Ok(f32::NAN),
+                _ => Err(parser.error(ParseErrorKind::UnknownIdentifier(ident))),
+            },
+            token => Err(parser.unexpected("number", &token)),
+        })
+    }
+
+    fn floats<const N: usize>(
+        &mut self,
+        constructor: &'static str,
+    ) -> Result<[f32; N], ParseError> {
+        let floats = self.construct_floats()?;
+        <[f32; N]>::try_from(floats).map_err(|floats| {
+            self.error(ParseErrorKind::InvalidArgumentCount {
+                constructor,
+                found: floats.len(),
+            })
+        })
+    }
+
+    fn float_chunks<const N: usize>(
+        &mut self,
+        constructor: &'static str,
+    ) -> Result<Vec<[f32; N]>, ParseError> {
+        let floats = self.construct_floats()?;
+        if floats.len() % N != 0 {
+            return Err(self.error(ParseErrorKind::InvalidArgumentCount {
+                constructor,
+                found: floats.len(),
+            }));
+        }
+
+        Ok(floats
+            .chunks_exact(N)
+            .map(|chunk| <[f32; N]>::try_from(chunk).expect("chunks should have N elements"))
+            .collect())
+    }
+
+    fn ints<T: TryFrom<i64>>(&mut self) -> Result<Vec<T>, ParseError> {
+        self.construct(|parser, token| match token {
+            Token::Number(Number::Int(i)) => T::try_from(i)
+                .map_err(|_| parser.error(ParseErrorKind::InvalidNumber(i.to_string()))),
+            token => Err(parser.unexpected("integer", &token)),
+        })
+    }
+
+    fn strings(&mut self) -> Result<Vec<String>, ParseError> {
+        self.construct(|parser, token| match token {
+            Token::String(s) => Ok(s),
+            token => Err(parser.unexpected("string", &token)),
+        })
+    }
+
+    /// Parses an object in full after the `Object` identifier, e.g.
+    /// `Object(Reference,"script":null)`.
+    fn object(&mut self) -> Result<Value, ParseError> {
+        self.expect(Token::ParenOpen, "`(`")?;
+        let class = match self.next_token()? {
+            Token::Identifier(class) => class,
+            token => return Err(self.unexpected("class name", &token)),
+        };
+        self.expect(Token::Comma, "`,`")?;
+
+        let mut properties = Vec::new();
+        loop {
+            let name = match self.next_token()? {
+                Token::ParenClose => break,
+                Token::String(name) => name,
+                token => return Err(self.unexpected("property name", &token)),
+            };
+            self.expect(Token::Colon, "`:`")?;
+            let value = self.value()?;
+            properties.push((name, value));
+
+            match self.next_token()? {
+                Token::Comma => {}
+                Token::ParenClose => break,
+                token => return Err(self.unexpected("`,` or `)`", &token)),
+            }
+        }
+
+        Ok(Value::Object(Some(Box::new(ObjectValue {
+            class,
+            properties,
+        }))))
+    }
+}
+
+fn basis_from_components(components: &[f32]) -> Basis {
+    let row = |i: usize| Vector3::new(components[i], components[i + 1], components[i + 2]);
+    Basis {
+        elements: [row(0), row(3), row(6)],
+    }
+}
+
+/// Parses a color in the hexadecimal formats accepted by Godot 3's `Color::html`, i.e. `rgb`,
+/// `argb`, `rrggbb` or `aarrggbb`. Note that alpha comes first.
+fn parse_html_color(hex: &str) -> Option<Color> {
+    let digits = hex
+        .chars()
+        .map(|c| c.to_digit(16).map(|d| d as f32))
+        .collect::<Option<Vec<_>>>()?;
+
+    let channels = match digits.len() {
+        3 | 4 => digits.iter().map(|d| d * 17.0 / 255.0).collect::<Vec<_>>(),
+        6 | 8 => digits
+            .chunks_exact(2)
+            .map(|pair| (pair[0] * 16.0 + pair[1]) / 255.0)
+            .collect(),
+        _ => return None,
+    };
+
+    let color = match *channels.as_slice() {
+        [r, g, b] => Color::from_rgb(r, g, b),
+        [a, r, g, b] => Color::from_rgba(r, g, b, a),
+        _ => unreachable!("channels should have been validated"),
+    };
+    Some(color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn print_fixtures() {
+        let cases = [
+            (Value::Nil, "null"),
+            (Value::Bool(true), "true"),
+            (Value::I64(-42), "-42"),
+            (Value::F64(1.0), "1.0"),
+            (Value::F64(-0.0), "0.0"),
+            (Value::F64(0.1), "0.1"),
+            (Value::F64(123456789.0), "1.23457e+08"),
+            (Value::F64(0.0001), "0.0001"),
+            (Value::F64(0.00001), "1e-05"),
+            (Value::F64(f64::INFINITY), "inf"),
+            (Value::F64(f64::NAN), "nan"),
+            (
+                Value::GodotString("a \"b\"\\\n".into()),
+                "\"a \\\"b\\\"\\\\\n\"",
+            ),
+            (Value::Vector2(Vector2::new(1.0, 2.5)), "Vector2( 1, 2.5 )"),
+            (
+                Value::Rect2(Rect2::new(Vector2::new(0.0, 1.0), Vector2::new(2.0, 3.0))),
+                "Rect2( 0, 1, 2, 3 )",
+            ),
+            (
+                Value::Transform2D(Transform2D::IDENTITY),
+                "Transform2D( 1, 0, 0, 1, 0, 0 )",
+            ),
+            (
+                Value::Aabb(Aabb::new(Vector3::ZERO, Vector3::new(1.0, 2.0, 3.0))),
+                "AABB( 0, 0, 0, 1, 2, 3 )",
+            ),
+            (
+                Value::Transform(Transform {
+                    basis: Basis::IDENTITY,
+                    origin: Vector3::new(1.0, 2.0, 3.0),
+                }),
+                "Transform( 1, 0, 0, 0, 1, 0, 0, 0, 1, 1, 2, 3 )",
+            ),
+            (
+                Value::Color(Color::from_rgba(1.0, 0.5, 0.0, 1.0)),
+                "Color( 1, 0.5, 0, 1 )",
+            ),
+            (
+                Value::NodePath("/root/Player:position:x".into()),
+                "NodePath(\"/root/Player:position:x\")",
+            ),
+            (
+                Value::Dictionary(vec![
+                    (Value::GodotString("b".into()), Value::I64(2)),
+                    (Value::I64(1), Value::Nil),
+                    (Value::GodotString("a".into()), Value::Dictionary(vec![])),
+                ]),
+                "{\n1: null,\n\"a\": {\n\n},\n\"b\": 2\n}",
+            ),
+            (Value::VariantArray(vec![]), "[  ]"),
+            (
+                Value::VariantArray(vec![Value::I64(1), Value::GodotString("a".into())]),
+                "[ 1, \"a\" ]",
+            ),
+            (Value::ByteArray(vec![1, 2]), "PoolByteArray( 1, 2 )"),
+            (Value::Int32Array(vec![]), "PoolIntArray(  )"),
+            (
+                Value::Float32Array(vec![1.0, 0.5]),
+                "PoolRealArray( 1, 0.5 )",
+            ),
+            (
+                Value::StringArray(vec!["a\n".into(), "b".into()]),
+                "PoolStringArray( \"a\\n\", \"b\" )",
+            ),
+            (
+                Value::Vector2Array(vec![Vector2::new(1.0, 2.0), Vector2::new(3.0, 4.0)]),
+                "PoolVector2Array( 1, 2, 3, 4 )",
+            ),
+            (
+                Value::ColorArray(vec![Color::from_rgba(0.0, 0.5, 1.0, 1.0)]),
+                "PoolColorArray( 0, 0.5, 1, 1 )",
+            ),
+            (
+                Value::Object(Some(Box::new(ObjectValue {
+                    class: "Reference".into(),
+                    properties: vec![("script".into(), Value::Nil)],
+                }))),
+                "Object(Reference,\"script\":null)\n",
+            ),
+            (Value::ObjectId(42), "null"),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(expected, to_string(&value), "{value:?}");
+        }
+    }
+
+    #[test]
+    fn parse_round_trip() {
+        let values = [
+            Value::Nil,
+            Value::Bool(false),
+            Value::I64(i64::MIN),
+            Value::F64(-2.5),
+            Value::F64(f64::NEG_INFINITY),
+            Value::GodotString("héllo \"world\"\\\n\t".into()),
+            Value::Vector3(Vector3::new(1.0, -2.0, 3.5)),
+            Value::Plane(Plane::new(Vector3::UP, 2.0)),
+            Value::Quat(Quat::new(0.0, 0.0, 0.0, 1.0)),
+            Value::Basis(Basis::IDENTITY),
+            Value::NodePath("../a:b".into()),
+            Value::Dictionary(vec![
+                (Value::I64(1), Value::VariantArray(vec![Value::Nil])),
+                (Value::GodotString("a".into()), Value::Dictionary(vec![])),
+            ]),
+            Value::Int32Array(vec![1, -1]),
+            Value::StringArray(vec!["\u{7}?'\"\\".into()]),
+            Value::Vector3Array(vec![Vector3::new(1.0, 2.0, 3.0)]),
+            Value::Object(Some(Box::new(ObjectValue {
+                class: "Resource".into(),
+                properties: vec![
+                    ("resource_name".into(), Value::GodotString("foo".into())),
+                    ("script".into(), Value::Nil),
+                ],
+            }))),
+        ];
+
+        for value in values {
+            let text = to_string(&value);
+            assert_eq!(Ok(value), parse(&text), "{text}");
+        }
+    }
+
+    #[test]
+    fn parse_syntax() {
+        let cases = [
+            ("  42 ; comment", Value::I64(42)),
+            ("1e3", Value::F64(1000.0)),
+            ("-1.5e-1", Value::F64(-0.15)),
+            ("inf_neg", Value::F64(f64::NEG_INFINITY)),
+            ("nil", Value::Nil),
+            ("\"\\u00e9\\q\"", Value::GodotString("éq".into())),
+            ("@\"name\"", Value::GodotString("name".into())),
+            (
+                "#ff8000",
+                Value::Color(Color::from_rgb(1.0, 128.0 / 255.0, 0.0)),
+            ),
+            (
+                "#80ff0000",
+                Value::Color(Color::from_rgba(1.0, 0.0, 0.0, 128.0 / 255.0)),
+            ),
+            (
+                "Vector2( inf, -1 )",
+                Value::Vector2(Vector2::new(f32::INFINITY, -1.0)),
+            ),
+            (
+                "Matrix32( 1, 0, 0, 1, 0, 0 )",
+                Value::Transform2D(Transform2D::IDENTITY),
+            ),
+            ("IntArray( )", Value::Int32Array(vec![])),
+            (
+                "{ \"a\": 1, 2: [ 3, ], }",
+                Value::Dictionary(vec![
+                    (Value::GodotString("a".into()), Value::I64(1)),
+                    (Value::I64(2), Value::VariantArray(vec![Value::I64(3)])),
+                ]),
+            ),
+            (
+                "Object(Reference,)",
+                Value::Object(Some(Box::new(ObjectValue {
+                    class: "Reference".into(),
+                    properties: vec![],
+                }))),
+            ),
+        ];
+
+        for (text, expected) in cases {
+            assert_eq!(Ok(expected), parse(text), "{text}");
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        let cases = [
+            ("", 1, 1, ParseErrorKind::UnexpectedEof),
+            (
+                "[ 1,\n  foo ]",
+                2,
+                3,
+                ParseErrorKind::UnknownIdentifier("foo".into()),
+            ),
+            ("1 2", 1, 3, ParseErrorKind::TrailingCharacters),
+            ("\"abc", 1, 1, ParseErrorKind::UnexpectedEof),
+            ("\"\\u12\"", 1, 1, ParseErrorKind::InvalidEscape),
+            (
+                "#12345",
+                1,
+                1,
+                ParseErrorKind::InvalidColor("#12345".into()),
+            ),
+            (
+                "Vector2( 1 )",
+                1,
+                12,
+                ParseErrorKind::InvalidArgumentCount {
+                    constructor: "Vector2",
+                    found: 1,
+                },
+            ),
+            (
+                "PoolVector2Array( 1, 2, 3 )",
+                1,
+                27,
+                ParseErrorKind::InvalidArgumentCount {
+                    constructor: "PoolVector2Array",
+                    found: 3,
+                },
+            ),
+            (
+                "PoolByteArray( 256 )",
+                1,
+                16,
+                ParseErrorKind::InvalidNumber("256".into()),
+            ),
+            (
+                "{ 1 2 }",
+                1,
+                5,
+                ParseErrorKind::UnexpectedToken {
+                    expected: "`:`",
+                    found: "number".into(),
+                },
+            ),
+            (
+                "ExtResource( 1 )",
+                1,
+                1,
+                ParseErrorKind::Resource("ExtResource".into()),
+            ),
+        ];
+
+        for (text, line, column, kind) in cases {
+            let err = parse(text).expect_err(text);
+            assert_eq!(
+                (line, column, &kind),
+                (err.line(), err.column(), err.kind()),
+                "{text}"
+            );
+        }
+
+        let nested = "[".repeat(MAX_DEPTH + 1);
+        assert_eq!(
+            &ParseErrorKind::DepthLimitExceeded,
+            parse(&nested).unwrap_err().kind()
+        );
+    }
+}
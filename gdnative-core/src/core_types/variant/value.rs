@@ -0,0 +1,289 @@
+use std::fmt;
+use std::ptr;
+
+use crate::core_types::{
+    Aabb, Basis, Color, Dictionary, FromVariant, FromVariantError, GodotString, NodePath, Plane,
+    PoolArray, Quat, Rect2, Rid, ToVariant, Transform, Transform2D, Variant, VariantArray,
+    VariantDispatch, VariantType, Vector2, Vector3,
+};
+use crate::object::RawObject;
+use crate::private::{get_api, ManuallyManagedClassPlaceholder};
+use crate::sys;
+
+/// Plain Rust representation of a `Variant`, used by the [`marshal`][super::marshal] and
+/// [`text`][super::text] serialization formats.
+///
+/// Variants correspond to those of [`VariantType`]. Objects are represented either in full, as
+/// produced by `var2bytes(value, true)` and `var2str`, or as their instance IDs, as produced by
+/// `var2bytes(value)`.
+///
+/// Values can be converted from and to [`Variant`] with [`FromVariant`] and [`ToVariant`] when
+/// running in the engine. Objects are converted into their instance IDs, which are resolved
+/// again if the objects are still alive. Since values are detached from the engine, the
+/// conversion to `Variant` is lossy in some cases:
+///
+/// - Object IDs of freed objects become nil variants.
+/// - Full objects become nil variants, since they can't be instantiated without running their
+///   scripts.
+/// - Resource IDs become empty [`Rid`]s.
+#[derive(Clone, PartialEq, Debug)]
+#[non_exhaustive]
+pub enum Value {
+    Nil,
+    Bool(bool),
+    I64(i64),
+    F64(f64),
+    GodotString(String),
+    Vector2(Vector2),
+    Rect2(Rect2),
+    Vector3(Vector3),
+    Transform2D(Transform2D),
+    Plane(Plane),
+    Quat(Quat),
+    Aabb(Aabb),
+    Basis(Basis),
+    Transform(Transform),
+    Color(Color),
+    NodePath(NodePathValue),
+    /// A resource ID. These are not serializable, so no further data is stored.
+    Rid,
+    /// An object encoded with its class name and stored properties, or `None` for a null object.
+    Object(Option<Box<ObjectValue>>),
+    /// An object encoded as its instance ID, which is `0` for a null object.
+    ObjectId(u64),
+    /// Entries of a `Dictionary`, in insertion order.
+    Dictionary(Vec<(Value, Value)>),
+    VariantArray(Vec<Value>),
+    ByteArray(Vec<u8>),
+    Int32Array(Vec<i32>),
+    Float32Array(Vec<f32>),
+    StringArray(Vec<String>),
+    Vector2Array(Vec<Vector2>),
+    Vector3Array(Vec<Vector3>),
+    ColorArray(Vec<Color>),
+}
+
+impl Value {
+    /// Returns the type of the `Variant` this value corresponds to.
+    #[inline]
+    pub fn get_type(&self) -> VariantType {
+        match self {
+            Value::Nil => VariantType::Nil,
+            Value::Bool(_) => VariantType::Bool,
+            Value::I64(_) => VariantType::I64,
+            Value::F64(_) => VariantType::F64,
+            Value::GodotString(_) => VariantType::GodotString,
+            Value::Vector2(_) => VariantType::Vector2,
+            Value::Rect2(_) => VariantType::Rect2,
+            Value::Vector3(_) => VariantType::Vector3,
+            Value::Transform2D(_) => VariantType::Transform2D,
+            Value::Plane(_) => VariantType::Plane,
+            Value::Quat(_) => VariantType::Quat,
+            Value::Aabb(_) => VariantType::Aabb,
+            Value::Basis(_) => VariantType::Basis,
+            Value::Transform(_) => VariantType::Transform,
+            Value::Color(_) => VariantType::Color,
+            Value::NodePath(_) => VariantType::NodePath,
+            Value::Rid => VariantType::Rid,
+            Value::Object(_) | Value::ObjectId(_) => VariantType::Object,
+            Value::Dictionary(_) => VariantType::Dictionary,
+            Value::VariantArray(_) => VariantType::VariantArray,
+            Value::ByteArray(_) => VariantType::ByteArray,
+            Value::Int32Array(_) => VariantType::Int32Array,
+            Value::Float32Array(_) => VariantType::Float32Array,
+            Value::StringArray(_) => VariantType::StringArray,
+            Value::Vector2Array(_) => VariantType::Vector2Array,
+            Value::Vector3Array(_) => VariantType::Vector3Array,
+            Value::ColorArray(_) => VariantType::ColorArray,
+        }
+    }
+}
+
+/// A node path, split into node names and subnames, i.e. property names.
+///
+/// `/root/Player:position:x` is represented with the names `["root", "Player"]` and the subnames
+/// `["position", "x"]`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct NodePathValue {
+    pub names: Vec<String>,
+    pub subnames: Vec<String>,
+    pub absolute: bool,
+}
+
+impl From<&str> for NodePathValue {
+    /// Parses a node path from its string representation. Empty names and subnames are ignored.
+    #[inline]
+    fn from(path: &str) -> Self {
+        let absolute = path.starts_with('/');
+        let (names, subnames) = path.split_once(':').unwrap_or((path, ""));
+
+        let split = |s: &str, pat: char| {
+            s.split(pat)
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect()
+        };
+
+        NodePathValue {
+            names: split(names, '/'),
+            subnames: split(subnames, ':'),
+            absolute,
+        }
+    }
+}
+
+impl fmt::Display for NodePathValue {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.absolute {
+            write!(f, "/")?;
+        }
+        for (i, name) in self.names.iter().enumerate() {
+            if i > 0 {
+                write!(f, "/")?;
+            }
+            write!(f, "{name}")?;
+        }
+        for subname in &self.subnames {
+            write!(f, ":{subname}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An object encoded in full, with its class name and the values of its stored properties.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct ObjectValue {
+    pub class: String,
+    /// Stored properties, in the order they were encoded.
+    pub properties: Vec<(String, Value)>,
+}
+
+impl ToVariant for Value {
+    /// Converts a value into a variant. This is lossy for full objects, object IDs of freed
+    /// objects and resource IDs. See [`Value`] for details.
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        match self {
+            Value::Nil | Value::Object(_) => Variant::nil(),
+            Value::ObjectId(id) => {
+                // `godot_instance_from_id` returns null for freed objects and the null ID `0`.
+                let obj = unsafe { (get_api().godot_instance_from_id)(*id as sys::godot_int) };
+                if obj.is_null() {
+                    Variant::nil()
+                } else {
+                    // SAFETY: `obj` is a live object returned by the engine.
+                    unsafe { Variant::from_object_ptr(obj) }
+                }
+            }
+            Value::Bool(v) => v.to_variant(),
+            Value::I64(v) => v.to_variant(),
+            Value::F64(v) => v.to_variant(),
+            Value::GodotString(v) => v.to_variant(),
+            Value::Vector2(v) => v.to_variant(),
+            Value::Rect2(v) => v.to_variant(),
+            Value::Vector3(v) => v.to_variant(),
+            Value::Transform2D(v) => v.to_variant(),
+            Value::Plane(v) => v.to_variant(),
+            Value::Quat(v) => v.to_variant(),
+            Value::Aabb(v) => v.to_variant(),
+            Value::Basis(v) => v.to_variant(),
+            Value::Transform(v) => v.to_variant(),
+            Value::Color(v) => v.to_variant(),
+            Value::NodePath(v) => NodePath::from_str(&v.to_string()).to_variant(),
+            Value::Rid => Rid::new().to_variant(),
+            Value::Dictionary(entries) => {
+                let dictionary = Dictionary::new();
+                for (key, value) in entries {
+                    dictionary.insert(key.to_variant(), value.to_variant());
+                }
+                dictionary.into_shared().to_variant()
+            }
+            Value::VariantArray(values) => {
+                let array = VariantArray::new();
+                for value in values {
+                    array.push(value.to_variant());
+                }
+                array.into_shared().to_variant()
+            }
+            Value::ByteArray(v) => PoolArray::from_slice(v).to_variant(),
+            Value::Int32Array(v) => PoolArray::from_slice(v).to_variant(),
+            Value::Float32Array(v) => PoolArray::from_slice(v).to_variant(),
+            Value::StringArray(v) => v
+                .iter()
+                .map(GodotString::from)
+                .collect::<PoolArray<_>>()
+                .to_variant(),
+            Value::Vector2Array(v) => PoolArray::from_slice(v).to_variant(),
+            Value::Vector3Array(v) => PoolArray::from_slice(v).to_variant(),
+            Value::ColorArray(v) => PoolArray::from_slice(v).to_variant(),
+        }
+    }
+}
+
+impl FromVariant for Value {
+    /// Converts a variant into a value. This never fails. Objects are converted into their
+    /// instance IDs.
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        Ok(Value::from_variant_infallible(variant))
+    }
+}
+
+impl Value {
+    fn from_variant_infallible(variant: &Variant) -> Self {
+        match variant.dispatch() {
+            VariantDispatch::Nil => Value::Nil,
+            VariantDispatch::Bool(v) => Value::Bool(v),
+            VariantDispatch::I64(v) => Value::I64(v),
+            VariantDispatch::F64(v) => Value::F64(v),
+            VariantDispatch::GodotString(v) => Value::GodotString(v.to_string()),
+            VariantDispatch::Vector2(v) => Value::Vector2(v),
+            VariantDispatch::Rect2(v) => Value::Rect2(v),
+            VariantDispatch::Vector3(v) => Value::Vector3(v),
+            VariantDispatch::Transform2D(v) => Value::Transform2D(v),
+            VariantDispatch::Plane(v) => Value::Plane(v),
+            VariantDispatch::Quat(v) => Value::Quat(v),
+            VariantDispatch::Aabb(v) => Value::Aabb(v),
+            VariantDispatch::Basis(v) => Value::Basis(v),
+            VariantDispatch::Transform(v) => Value::Transform(v),
+            VariantDispatch::Color(v) => Value::Color(v),
+            VariantDispatch::NodePath(v) => Value::NodePath(v.to_string().as_str().into()),
+            VariantDispatch::Rid(_) => Value::Rid,
+            VariantDispatch::Object(v) => {
+                let id = unsafe {
+                    let obj = (get_api().godot_variant_as_object)(v.sys());
+                    ptr::NonNull::new(obj).map_or(0, |obj| {
+                        RawObject::<ManuallyManagedClassPlaceholder>::from_sys_ref_unchecked(obj)
+                            .instance_id()
+                    })
+                };
+                Value::ObjectId(id as u64)
+            }
+            VariantDispatch::Dictionary(v) => Value::Dictionary(
+                v.iter()
+                    .map(|(key, value)| {
+                        (
+                            Value::from_variant_infallible(&key),
+                            Value::from_variant_infallible(&value),
+                        )
+                    })
+                    .collect(),
+            ),
+            VariantDispatch::VariantArray(v) => Value::VariantArray(
+                v.iter()
+                    .map(|value| Value::from_variant_infallible(&value))
+                    .collect(),
+            ),
+            VariantDispatch::ByteArray(v) => Value::ByteArray(v.to_vec()),
+            VariantDispatch::Int32Array(v) => Value::Int32Array(v.to_vec()),
+            VariantDispatch::Float32Array(v) => Value::Float32Array(v.to_vec()),
+            VariantDispatch::StringArray(v) => {
+                Value::StringArray(v.read().iter().map(GodotString::to_string).collect())
+            }
+            VariantDispatch::Vector2Array(v) => Value::Vector2Array(v.to_vec()),
+            VariantDispatch::Vector3Array(v) => Value::Vector3Array(v.to_vec()),
+            VariantDispatch::ColorArray(v) => Value::ColorArray(v.to_vec()),
+        }
+    }
+}
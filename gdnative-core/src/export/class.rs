@@ -1,7 +1,7 @@
 use crate::export::user_data::UserData;
 use crate::export::{class_registry, ClassBuilder};
 use crate::object::ownership::{Ownership, Shared, Unique};
-use crate::object::{GodotObject, Instance, Instanciable, TRef};
+use crate::object::{GodotObject, Instance, Instanciable, TInstance, TRef};
 
 /// Trait used for describing and initializing a Godot script class.
 ///
@@ -60,10 +60,28 @@ pub trait NativeClass: Sized + 'static {
         )
     }
 
+    /// Function that is called with the base object right after the script instance is created,
+    /// either by `nativeclass_init` or through `Instance::emplace`. The default implementation
+    /// does nothing.
+    ///
+    /// This is used by the `NativeClass` derive macro to attach [`Base`](crate::object::Base)
+    /// fields.
+    #[inline]
+    fn nativeclass_attach_base(&mut self, _base: TRef<'_, Self::Base, Shared>) {}
+
     /// Register any exported properties to Godot.
     #[inline]
     fn nativeclass_register_properties(_builder: &ClassBuilder<Self>) {}
 
+    /// Function that is called right before the `_ready` method of the script, if any. The
+    /// default implementation does nothing.
+    ///
+    /// This is used by the `NativeClass` derive macro to resolve `#[node]` fields. It is called
+    /// for any method registered as `_ready`, including ones registered manually with
+    /// `ClassBuilder::method`.
+    #[inline]
+    fn nativeclass_pre_ready(_this: TInstance<'_, Self, Shared>) {}
+
     /// Convenience method to create an `Instance<Self, Unique>`. This is a new `Self::Base`
     /// with the script attached.
     ///
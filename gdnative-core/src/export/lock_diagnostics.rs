@@ -0,0 +1,280 @@
+//! Diagnostics for the locking user-data wrappers, enabled with `LockOptions::DIAGNOSTICS`.
+
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread::{self, Thread};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, RwLock};
+
+use crate::export::user_data::{DeadlockPolicy, LockFailed};
+use crate::export::{class_registry, NativeClass};
+use crate::log::Site;
+
+static CONTENTION: Lazy<RwLock<HashMap<TypeId, LockContention>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Whether any lock with diagnostics has been created. Exported methods are only recorded as
+/// holders once this is set, so that classes without diagnostics pay no cost on each call.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static CURRENT_METHOD: Cell<Option<Site<'static>>> = Cell::new(None);
+}
+
+/// Contention statistics of the locking user-data wrappers of a `NativeClass`, as returned by
+/// [`lock_contention`].
+///
+/// Statistics are only collected for wrappers with `LockOptions::DIAGNOSTICS` enabled.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct LockContention {
+    /// Name of the class.
+    pub class_name: Cow<'static, str>,
+    /// Number of lock attempts that could not be satisfied immediately.
+    pub contended: u64,
+    /// Number of lock attempts that failed according to the `DeadlockPolicy`.
+    pub failed: u64,
+    /// Total time spent waiting on contended locks, including failed attempts.
+    pub total_wait: Duration,
+    /// Longest time spent waiting on a single contended lock.
+    pub max_wait: Duration,
+    /// Report of the most recent failed lock attempt, naming the waiter and the holders, as
+    /// printed to the Godot console.
+    pub last_failure: Option<String>,
+}
+
+/// Returns contention statistics for all classes that have seen lock contention since the
+/// start or the last call to [`reset_lock_contention`], sorted by descending contention.
+#[inline]
+pub fn lock_contention() -> Vec<LockContention> {
+    let mut stats = CONTENTION.read().values().cloned().collect::<Vec<_>>();
+    stats.sort_by(|a, b| b.contended.cmp(&a.contended));
+    stats
+}
+
+/// Clears all collected contention statistics.
+#[inline]
+pub fn reset_lock_contention() {
+    CONTENTION.write().clear();
+}
+
+/// Resets the statistics during `terminate`.
+pub(crate) fn cleanup() {
+    reset_lock_contention();
+    ENABLED.store(false, Ordering::Relaxed);
+}
+
+/// Records `site` as the exported method running on the current thread during `f`, if any lock
+/// with diagnostics exists.
+#[inline]
+pub(crate) fn with_current_method<F, R>(site: Option<Site<'static>>, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    if !ENABLED.load(Ordering::Relaxed) {
+        return f();
+    }
+
+    struct Restore(Option<Site<'static>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0;
+            let _ = CURRENT_METHOD.try_with(|current| current.set(previous));
+        }
+    }
+
+    let _restore = Restore(CURRENT_METHOD.with(|current| current.replace(site)));
+    f()
+}
+
+fn current_method() -> Option<Site<'static>> {
+    CURRENT_METHOD.try_with(Cell::get).ok().flatten()
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) enum Access {
+    Read,
+    Write,
+}
+
+impl fmt::Display for Access {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Access::Read => write!(f, "read"),
+            Access::Write => write!(f, "write"),
+        }
+    }
+}
+
+/// A party holding or waiting on a lock.
+struct Party {
+    method: Option<Site<'static>>,
+    thread: Thread,
+    access: Access,
+    since: Instant,
+}
+
+impl Party {
+    fn current(access: Access) -> Self {
+        Party {
+            method: current_method(),
+            thread: thread::current(),
+            access,
+            since: Instant::now(),
+        }
+    }
+}
+
+impl fmt::Display for Party {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} access ", self.access)?;
+        match &self.method {
+            Some(site) => write!(f, "from method ({site})")?,
+            None => write!(f, "from outside an exported method")?,
+        }
+        match self.thread.name() {
+            Some(name) => write!(f, " on thread '{name}' ({:?})", self.thread.id())?,
+            None => write!(f, " on thread {:?}", self.thread.id())?,
+        }
+        write!(f, ", since {:?}", self.since.elapsed())
+    }
+}
+
+/// Per-instance record of the current holders of a lock.
+#[derive(Default)]
+pub(crate) struct LockState {
+    next_id: AtomicU64,
+    holders: Mutex<Vec<(u64, Party)>>,
+}
+
+impl fmt::Debug for LockState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LockState")
+            .field("holders", &self.holders.lock().len())
+            .finish()
+    }
+}
+
+/// Removes its holder record from a `LockState` when dropped.
+pub(crate) struct HolderToken<'a> {
+    state: &'a LockState,
+    id: u64,
+}
+
+impl<'a> Drop for HolderToken<'a> {
+    fn drop(&mut self) {
+        self.state.holders.lock().retain(|(id, _)| *id != self.id);
+    }
+}
+
+impl LockState {
+    /// Creates the state for a new lock, enabling the recording of exported methods.
+    pub(crate) fn new() -> Self {
+        ENABLED.store(true, Ordering::Relaxed);
+        LockState::default()
+    }
+
+    /// Acquires a lock according to `policy`, recording the holder, and any contention for the
+    /// class `C`. `try_now` must not block, `block` and `block_for` are used on contention.
+    pub(crate) fn acquire<C, G>(
+        &self,
+        policy: DeadlockPolicy,
+        access: Access,
+        try_now: impl FnOnce() -> Option<G>,
+        block: impl FnOnce() -> G,
+        block_for: impl FnOnce(Duration) -> Option<G>,
+    ) -> Result<(G, HolderToken<'_>), LockFailed>
+    where
+        C: NativeClass,
+    {
+        let guard = match try_now() {
+            Some(guard) => guard,
+            None => {
+                let waiter = Party::current(access);
+
+                let result = match policy {
+                    DeadlockPolicy::Allow => Ok(block()),
+                    DeadlockPolicy::Pessimistic => Err(LockFailed::Pessimistic),
+                    DeadlockPolicy::Timeout(dur) => block_for(dur).ok_or(LockFailed::Timeout(dur)),
+                };
+
+                let wait = waiter.since.elapsed();
+
+                match result {
+                    Ok(guard) => {
+                        record_contention::<C>(wait, None);
+                        guard
+                    }
+                    Err(err) => {
+                        let report = self.failure_report::<C>(&waiter, err);
+                        record_contention::<C>(wait, Some(&report));
+                        crate::log::error(waiter.method.unwrap_or_default(), report);
+                        return Err(err);
+                    }
+                }
+            }
+        };
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.holders.lock().push((id, Party::current(access)));
+
+        Ok((guard, HolderToken { state: self, id }))
+    }
+
+    fn failure_report<C: NativeClass>(&self, waiter: &Party, err: LockFailed) -> String {
+        use std::fmt::Write;
+
+        let mut msg = format!(
+            "gdnative-core: {err} on an instance of {}\n  waiter: {waiter}",
+            class_registry::class_name_or_default::<C>(),
+        );
+
+        let holders = self.holders.lock();
+        if holders.is_empty() {
+            msg.push_str("\n  holder: <released in the meantime>");
+        }
+        for (_, holder) in holders.iter() {
+            let _ = write!(msg, "\n  holder: {holder}");
+        }
+        drop(holders);
+
+        msg
+    }
+}
+
+/// Records a contended lock attempt for `C`, with the report of the failure if it failed.
+fn record_contention<C: NativeClass>(wait: Duration, failure: Option<&str>) {
+    let type_id = TypeId::of::<C>();
+
+    // Resolve the class name outside the lock, since it might take another one.
+    let class_name = if CONTENTION.read().contains_key(&type_id) {
+        None
+    } else {
+        Some(class_registry::class_name_or_default::<C>())
+    };
+
+    let mut stats = CONTENTION.write();
+    let entry = stats.entry(type_id).or_insert_with(|| LockContention {
+        class_name: class_name.unwrap_or(Cow::Borrowed(std::any::type_name::<C>())),
+        contended: 0,
+        failed: 0,
+        total_wait: Duration::ZERO,
+        max_wait: Duration::ZERO,
+        last_failure: None,
+    });
+
+    entry.contended += 1;
+    if let Some(report) = failure {
+        entry.failed += 1;
+        entry.last_failure = Some(report.to_owned());
+    }
+    entry.total_wait += wait;
+    entry.max_wait = entry.max_wait.max(wait);
+}
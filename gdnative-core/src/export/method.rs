@@ -6,10 +6,11 @@ use std::{fmt, ops};
 
 use crate::core_types::{FromVariant, FromVariantError, Variant};
 use crate::export::class::NativeClass;
-use crate::export::{class_registry, ClassBuilder};
+use crate::export::user_data::UserData;
+use crate::export::{class_registry, lock_diagnostics, ClassBuilder};
 use crate::log::Site;
 use crate::object::ownership::Shared;
-use crate::object::{Ref, TInstance, TRef};
+use crate::object::{try_get_user_data_ptr, Ref, TInstance, TRef};
 
 /// Builder type used to register a method on a `NativeClass`.
 #[must_use = "MethodBuilder left unbuilt -- did you forget to call done() or done_stateless()?"]
@@ -45,6 +46,14 @@ where
     /// Register the method.
     #[inline]
     pub fn done(self) {
+        if self.name == READY {
+            self.map_method(PreReady::new).register();
+        } else {
+            self.register();
+        }
+    }
+
+    fn register(self) {
         let method_data = Box::into_raw(Box::new(self.method));
 
         let script_method = ScriptMethod {
@@ -71,6 +80,14 @@ where
     /// but can be used with any `Method` type with `Copy + Default`.
     #[inline]
     pub fn done_stateless(self) {
+        if self.name == READY {
+            self.map_method(PreReady::new).register_stateless();
+        } else {
+            self.register_stateless();
+        }
+    }
+
+    fn register_stateless(self) {
         let script_method = ScriptMethod {
             name: self.name,
             method_ptr: Some(method_wrapper::<C, Stateless<F>>),
@@ -88,6 +105,21 @@ where
     }
 }
 
+impl<'a, C, F> MethodBuilder<'a, C, F> {
+    fn map_method<G>(self, f: impl FnOnce(F) -> G) -> MethodBuilder<'a, C, G> {
+        MethodBuilder {
+            class_builder: self.class_builder,
+            name: self.name,
+            method: f(self.method),
+            rpc_mode: self.rpc_mode,
+        }
+    }
+}
+
+/// Name of the method before which `NativeClass::nativeclass_pre_ready` is called, however it
+/// is registered.
+const READY: &str = "_ready";
+
 type ScriptMethodFn = unsafe extern "C" fn(
     *mut sys::godot_object,
     *mut libc::c_void,
@@ -168,6 +200,45 @@ impl<C: NativeClass, F: Method<C> + Copy + Default> Method<C> for Stateless<F> {
     }
 }
 
+/// Adapter that calls `NativeClass::nativeclass_pre_ready` before calling the wrapped `_ready`
+/// method. Applied automatically to methods registered as `_ready`.
+#[derive(Clone, Copy, Default, Debug)]
+struct PreReady<F> {
+    f: F,
+}
+
+impl<F> PreReady<F> {
+    fn new(f: F) -> Self {
+        PreReady { f }
+    }
+}
+
+impl<C: NativeClass, F: Method<C>> Method<C> for PreReady<F> {
+    #[inline]
+    fn call(&self, this: TInstance<'_, C>, args: Varargs<'_>) -> Variant {
+        C::nativeclass_pre_ready(this.clone());
+        self.f.call(this, args)
+    }
+
+    #[inline]
+    fn site() -> Option<Site<'static>> {
+        F::site()
+    }
+}
+
+/// `_ready` method that does nothing, registered for classes that need `nativeclass_pre_ready`
+/// but don't export a `_ready` method themselves. This is an internal interface.
+#[doc(hidden)]
+#[derive(Clone, Copy, Default, Debug)]
+pub struct EmptyReady;
+
+impl<C: NativeClass> Method<C> for EmptyReady {
+    #[inline]
+    fn call(&self, _this: TInstance<'_, C>, _args: Varargs<'_>) -> Variant {
+        Variant::nil()
+    }
+}
+
 /// Adapter for methods whose arguments are statically determined. If the arguments would fail to
 /// type check, the method will print the errors to Godot's debug console and return `null`.
 #[derive(Clone, Copy, Default, Debug)]
@@ -439,7 +510,7 @@ macro_rules! count_tts {
     };
 }
 
-// Convert from Varargs to tuples, implement traits.
+// Convert from Varargs to tuples, implement traits. Tuples also implement `FromVarargs`.
 macro_rules! varargs_into_tuple {
     ($($params:ident),*) => {
         impl<'a, $($params: FromVariant),*> std::convert::TryFrom<Varargs<'a>> for ($($params,)*) {
@@ -461,6 +532,25 @@ macro_rules! varargs_into_tuple {
                 ))
             }
         }
+
+        impl<$($params: FromVariant),*> FromVarargs for ($($params,)*) {
+            #[inline]
+            #[allow(non_snake_case, unused_mut)]
+            fn read<'a>(args: &mut Varargs<'a>) -> Result<Self, Vec<ArgumentError<'a>>> {
+                let mut errors = Vec::new();
+                $(
+                    let $params = args.read::<$params>().get().map_err(|err| errors.push(err)).ok();
+                )*
+
+                if !errors.is_empty() {
+                    return Err(errors);
+                }
+
+                Ok((
+                    $($params.expect("argument should be present if there are no errors"),)*
+                ))
+            }
+        }
     };
 }
 
@@ -880,22 +970,60 @@ unsafe extern "C" fn method_wrapper<C: NativeClass, F: Method<C>>(
     let result = std::panic::catch_unwind(move || {
         let method = &*(method_data as *const F);
 
-        let this: Ref<C::Base, Shared> = Ref::from_sys(this);
-        let this: TRef<'_, C::Base, _> = this.assume_safe_unchecked();
-        let this: TInstance<'_, C, _> = TInstance::from_raw_unchecked(this, user_data);
+        let base: Ref<C::Base, Shared> = Ref::from_sys(this);
+        let base: TRef<'_, C::Base, _> = base.assume_safe_unchecked();
+        let this: TInstance<'_, C, _> = TInstance::from_raw_unchecked(base, user_data);
+
+        // Some wrappers queue re-entrant calls instead of failing them. The queued calls are
+        // run on the same thread before the outer borrow ends. They only hold a weak reference
+        // to the base object, and are skipped if it was freed or lost its script in the meantime.
+        let deferred = this.script().try_defer_call(|| {
+            let mut args = (0..num_args as isize)
+                .map(|n| Variant::cast_ref(*args.offset(n)).clone())
+                .collect::<Vec<_>>();
+
+            let weak_base = base.downgrade();
+
+            Box::new(move || {
+                let base = match weak_base.upgrade() {
+                    Some(base) => base,
+                    None => return,
+                };
+
+                let user_data = match try_get_user_data_ptr::<C>(base.as_raw()) {
+                    Some(user_data) => user_data,
+                    None => return,
+                };
+
+                let mut arg_ptrs = args.iter_mut().map(Variant::sys_mut).collect::<Vec<_>>();
+
+                drop(Variant::from_sys(method_wrapper::<C, F>(
+                    base.as_raw().sys().as_ptr(),
+                    method_data,
+                    user_data,
+                    arg_ptrs.len() as libc::c_int,
+                    arg_ptrs.as_mut_ptr(),
+                )));
+            })
+        });
+
+        if deferred {
+            return Variant::nil();
+        }
 
         let args = Varargs::from_sys(num_args, args);
 
-        F::call(method, this, args)
+        lock_diagnostics::with_current_method(F::site(), || F::call(method, this, args))
     });
 
     result
         .unwrap_or_else(|e| {
-            crate::log::error(
-                F::site().unwrap_or_default(),
-                "gdnative-core: method panicked (check stderr for output)",
+            crate::init::panic_policy::report_panic(
+                "method",
+                class_registry::class_name::<C>().as_deref(),
+                F::site(),
+                e,
             );
-            crate::private::print_panic_error(e);
             Variant::nil()
         })
         .leak()
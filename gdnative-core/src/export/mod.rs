@@ -22,6 +22,7 @@ mod signal;
 
 pub(crate) mod class_registry;
 pub(crate) mod emplace;
+pub(crate) mod lock_diagnostics;
 pub(crate) mod type_tag;
 
 pub mod user_data;
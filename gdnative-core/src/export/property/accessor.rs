@@ -253,8 +253,12 @@ where
             });
 
             result.unwrap_or_else(|e| {
-                godot_error!("gdnative-core: property setter panicked (check stderr for output)");
-                crate::private::print_panic_error(e);
+                crate::init::panic_policy::report_panic(
+                    "property setter",
+                    class_registry::class_name::<C>().as_deref(),
+                    None,
+                    e,
+                );
             })
         }
         set.set_func = Some(invoke::<SelfArg, C, F, T>);
@@ -326,8 +330,12 @@ where
             });
 
             result.unwrap_or_else(|e| {
-                godot_error!("gdnative-core: property getter panicked (check stderr for output)");
-                crate::private::print_panic_error(e);
+                crate::init::panic_policy::report_panic(
+                    "property getter",
+                    class_registry::class_name::<C>().as_deref(),
+                    None,
+                    e,
+                );
                 Variant::nil().leak()
             })
         }
@@ -52,25 +52,45 @@
 //! - All your exported methods take `&self`.
 //! - Your `NativeClass` type is `Send + Sync`.
 //!
+//! ### Use an `ArcSwapData<T>` when:
+//!
+//! - Your `NativeClass` type is read from many threads, and written rarely.
+//! - Your `NativeClass` type is `Clone + Send + Sync`, and cloning it on each `&mut self` call
+//!   is acceptable.
+//! - Your `&mut self` methods are not called concurrently, or it's acceptable for concurrent
+//!   calls to fail instead of waiting.
+//!
 //! ### Use a `LocalCellData<T>` when:
 //!
 //! - Your `NativeClass` type is not `Send`, and you will only ever use it from the thread where
 //!   it's originally created.
 //!
+//! ### Use a `ReentrantCellData<T>` when:
+//!
+//! - Your `NativeClass` type is not `Send`, and you will only ever use it from the thread where
+//!   it's originally created.
+//! - Your `&mut self` methods emit signals or call into scripts that may call other methods on
+//!   the same instance, and it's acceptable for those calls to run after the outer method
+//!   returns. See the type-level documentation for the exact behavior.
+//!
 //! ### Use `Aether<T>` when:
 //!
 //! - Your `NativeClass` type is a zero-sized type (ZST) that is `Copy + Default`.
 //! - You don't need to do anything special in `Drop`.
 
-use parking_lot::{Mutex, RwLock};
+use parking_lot::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use std::fmt::{self, Debug, Display};
 use std::marker::PhantomData;
 use std::mem;
+use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::export::lock_diagnostics::{Access, HolderToken, LockState};
 use crate::export::NativeClass;
 
+pub use crate::export::lock_diagnostics::{lock_contention, reset_lock_contention, LockContention};
+
 /// Trait for customizable user-data wrappers.
 ///
 /// See module-level documentation for detailed explanation on user-data.
@@ -117,6 +137,21 @@ pub unsafe trait UserData: Sized + Clone {
     ///
     /// `ptr` must be pointing to valid data of the correct type.
     unsafe fn clone_from_user_data_unchecked(ptr: *const libc::c_void) -> Self;
+
+    /// Hook for wrappers that queue re-entrant method calls instead of failing them. If the
+    /// wrapper decides to defer the current call, it takes ownership of the closure produced
+    /// by `make_call`, runs it at a later time on the same thread, and returns `true`.
+    ///
+    /// This is an internal interface. The default implementation never defers.
+    #[doc(hidden)]
+    #[inline]
+    fn try_defer_call<F>(&self, make_call: F) -> bool
+    where
+        F: FnOnce() -> Box<dyn FnOnce()>,
+    {
+        let _ = make_call;
+        false
+    }
 }
 
 /// Trait for wrappers that can be mapped immutably.
@@ -159,6 +194,38 @@ pub trait MapOwned: UserData {
         F: FnOnce(Self::Target) -> U;
 }
 
+/// Trait for wrappers that can be borrowed immutably through an RAII guard, as an alternative
+/// to [`Map`] for code that needs early returns or `?`.
+///
+/// Typically used through [`Instance::borrow`][crate::object::Instance::borrow] or
+/// [`TInstance::borrow`][crate::object::TInstance::borrow].
+pub trait TryBorrow<'a>: UserData {
+    type Guard: Deref<Target = Self::Target> + 'a;
+    type Err: Debug;
+
+    /// Borrows the underlying value immutably, until the guard is dropped.
+    ///
+    /// Implementations of this method must not panic. Failures should be indicated by
+    /// returning `Err`.
+    fn try_borrow(&'a self) -> Result<Self::Guard, Self::Err>;
+}
+
+/// Trait for wrappers that can be borrowed mutably through an RAII guard, as an alternative
+/// to [`MapMut`] for code that needs early returns or `?`.
+///
+/// Typically used through [`Instance::borrow_mut`][crate::object::Instance::borrow_mut] or
+/// [`TInstance::borrow_mut`][crate::object::TInstance::borrow_mut].
+pub trait TryBorrowMut<'a>: UserData {
+    type Guard: DerefMut<Target = Self::Target> + 'a;
+    type Err: Debug;
+
+    /// Borrows the underlying value mutably, until the guard is dropped.
+    ///
+    /// Implementations of this method must not panic. Failures should be indicated by
+    /// returning `Err`.
+    fn try_borrow_mut(&'a self) -> Result<Self::Guard, Self::Err>;
+}
+
 /// The default user data wrapper used by derive macro, when no `user_data` attribute is present.
 /// This may change in the future.
 pub type DefaultUserData<T> = LocalCellData<T>;
@@ -186,6 +253,9 @@ impl std::fmt::Display for Infallible {
 ///
 /// As there is no universal way to deal with such situations, behavior of locking wrappers can
 /// be customized using this enum.
+///
+/// To find out which methods are involved when a lock can't be obtained, enable
+/// [`LockOptions::DIAGNOSTICS`].
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 #[non_exhaustive]
 pub enum DeadlockPolicy {
@@ -211,6 +281,124 @@ pub enum DeadlockPolicy {
 /// [rfc-2000]: https://github.com/rust-lang/rfcs/blob/master/text/2000-const-generics.md
 pub trait LockOptions {
     const DEADLOCK_POLICY: DeadlockPolicy;
+
+    /// Whether lock diagnostics are enabled. Defaults to `false`.
+    ///
+    /// When enabled, each instance lock records the exported method, thread and time of
+    /// acquisition of its current holders. When a lock can't be obtained according to the
+    /// `DeadlockPolicy`, the holders and the waiter are reported as an error to the Godot
+    /// console, in addition to the `LockFailed` error returned to the caller. Lock attempts
+    /// that can't be satisfied immediately are counted per class, and can be queried using
+    /// [`lock_contention`].
+    ///
+    /// This comes with some overhead on each lock operation, and is intended for debugging.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use gdnative::export::user_data::{DeadlockPolicy, LockOptions, MutexData};
+    ///
+    /// struct Diagnosed;
+    ///
+    /// impl LockOptions for Diagnosed {
+    ///     const DEADLOCK_POLICY: DeadlockPolicy = DeadlockPolicy::Timeout(Duration::from_secs(1));
+    ///     const DIAGNOSTICS: bool = true;
+    /// }
+    ///
+    /// type MyUserData<T> = MutexData<T, Diagnosed>;
+    /// ```
+    const DIAGNOSTICS: bool = false;
+}
+
+/// A lock shared between all clones of a locking wrapper, with optional diagnostics state.
+#[derive(Debug)]
+struct Diagnosed<L> {
+    lock: L,
+    state: Option<LockState>,
+}
+
+impl<L> Diagnosed<L> {
+    #[inline]
+    fn new<OPT: LockOptions>(lock: L) -> Self {
+        Diagnosed {
+            lock,
+            state: OPT::DIAGNOSTICS.then(LockState::new),
+        }
+    }
+
+    /// Acquires the lock according to `OPT`. `try_now` must not block.
+    #[inline]
+    fn acquire<'a, C, OPT, G>(
+        &'a self,
+        access: Access,
+        try_now: impl FnOnce(&'a L) -> Option<G>,
+        block: impl FnOnce(&'a L) -> G,
+        block_for: impl FnOnce(&'a L, Duration) -> Option<G>,
+    ) -> Result<LockGuard<'a, G>, LockFailed>
+    where
+        C: NativeClass,
+        OPT: LockOptions,
+    {
+        let lock = &self.lock;
+
+        if let Some(state) = &self.state {
+            let (guard, holder) = state.acquire::<C, _>(
+                OPT::DEADLOCK_POLICY,
+                access,
+                || try_now(lock),
+                || block(lock),
+                |dur| block_for(lock, dur),
+            )?;
+
+            return Ok(LockGuard {
+                guard,
+                _holder: Some(holder),
+            });
+        }
+
+        let guard = match OPT::DEADLOCK_POLICY {
+            DeadlockPolicy::Allow => block(lock),
+            DeadlockPolicy::Pessimistic => try_now(lock).ok_or(LockFailed::Pessimistic)?,
+            DeadlockPolicy::Timeout(dur) => block_for(lock, dur).ok_or(LockFailed::Timeout(dur))?,
+        };
+
+        Ok(LockGuard {
+            guard,
+            _holder: None,
+        })
+    }
+}
+
+/// RAII guard returned by [`TryBorrow`] and [`TryBorrowMut`] implementations of the locking
+/// wrappers. The lock is released when the guard is dropped.
+pub struct LockGuard<'a, G> {
+    guard: G,
+    _holder: Option<HolderToken<'a>>,
+}
+
+impl<'a, G: Deref> Deref for LockGuard<'a, G> {
+    type Target = G::Target;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<'a, G: DerefMut> DerefMut for LockGuard<'a, G> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<'a, G: Deref> Debug for LockGuard<'a, G>
+where
+    G::Target: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
 }
 
 /// Default lock policy that may change in future versions.
@@ -248,7 +436,7 @@ impl std::error::Error for LockFailed {}
 /// `parking_lot`.
 #[derive(Debug)]
 pub struct MutexData<T, OPT = DefaultLockPolicy> {
-    lock: Arc<Mutex<T>>,
+    lock: Arc<Diagnosed<Mutex<T>>>,
     _marker: PhantomData<OPT>,
 }
 
@@ -262,7 +450,7 @@ where
     #[inline]
     fn new(val: Self::Target) -> Self {
         MutexData {
-            lock: Arc::new(Mutex::new(val)),
+            lock: Arc::new(Diagnosed::new::<OPT>(Mutex::new(val))),
             _marker: PhantomData,
         }
     }
@@ -275,14 +463,14 @@ where
     #[inline]
     unsafe fn consume_user_data_unchecked(ptr: *const libc::c_void) -> Self {
         MutexData {
-            lock: Arc::from_raw(ptr as *const Mutex<T>),
+            lock: Arc::from_raw(ptr as *const Diagnosed<Mutex<T>>),
             _marker: PhantomData,
         }
     }
 
     #[inline]
     unsafe fn clone_from_user_data_unchecked(ptr: *const libc::c_void) -> Self {
-        let borrowed = Arc::from_raw(ptr as *const Mutex<T>);
+        let borrowed = Arc::from_raw(ptr as *const Diagnosed<Mutex<T>>);
         let lock = borrowed.clone();
         mem::forget(borrowed);
         MutexData {
@@ -320,19 +508,50 @@ where
     where
         F: FnOnce(&mut T) -> U,
     {
-        let mut guard = match OPT::DEADLOCK_POLICY {
-            DeadlockPolicy::Allow => self.lock.lock(),
-            DeadlockPolicy::Pessimistic => self.lock.try_lock().ok_or(LockFailed::Pessimistic)?,
-            DeadlockPolicy::Timeout(dur) => self
-                .lock
-                .try_lock_for(dur)
-                .ok_or(LockFailed::Timeout(dur))?,
-        };
+        let mut guard = self.lock.acquire::<T, OPT, _>(
+            Access::Write,
+            |lock| lock.try_lock(),
+            |lock| lock.lock(),
+            |lock, dur| lock.try_lock_for(dur),
+        )?;
 
         Ok(op(&mut *guard))
     }
 }
 
+impl<'a, T, OPT> TryBorrow<'a> for MutexData<T, OPT>
+where
+    T: NativeClass + Send,
+    OPT: LockOptions,
+{
+    type Guard = LockGuard<'a, MutexGuard<'a, T>>;
+    type Err = LockFailed;
+
+    #[inline]
+    fn try_borrow(&'a self) -> Result<Self::Guard, LockFailed> {
+        self.try_borrow_mut()
+    }
+}
+
+impl<'a, T, OPT> TryBorrowMut<'a> for MutexData<T, OPT>
+where
+    T: NativeClass + Send,
+    OPT: LockOptions,
+{
+    type Guard = LockGuard<'a, MutexGuard<'a, T>>;
+    type Err = LockFailed;
+
+    #[inline]
+    fn try_borrow_mut(&'a self) -> Result<Self::Guard, LockFailed> {
+        self.lock.acquire::<T, OPT, _>(
+            Access::Write,
+            |lock| lock.try_lock(),
+            |lock| lock.lock(),
+            |lock, dur| lock.try_lock_for(dur),
+        )
+    }
+}
+
 impl<T, OPT> Clone for MutexData<T, OPT> {
     #[inline]
     fn clone(&self) -> Self {
@@ -349,7 +568,7 @@ impl<T, OPT> Clone for MutexData<T, OPT> {
 /// `parking_lot`.
 #[derive(Debug)]
 pub struct RwLockData<T, OPT = DefaultLockPolicy> {
-    lock: Arc<RwLock<T>>,
+    lock: Arc<Diagnosed<RwLock<T>>>,
     _marker: PhantomData<OPT>,
 }
 
@@ -363,7 +582,7 @@ where
     #[inline]
     fn new(val: Self::Target) -> Self {
         RwLockData {
-            lock: Arc::new(RwLock::new(val)),
+            lock: Arc::new(Diagnosed::new::<OPT>(RwLock::new(val))),
             _marker: PhantomData,
         }
     }
@@ -376,14 +595,14 @@ where
     #[inline]
     unsafe fn consume_user_data_unchecked(ptr: *const libc::c_void) -> Self {
         RwLockData {
-            lock: Arc::from_raw(ptr as *const RwLock<T>),
+            lock: Arc::from_raw(ptr as *const Diagnosed<RwLock<T>>),
             _marker: PhantomData,
         }
     }
 
     #[inline]
     unsafe fn clone_from_user_data_unchecked(ptr: *const libc::c_void) -> Self {
-        let borrowed = Arc::from_raw(ptr as *const RwLock<T>);
+        let borrowed = Arc::from_raw(ptr as *const Diagnosed<RwLock<T>>);
         let lock = borrowed.clone();
         mem::forget(borrowed);
         RwLockData {
@@ -405,14 +624,12 @@ where
     where
         F: FnOnce(&T) -> U,
     {
-        let guard = match OPT::DEADLOCK_POLICY {
-            DeadlockPolicy::Allow => self.lock.read(),
-            DeadlockPolicy::Pessimistic => self.lock.try_read().ok_or(LockFailed::Pessimistic)?,
-            DeadlockPolicy::Timeout(dur) => self
-                .lock
-                .try_read_for(dur)
-                .ok_or(LockFailed::Timeout(dur))?,
-        };
+        let guard = self.lock.acquire::<T, OPT, _>(
+            Access::Read,
+            |lock| lock.try_read(),
+            |lock| lock.read(),
+            |lock, dur| lock.try_read_for(dur),
+        )?;
 
         Ok(op(&*guard))
     }
@@ -430,19 +647,55 @@ where
     where
         F: FnOnce(&mut T) -> U,
     {
-        let mut guard = match OPT::DEADLOCK_POLICY {
-            DeadlockPolicy::Allow => self.lock.write(),
-            DeadlockPolicy::Pessimistic => self.lock.try_write().ok_or(LockFailed::Pessimistic)?,
-            DeadlockPolicy::Timeout(dur) => self
-                .lock
-                .try_write_for(dur)
-                .ok_or(LockFailed::Timeout(dur))?,
-        };
+        let mut guard = self.lock.acquire::<T, OPT, _>(
+            Access::Write,
+            |lock| lock.try_write(),
+            |lock| lock.write(),
+            |lock, dur| lock.try_write_for(dur),
+        )?;
 
         Ok(op(&mut *guard))
     }
 }
 
+impl<'a, T, OPT> TryBorrow<'a> for RwLockData<T, OPT>
+where
+    T: NativeClass + Send + Sync,
+    OPT: LockOptions,
+{
+    type Guard = LockGuard<'a, RwLockReadGuard<'a, T>>;
+    type Err = LockFailed;
+
+    #[inline]
+    fn try_borrow(&'a self) -> Result<Self::Guard, LockFailed> {
+        self.lock.acquire::<T, OPT, _>(
+            Access::Read,
+            |lock| lock.try_read(),
+            |lock| lock.read(),
+            |lock, dur| lock.try_read_for(dur),
+        )
+    }
+}
+
+impl<'a, T, OPT> TryBorrowMut<'a> for RwLockData<T, OPT>
+where
+    T: NativeClass + Send + Sync,
+    OPT: LockOptions,
+{
+    type Guard = LockGuard<'a, RwLockWriteGuard<'a, T>>;
+    type Err = LockFailed;
+
+    #[inline]
+    fn try_borrow_mut(&'a self) -> Result<Self::Guard, LockFailed> {
+        self.lock.acquire::<T, OPT, _>(
+            Access::Write,
+            |lock| lock.try_write(),
+            |lock| lock.write(),
+            |lock, dur| lock.try_write_for(dur),
+        )
+    }
+}
+
 impl<T, OPT> Clone for RwLockData<T, OPT> {
     #[inline]
     fn clone(&self) -> Self {
@@ -517,6 +770,19 @@ where
     }
 }
 
+impl<'a, T> TryBorrow<'a> for ArcData<T>
+where
+    T: NativeClass + Send + Sync,
+{
+    type Guard = &'a T;
+    type Err = Infallible;
+
+    #[inline]
+    fn try_borrow(&'a self) -> Result<&'a T, Infallible> {
+        Ok(&*self.0)
+    }
+}
+
 impl<T> Clone for ArcData<T> {
     #[inline]
     fn clone(&self) -> Self {
@@ -524,6 +790,201 @@ impl<T> Clone for ArcData<T> {
     }
 }
 
+/// User-data wrapper for read-mostly data, with lock-free reads and copy-on-write updates.
+///
+/// The current version of `T` is stored behind an atomically swappable `Arc<T>`:
+///
+/// - `map` loads the current version without taking any locks, and never blocks, even while
+///   the value is being updated.
+/// - `map_mut` clones the current version, applies the changes to the clone, and publishes it
+///   atomically as the new version. Calls to `map` that started before the update will still
+///   see the old version, which is dropped once the last reader is done with it.
+///
+/// Only one `map_mut` call may be in progress at a time, so no updates are lost. Calls from
+/// other threads block until the current update is published. Calls from the thread that is
+/// already updating the value fail with `LockFailed::Pessimistic` instead of deadlocking, for
+/// example when a `&mut self` method emits a signal or calls a script that calls back into the
+/// same instance.
+///
+/// As every update clones `T`, this is only suitable for types that are cheap to clone, or
+/// written rarely compared to reads, such as configuration data read from many threads every
+/// frame.
+///
+/// The underlying implementation may change in the future. The current implementation is
+/// `arc-swap`.
+pub struct ArcSwapData<T> {
+    inner: Arc<arc_swap_data::Inner<T>>,
+}
+
+mod arc_swap_data {
+    use std::sync::Arc;
+    use std::thread::{self, ThreadId};
+
+    use arc_swap::ArcSwap;
+    use parking_lot::{Mutex, MutexGuard};
+
+    pub struct Inner<T> {
+        pub current: ArcSwap<T>,
+        writer: Mutex<()>,
+        writer_thread: Mutex<Option<ThreadId>>,
+    }
+
+    /// Guard for an update in progress. Clears the recorded writer thread when dropped, even
+    /// if the update panics.
+    pub struct WriteGuard<'a, T> {
+        inner: &'a Inner<T>,
+        _writer: MutexGuard<'a, ()>,
+    }
+
+    impl<T> Inner<T> {
+        #[inline]
+        pub fn new(val: T) -> Self {
+            Inner {
+                current: ArcSwap::new(Arc::new(val)),
+                writer: Mutex::new(()),
+                writer_thread: Mutex::new(None),
+            }
+        }
+
+        /// Waits for updates on other threads to finish, and returns `None` if the current
+        /// thread is already updating the value.
+        #[inline]
+        pub fn write(&self) -> Option<WriteGuard<'_, T>> {
+            let current = thread::current().id();
+
+            // Only the current thread can store its own ID, so this can't change under us.
+            if *self.writer_thread.lock() == Some(current) {
+                return None;
+            }
+
+            let writer = self.writer.lock();
+            *self.writer_thread.lock() = Some(current);
+
+            Some(WriteGuard {
+                inner: self,
+                _writer: writer,
+            })
+        }
+    }
+
+    impl<'a, T> Drop for WriteGuard<'a, T> {
+        #[inline]
+        fn drop(&mut self) {
+            *self.inner.writer_thread.lock() = None;
+        }
+    }
+}
+
+impl<T> ArcSwapData<T> {
+    /// Returns the current version of the value. Later updates are not reflected in the
+    /// returned `Arc<T>`.
+    #[inline]
+    pub fn load_full(&self) -> Arc<T> {
+        self.inner.current.load_full()
+    }
+}
+
+impl<T: Debug> Debug for ArcSwapData<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArcSwapData")
+            .field(&**self.inner.current.load())
+            .finish()
+    }
+}
+
+unsafe impl<T> UserData for ArcSwapData<T>
+where
+    T: NativeClass + Clone + Send + Sync,
+{
+    type Target = T;
+
+    #[inline]
+    fn new(val: Self::Target) -> Self {
+        ArcSwapData {
+            inner: Arc::new(arc_swap_data::Inner::new(val)),
+        }
+    }
+
+    #[inline]
+    fn into_user_data(self) -> *const libc::c_void {
+        Arc::into_raw(self.inner) as *const libc::c_void
+    }
+
+    #[inline]
+    unsafe fn consume_user_data_unchecked(ptr: *const libc::c_void) -> Self {
+        ArcSwapData {
+            inner: Arc::from_raw(ptr as *const arc_swap_data::Inner<T>),
+        }
+    }
+
+    #[inline]
+    unsafe fn clone_from_user_data_unchecked(ptr: *const libc::c_void) -> Self {
+        let borrowed = Arc::from_raw(ptr as *const arc_swap_data::Inner<T>);
+        let inner = borrowed.clone();
+        mem::forget(borrowed);
+        ArcSwapData { inner }
+    }
+}
+
+impl<T> Map for ArcSwapData<T>
+where
+    T: NativeClass + Clone + Send + Sync,
+{
+    type Err = Infallible;
+
+    #[inline]
+    fn map<F, U>(&self, op: F) -> Result<U, Infallible>
+    where
+        F: FnOnce(&T) -> U,
+    {
+        Ok(op(&**self.inner.current.load()))
+    }
+}
+
+impl<T> MapMut for ArcSwapData<T>
+where
+    T: NativeClass + Clone + Send + Sync,
+{
+    type Err = LockFailed;
+
+    #[inline]
+    fn map_mut<F, U>(&self, op: F) -> Result<U, LockFailed>
+    where
+        F: FnOnce(&mut T) -> U,
+    {
+        let _writer = self.inner.write().ok_or(LockFailed::Pessimistic)?;
+
+        let mut val = T::clone(&**self.inner.current.load());
+        let ret = op(&mut val);
+        self.inner.current.store(Arc::new(val));
+
+        Ok(ret)
+    }
+}
+
+impl<'a, T> TryBorrow<'a> for ArcSwapData<T>
+where
+    T: NativeClass + Clone + Send + Sync,
+{
+    type Guard = Arc<T>;
+    type Err = Infallible;
+
+    #[inline]
+    fn try_borrow(&'a self) -> Result<Arc<T>, Infallible> {
+        Ok(self.load_full())
+    }
+}
+
+impl<T> Clone for ArcSwapData<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ArcSwapData {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
 /// User-data wrapper analogous to a `Arc<RefCell<T>>`, that is restricted to the thread
 /// where it was originally created. The destructor of `T` is not guaranteed to be run if
 /// this is actually shared across multiple threads.
@@ -701,6 +1162,36 @@ where
     }
 }
 
+impl<'a, T> TryBorrow<'a> for LocalCellData<T>
+where
+    T: NativeClass,
+{
+    type Guard = std::cell::Ref<'a, T>;
+    type Err = LocalCellError;
+
+    #[inline]
+    fn try_borrow(&'a self) -> Result<Self::Guard, Self::Err> {
+        self.inner
+            .try_borrow()
+            .map(|r| std::cell::Ref::map(r, |val| &**val))
+    }
+}
+
+impl<'a, T> TryBorrowMut<'a> for LocalCellData<T>
+where
+    T: NativeClass,
+{
+    type Guard = std::cell::RefMut<'a, T>;
+    type Err = LocalCellError;
+
+    #[inline]
+    fn try_borrow_mut(&'a self) -> Result<Self::Guard, Self::Err> {
+        self.inner
+            .try_borrow_mut()
+            .map(|w| std::cell::RefMut::map(w, |val| &mut **val))
+    }
+}
+
 impl<T> Clone for LocalCellData<T> {
     #[inline]
     fn clone(&self) -> Self {
@@ -710,6 +1201,260 @@ impl<T> Clone for LocalCellData<T> {
     }
 }
 
+/// User-data wrapper similar to [`LocalCellData`], that tolerates re-entrant method calls
+/// by queuing them until the outer borrow ends.
+///
+/// A common source of borrow failures are `&mut self` methods that emit signals or call into
+/// GDScript, which then call another method on the same instance before the outer method
+/// returns. With [`LocalCellData`] or [`MutexData`], the inner call fails with an error,
+/// because the outer `&mut self` borrow is still active. With `ReentrantCellData`:
+///
+/// - A **method call** made while a `&mut self` method is running on the same instance is not
+///   executed immediately. Instead, its arguments are copied, and `nil` is returned to the
+///   caller right away. The call is then executed as soon as the outermost borrow of the
+///   instance ends, i.e. right before the outer method returns to the engine. Queued calls run
+///   in the order they were made, and their return values are discarded.
+/// - A `&self` method called while only `&self` methods are running is executed immediately,
+///   like with [`LocalCellData`].
+/// - A `&mut self` method called while a `&self` method is running fails with
+///   [`LocalCellError::BorrowFailed`], since its queued execution would still happen inside
+///   the outer shared borrow.
+/// - **Property accessors** are never queued, since their results are needed immediately.
+///   They fail with [`LocalCellError::BorrowFailed`] on conflicting borrows.
+/// - Calls to [`Map::map`] or [`MapMut::map_mut`] made directly from Rust, e.g. through
+///   [`Instance::map_mut`][crate::object::Instance::map_mut], are never queued either.
+///
+/// This means that script code can't rely on the results or side effects of re-entrant calls
+/// until the outer method has returned. Methods that are expected to be called re-entrantly
+/// should therefore not return meaningful values.
+///
+/// Like [`LocalCellData`], the wrapper is restricted to the thread where it was originally
+/// created. Access from other threads fails with [`LocalCellError::DifferentThread`], and
+/// the destructor of `T` isn't run if the last reference is dropped on another thread.
+#[derive(Debug)]
+pub struct ReentrantCellData<T> {
+    inner: Arc<reentrant_cell::ReentrantCell<T>>,
+}
+
+mod reentrant_cell {
+    use std::cell::{Cell, RefCell};
+    use std::collections::VecDeque;
+    use std::fmt;
+    use std::mem::ManuallyDrop;
+    use std::thread::{self, ThreadId};
+
+    use super::LocalCellError;
+
+    type DeferredCall = Box<dyn FnOnce()>;
+
+    pub struct ReentrantCell<T> {
+        thread_id: ThreadId,
+        cell: RefCell<ManuallyDrop<T>>,
+        queue: RefCell<ManuallyDrop<VecDeque<DeferredCall>>>,
+        draining: Cell<bool>,
+    }
+
+    impl<T: fmt::Debug> fmt::Debug for ReentrantCell<T> {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("ReentrantCell")
+                .field("thread_id", &self.thread_id)
+                .field("cell", &self.cell)
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl<T> Drop for ReentrantCell<T> {
+        fn drop(&mut self) {
+            if self.thread_id == thread::current().id() {
+                unsafe {
+                    ManuallyDrop::drop(self.cell.get_mut());
+                    ManuallyDrop::drop(self.queue.get_mut());
+                }
+            }
+        }
+    }
+
+    impl<T> ReentrantCell<T> {
+        #[inline]
+        pub fn new(val: T) -> Self {
+            ReentrantCell {
+                thread_id: thread::current().id(),
+                cell: RefCell::new(ManuallyDrop::new(val)),
+                queue: RefCell::new(ManuallyDrop::new(VecDeque::new())),
+                draining: Cell::new(false),
+            }
+        }
+
+        #[inline]
+        fn check_thread(&self) -> Result<(), LocalCellError> {
+            let current = thread::current().id();
+
+            if self.thread_id == current {
+                Ok(())
+            } else {
+                Err(LocalCellError::DifferentThread {
+                    original: self.thread_id,
+                    current,
+                })
+            }
+        }
+
+        #[inline]
+        pub fn map<F, U>(&self, op: F) -> Result<U, LocalCellError>
+        where
+            F: FnOnce(&T) -> U,
+        {
+            self.check_thread()?;
+            let ret = {
+                let r = self
+                    .cell
+                    .try_borrow()
+                    .map_err(|_| LocalCellError::BorrowFailed)?;
+                op(&r)
+            };
+            self.drain_queue();
+            Ok(ret)
+        }
+
+        #[inline]
+        pub fn map_mut<F, U>(&self, op: F) -> Result<U, LocalCellError>
+        where
+            F: FnOnce(&mut T) -> U,
+        {
+            self.check_thread()?;
+            let ret = {
+                let mut w = self
+                    .cell
+                    .try_borrow_mut()
+                    .map_err(|_| LocalCellError::BorrowFailed)?;
+                op(&mut w)
+            };
+            self.drain_queue();
+            Ok(ret)
+        }
+
+        /// Queues a call if the cell is currently borrowed mutably on this thread.
+        #[inline]
+        pub fn try_defer<F>(&self, make_call: F) -> bool
+        where
+            F: FnOnce() -> DeferredCall,
+        {
+            if self.check_thread().is_err() || self.cell.try_borrow().is_ok() {
+                return false;
+            }
+
+            self.queue.borrow_mut().push_back(make_call());
+            true
+        }
+
+        /// Runs queued calls once the outermost borrow has ended. Calls that are queued while
+        /// draining are run in the same loop.
+        fn drain_queue(&self) {
+            if self.draining.get() || self.cell.try_borrow_mut().is_err() {
+                return;
+            }
+
+            self.draining.set(true);
+            loop {
+                // The borrow on the queue must not be held while running the call.
+                let call = self.queue.borrow_mut().pop_front();
+                match call {
+                    Some(call) => call(),
+                    None => break,
+                }
+            }
+            self.draining.set(false);
+        }
+    }
+
+    // Implementing Send + Sync is ok because the cell and the queue are guarded from access
+    // outside the original thread.
+    unsafe impl<T> Send for ReentrantCell<T> {}
+    unsafe impl<T> Sync for ReentrantCell<T> {}
+}
+
+unsafe impl<T> UserData for ReentrantCellData<T>
+where
+    T: NativeClass,
+{
+    type Target = T;
+
+    #[inline]
+    fn new(val: Self::Target) -> Self {
+        ReentrantCellData {
+            inner: Arc::new(reentrant_cell::ReentrantCell::new(val)),
+        }
+    }
+
+    #[inline]
+    fn into_user_data(self) -> *const libc::c_void {
+        Arc::into_raw(self.inner) as *const libc::c_void
+    }
+
+    #[inline]
+    unsafe fn consume_user_data_unchecked(ptr: *const libc::c_void) -> Self {
+        ReentrantCellData {
+            inner: Arc::from_raw(ptr as *const reentrant_cell::ReentrantCell<T>),
+        }
+    }
+
+    #[inline]
+    unsafe fn clone_from_user_data_unchecked(ptr: *const libc::c_void) -> Self {
+        let borrowed = Arc::from_raw(ptr as *const reentrant_cell::ReentrantCell<T>);
+        let arc = borrowed.clone();
+        mem::forget(borrowed);
+        ReentrantCellData { inner: arc }
+    }
+
+    #[inline]
+    fn try_defer_call<F>(&self, make_call: F) -> bool
+    where
+        F: FnOnce() -> Box<dyn FnOnce()>,
+    {
+        self.inner.try_defer(make_call)
+    }
+}
+
+impl<T> Map for ReentrantCellData<T>
+where
+    T: NativeClass,
+{
+    type Err = LocalCellError;
+
+    #[inline]
+    fn map<F, U>(&self, op: F) -> Result<U, Self::Err>
+    where
+        F: FnOnce(&Self::Target) -> U,
+    {
+        self.inner.map(op)
+    }
+}
+
+impl<T> MapMut for ReentrantCellData<T>
+where
+    T: NativeClass,
+{
+    type Err = LocalCellError;
+
+    #[inline]
+    fn map_mut<F, U>(&self, op: F) -> Result<U, Self::Err>
+    where
+        F: FnOnce(&mut Self::Target) -> U,
+    {
+        self.inner.map_mut(op)
+    }
+}
+
+impl<T> Clone for ReentrantCellData<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ReentrantCellData {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
 /// Special user-data wrapper intended for zero-sized types, that does not perform any
 /// allocation or synchronization at runtime. Does not implement `MapMut`.
 ///
@@ -173,17 +173,19 @@ impl InitHandle {
                     };
 
                     let val = match panic::catch_unwind(AssertUnwindSafe(|| {
-                        emplace::take().unwrap_or_else(|| {
-                            C::nativeclass_init(TRef::new(C::Base::cast_ref(owner)))
-                        })
+                        let owner = TRef::new(C::Base::cast_ref(owner));
+                        let mut val = emplace::take().unwrap_or_else(|| C::nativeclass_init(owner));
+                        val.nativeclass_attach_base(owner);
+                        val
                     })) {
                         Ok(val) => val,
                         Err(e) => {
-                            godot_error!(
-                                "gdnative-core: error constructing {}: constructor panicked",
-                                class_registry::class_name_or_default::<C>(),
+                            crate::init::panic_policy::report_panic(
+                                "constructor",
+                                Some(&class_registry::class_name_or_default::<C>()),
+                                None,
+                                e,
                             );
-                            crate::private::print_panic_error(e);
                             return ptr::null_mut();
                         }
                     };
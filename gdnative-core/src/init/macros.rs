@@ -35,6 +35,8 @@ macro_rules! godot_nativescript_init {
                 return;
             }
 
+            $crate::private::record_main_thread();
+
             $crate::private::report_panics("nativescript_init", || {
                 $crate::init::auto_register($crate::init::InitHandle::new(
                     handle,
@@ -39,11 +39,17 @@
 mod info;
 mod init_handle;
 mod macros;
+pub(crate) mod panic_policy;
+pub(crate) mod thread_affinity;
 
 pub mod diagnostics;
 
 pub use info::*;
 pub use init_handle::*;
+pub use panic_policy::*;
+pub use thread_affinity::{
+    is_main_thread, main_thread_check, main_thread_id, set_main_thread_check, MainThreadCheck,
+};
 
 bitflags::bitflags! {
     /// Initialization level used to distinguish the source of init actions, such as class registration.
@@ -0,0 +1,420 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr::{self, NonNull};
+use std::sync::Once;
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+
+use crate::core_types::{Dictionary, FromVariant, GodotString, Variant, VariantArray};
+use crate::log::Site;
+use crate::object::RawObject;
+use crate::private::{get_api, ReferenceCountedClassPlaceholder};
+
+type PanicHandler = Box<dyn Fn(&PanicReport<'_>) + Send + Sync>;
+
+static POLICY: Lazy<RwLock<PanicPolicy>> = Lazy::new(|| RwLock::new(PanicPolicy::default()));
+static HANDLER: Lazy<RwLock<Option<PanicHandler>>> = Lazy::new(|| RwLock::new(None));
+
+thread_local! {
+    static LAST_PANIC: RefCell<Option<CapturedPanic>> = RefCell::default();
+}
+
+/// Policy deciding how panics in exported methods, property accessors, constructors and
+/// init callbacks are reported.
+///
+/// Panics never unwind into the engine. When a panic is caught, the failed call returns `nil`
+/// to the caller unless the policy is [`PanicPolicy::Abort`]. The policy is global to the
+/// library, and is usually set once in the `godot_gdnative_init` or `godot_nativescript_init`
+/// callback using [`set_panic_policy`]:
+///
+/// ```no_run
+/// use gdnative::prelude::*;
+/// use gdnative::init::PanicPolicy;
+///
+/// fn init(handle: InitHandle) {
+///     let policy = if cfg!(debug_assertions) {
+///         PanicPolicy::Abort
+///     } else {
+///         PanicPolicy::LogBacktrace
+///     };
+///
+///     gdnative::init::set_panic_policy(policy);
+/// }
+///
+/// godot_init!(init);
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum PanicPolicy {
+    /// Log the panic message to the Godot console and continue. This is the default.
+    Log,
+
+    /// Log the panic message along with the Rust call path: the exported method, accessor or
+    /// callback that was being called, the location of the panic, and a Rust backtrace.
+    LogBacktrace,
+
+    /// Log the panic message along with the GDScript call stack, as returned by `get_stack()`,
+    /// that led to the failed call.
+    ///
+    /// The GDScript stack is only available when the game is run with the script debugger,
+    /// e.g. from the editor. Otherwise, it will be reported as empty.
+    LogScriptStack,
+
+    /// Log a full report containing all information above, then abort the process. This is
+    /// intended for QA or CI builds, where panics must not go unnoticed.
+    Abort,
+}
+
+impl Default for PanicPolicy {
+    #[inline]
+    fn default() -> Self {
+        PanicPolicy::Log
+    }
+}
+
+impl PanicPolicy {
+    #[inline]
+    fn captures_backtrace(self) -> bool {
+        matches!(self, PanicPolicy::LogBacktrace | PanicPolicy::Abort)
+    }
+
+    #[inline]
+    fn captures_script_stack(self) -> bool {
+        matches!(self, PanicPolicy::LogScriptStack | PanicPolicy::Abort)
+    }
+}
+
+/// Sets the global [`PanicPolicy`] of the library.
+///
+/// The policy is reset to the default when the library is terminated.
+#[inline]
+pub fn set_panic_policy(policy: PanicPolicy) {
+    install_capture_hook();
+    *POLICY.write() = policy;
+}
+
+/// Returns the current global [`PanicPolicy`].
+#[inline]
+pub fn panic_policy() -> PanicPolicy {
+    *POLICY.read()
+}
+
+/// Sets a custom handler that is called with a [`PanicReport`] whenever a panic is caught,
+/// e.g. to forward it to a crash reporting service. Replaces any previously set handler.
+///
+/// The handler is called after the report is logged according to the current [`PanicPolicy`],
+/// and before the process is aborted if the policy is [`PanicPolicy::Abort`]. The report only
+/// contains the information collected under the current policy. Panics inside the handler are
+/// caught and logged.
+///
+/// The handler is removed when the library is terminated.
+#[inline]
+pub fn set_panic_handler<F>(handler: F)
+where
+    F: Fn(&PanicReport<'_>) + Send + Sync + 'static,
+{
+    install_capture_hook();
+    *HANDLER.write() = Some(Box::new(handler));
+}
+
+/// Removes the custom panic handler, if any.
+#[inline]
+pub fn clear_panic_handler() {
+    *HANDLER.write() = None;
+}
+
+/// A frame in the GDScript call stack, as returned by `get_stack()`.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ScriptFrame {
+    /// Name of the function.
+    pub function: String,
+    /// Path of the script resource.
+    pub source: String,
+    /// Line number of the call.
+    pub line: i64,
+}
+
+impl fmt::Display for ScriptFrame {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}:{})", self.function, self.source, self.line)
+    }
+}
+
+/// Information about a caught panic, passed to the handler set with [`set_panic_handler`].
+///
+/// The `Display` implementation formats all available information into a multi-line report.
+#[derive(Debug)]
+pub struct PanicReport<'a> {
+    context: &'a str,
+    class_name: Option<&'a str>,
+    site: Option<Site<'a>>,
+    message: Option<&'a str>,
+    location: Option<&'a str>,
+    backtrace: Option<&'a str>,
+    script_stack: Option<&'a [ScriptFrame]>,
+}
+
+impl<'a> PanicReport<'a> {
+    /// Returns a human-readable description of the kind of call that panicked, e.g.
+    /// `"method"` or `"property setter"`. The format is not guaranteed.
+    #[inline]
+    pub fn context(&self) -> &'a str {
+        self.context
+    }
+
+    /// Returns the name of the `NativeClass` involved, if any.
+    #[inline]
+    pub fn class_name(&self) -> Option<&'a str> {
+        self.class_name
+    }
+
+    /// Returns the site of the exported method that was being called, if known.
+    #[inline]
+    pub fn site(&self) -> Option<Site<'a>> {
+        self.site
+    }
+
+    /// Returns the panic message, if the payload is a string.
+    #[inline]
+    pub fn message(&self) -> Option<&'a str> {
+        self.message
+    }
+
+    /// Returns the source location where the panic occurred, if captured.
+    #[inline]
+    pub fn location(&self) -> Option<&'a str> {
+        self.location
+    }
+
+    /// Returns the formatted Rust backtrace, if captured under the current policy.
+    #[inline]
+    pub fn backtrace(&self) -> Option<&'a str> {
+        self.backtrace
+    }
+
+    /// Returns the GDScript call stack, if captured under the current policy.
+    #[inline]
+    pub fn script_stack(&self) -> Option<&'a [ScriptFrame]> {
+        self.script_stack
+    }
+}
+
+impl<'a> fmt::Display for PanicReport<'a> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "gdnative-core: {} ", self.context)?;
+        if let Some(class_name) = self.class_name {
+            write!(f, "of {class_name} ")?;
+        }
+        write!(
+            f,
+            "panicked: {}",
+            self.message.unwrap_or("<non-string payload>")
+        )?;
+
+        if let Some(site) = &self.site {
+            write!(f, "\n  in {site}")?;
+        }
+        if let Some(location) = self.location {
+            write!(f, "\n  at {location}")?;
+        }
+
+        if let Some(script_stack) = self.script_stack {
+            write!(f, "\nGDScript stack:")?;
+            if script_stack.is_empty() {
+                write!(f, "\n  <unavailable, is the script debugger active?>")?;
+            }
+            for (i, frame) in script_stack.iter().enumerate() {
+                write!(f, "\n  {i}: {frame}")?;
+            }
+        }
+
+        if let Some(backtrace) = self.backtrace {
+            write!(f, "\nRust backtrace:\n{backtrace}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Information captured by the panic hook, before the stack is unwound.
+struct CapturedPanic {
+    location: Option<String>,
+    backtrace: Option<backtrace::Backtrace>,
+}
+
+/// Installs a panic hook that captures the location and optionally a backtrace of each panic,
+/// chaining to the previous hook.
+fn install_capture_hook() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let wants_backtrace = POLICY.try_read().map_or(false, |p| p.captures_backtrace())
+                || HANDLER.try_read().map_or(false, |h| h.is_some());
+
+            let captured = CapturedPanic {
+                location: info.location().map(|location| location.to_string()),
+                backtrace: wants_backtrace.then(backtrace::Backtrace::new),
+            };
+
+            let _ = LAST_PANIC.try_with(|cell| {
+                if let Ok(mut cell) = cell.try_borrow_mut() {
+                    *cell = Some(captured);
+                }
+            });
+
+            previous(info);
+        }));
+    });
+}
+
+/// Reports a panic caught at an FFI boundary according to the current policy. `context` is a
+/// short description of the kind of call that panicked.
+pub(crate) fn report_panic(
+    context: &str,
+    class_name: Option<&str>,
+    site: Option<Site<'_>>,
+    payload: Box<dyn Any + Send>,
+) {
+    let policy = panic_policy();
+    let captured = LAST_PANIC
+        .try_with(|cell| cell.try_borrow_mut().ok().and_then(|mut c| c.take()))
+        .ok()
+        .flatten();
+
+    let message = payload
+        .downcast_ref::<String>()
+        .map(String::as_str)
+        .or_else(|| payload.downcast_ref::<&'static str>().copied());
+
+    let location = captured.as_ref().and_then(|c| c.location.as_deref());
+    let backtrace = captured
+        .as_ref()
+        .and_then(|c| c.backtrace.as_ref())
+        .map(|backtrace| format!("{backtrace:?}"));
+    let script_stack = policy
+        .captures_script_stack()
+        .then(|| script_stack().unwrap_or_default());
+
+    let report = PanicReport {
+        context,
+        class_name,
+        site,
+        message,
+        location,
+        backtrace: backtrace.as_deref(),
+        script_stack: script_stack.as_deref(),
+    };
+
+    match policy {
+        PanicPolicy::Log => {
+            crate::log::error(
+                site.unwrap_or_default(),
+                format_args!("gdnative-core: {context} panicked (check stderr for output)"),
+            );
+            match message {
+                Some(message) => godot_error!("Panic message: {}", message),
+                None => godot_error!("Panic message unknown, type {:?}", (*payload).type_id()),
+            }
+        }
+        _ => crate::log::error(site.unwrap_or_default(), &report),
+    }
+
+    if let Some(handler) = &*HANDLER.read() {
+        if let Err(e) = panic::catch_unwind(AssertUnwindSafe(|| handler(&report))) {
+            godot_error!("gdnative-core: panic handler panicked");
+            crate::private::print_panic_error(e);
+        }
+    }
+
+    if policy == PanicPolicy::Abort {
+        eprintln!("{report}");
+        eprintln!("gdnative-core: aborting due to panic policy");
+        std::process::abort();
+    }
+}
+
+/// Resets the policy and removes the handler during `terminate`.
+pub(crate) fn cleanup() {
+    *POLICY.write() = PanicPolicy::default();
+    *HANDLER.write() = None;
+}
+
+/// Retrieves the current GDScript call stack, by calling `get_stack()` from a temporary
+/// script. Returns `None` if the GDScript module is unavailable.
+fn script_stack() -> Option<Vec<ScriptFrame>> {
+    const SOURCE: &str = "static func __gdnative_get_stack():\n\treturn get_stack()\n";
+    const FUNCTION: &str = "__gdnative_get_stack";
+
+    let stack = unsafe {
+        let api = get_api();
+        let script_methods = crate::private::ScriptMethodTable::get(api);
+        let object_methods = crate::private::ObjectMethodTable::get(api);
+
+        // The API functions take NUL-terminated C strings. &CStr is not used for its runtime cost.
+        let class_name = b"GDScript\0".as_ptr() as *const libc::c_char;
+        let ctor = (api.godot_get_class_constructor)(class_name)?;
+        let script = NonNull::new(ctor())?;
+        let script = RawObject::<ReferenceCountedClassPlaceholder>::from_sys_ref_unchecked(script);
+        script.init_ref_count();
+
+        let source = GodotString::from_str(SOURCE);
+        let mut args: [*const libc::c_void; 1] = [source.sys() as *const _];
+        (api.godot_method_bind_ptrcall)(
+            script_methods.set_source_code,
+            script.sys().as_ptr(),
+            args.as_mut_ptr(),
+            ptr::null_mut(),
+        );
+
+        let keep_state = false;
+        let mut err: i64 = 0;
+        let mut args: [*const libc::c_void; 1] = [&keep_state as *const bool as *const _];
+        (api.godot_method_bind_ptrcall)(
+            script_methods.reload,
+            script.sys().as_ptr(),
+            args.as_mut_ptr(),
+            &mut err as *mut i64 as *mut _,
+        );
+
+        let stack = if err == 0 {
+            let method = Variant::new(FUNCTION);
+            let mut args: [*const sys::godot_variant; 1] = [method.sys()];
+            let ret = (api.godot_method_bind_call)(
+                object_methods.call,
+                script.sys().as_ptr(),
+                args.as_mut_ptr(),
+                1,
+                ptr::null_mut(),
+            );
+            Some(Variant::from_sys(ret))
+        } else {
+            None
+        };
+
+        script.unref_and_free_if_last();
+        stack?
+    };
+
+    let frames = VariantArray::from_variant(&stack).ok()?;
+    let frames = frames
+        .iter()
+        .filter_map(|frame| {
+            let frame = Dictionary::from_variant(&frame).ok()?;
+            Some(ScriptFrame {
+                function: frame.get("function")?.try_to().ok()?,
+                source: frame.get("source")?.try_to().ok()?,
+                line: frame.get("line")?.try_to().ok()?,
+            })
+        })
+        .filter(|frame| frame.function != FUNCTION)
+        .collect();
+
+    Some(frames)
+}
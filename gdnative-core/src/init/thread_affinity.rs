@@ -0,0 +1,94 @@
+use std::thread::{self, ThreadId};
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+
+static MAIN_THREAD: Lazy<RwLock<Option<ThreadId>>> = Lazy::new(|| RwLock::new(None));
+static POLICY: Lazy<RwLock<MainThreadCheck>> =
+    Lazy::new(|| RwLock::new(MainThreadCheck::default()));
+
+/// What to do when a node inside the scene tree is accessed from a thread other than the main
+/// thread, as detected by the `main-thread-checks` feature.
+///
+/// Godot's [thread-safety guidelines][thread-safety] forbid interacting with the active scene
+/// tree from other threads. Violations are data races inside the engine, which usually surface
+/// as random crashes far away from their cause. With `main-thread-checks` enabled,
+/// `Ref::assume_safe` and the methods of `Node` and its subclasses check the calling thread,
+/// and report violations with the caller location.
+///
+/// The policy is global to the library, and can be changed at any time with
+/// [`set_main_thread_check`]. Without the `main-thread-checks` feature, it has no effect.
+///
+/// [thread-safety]: https://docs.godotengine.org/en/stable/tutorials/threads/thread_safe_apis.html
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum MainThreadCheck {
+    /// Log a warning to the Godot console and continue.
+    Warn,
+
+    /// Panic at the call site. This is the default.
+    Panic,
+}
+
+impl Default for MainThreadCheck {
+    #[inline]
+    fn default() -> Self {
+        MainThreadCheck::Panic
+    }
+}
+
+/// Sets how accesses to the scene tree from other threads are reported. See [`MainThreadCheck`].
+#[inline]
+pub fn set_main_thread_check(policy: MainThreadCheck) {
+    *POLICY.write() = policy;
+}
+
+/// Returns the current [`MainThreadCheck`] policy.
+#[inline]
+pub fn main_thread_check() -> MainThreadCheck {
+    *POLICY.read()
+}
+
+/// Returns the ID of the main thread of the engine, which is recorded when
+/// `godot_nativescript_init` is called. Returns `None` before that.
+#[inline]
+pub fn main_thread_id() -> Option<ThreadId> {
+    *MAIN_THREAD.read()
+}
+
+/// Returns `true` if called from the main thread of the engine, or if the main thread isn't
+/// known yet.
+#[inline]
+pub fn is_main_thread() -> bool {
+    main_thread_id().map_or(true, |id| id == thread::current().id())
+}
+
+/// Records the current thread as the main thread.
+pub(crate) fn record_main_thread() {
+    *MAIN_THREAD.write() = Some(thread::current().id());
+}
+
+/// Reports an access to a node inside the scene tree from another thread, according to the
+/// current policy.
+#[cfg(feature = "main-thread-checks")]
+#[track_caller]
+pub(crate) fn report_violation(op: &str, class_name: &str, instance_id: i64) {
+    let site = std::panic::Location::caller();
+    let thread = thread::current();
+    let msg = format!(
+        "{op} on a node of class {class_name} (instance ID {instance_id}) inside the scene tree from thread {} \
+         ({:?}), which is not the main thread, at {site}",
+        thread.name().unwrap_or("<unnamed>"),
+        thread.id(),
+    );
+
+    match main_thread_check() {
+        MainThreadCheck::Warn => crate::log::warn(Default::default(), msg),
+        MainThreadCheck::Panic => panic!("{msg}"),
+    }
+}
+
+/// Forgets the main thread during `terminate`.
+pub(crate) fn cleanup() {
+    *MAIN_THREAD.write() = None;
+}
@@ -0,0 +1,116 @@
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+
+use crate::object::ownership::Shared;
+use crate::object::{GodotObject, TRef};
+
+/// Reference to the base object of a `NativeClass`, meant to be used as a field of the script
+/// type, so the base object doesn't have to be passed around as a parameter.
+///
+/// Fields of this type marked with `#[base]` are attached by the `NativeClass` derive macro right
+/// after the script instance is created, and are empty before that. The derive macro also
+/// generates a `base` method for the script type:
+///
+/// ```no_run
+/// use gdnative::prelude::*;
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node)]
+/// struct Player {
+///     #[base]
+///     base: Base<Node>,
+/// }
+///
+/// #[methods]
+/// impl Player {
+///     fn new(_base: &Node) -> Self {
+///         Player { base: Base::new() }
+///     }
+///
+///     #[method]
+///     fn _ready(&self) {
+///         self.rename("Player");
+///     }
+///
+///     fn rename(&self, name: &str) {
+///         self.base().set_name(name);
+///     }
+/// }
+/// ```
+///
+/// The reference does not keep the base object alive, and is stored as an instance ID, so access
+/// is checked even if the script value outlives the object, for example when it is taken out of
+/// its user data wrapper.
+pub struct Base<T: GodotObject> {
+    instance_id: Option<i64>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: GodotObject> Base<T> {
+    /// Creates an unattached `Base`, to be attached when the script instance is created.
+    #[inline]
+    pub fn new() -> Self {
+        Base {
+            instance_id: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Attaches the base object. This is called automatically for fields of script types
+    /// deriving `NativeClass`.
+    #[inline]
+    pub fn attach(&mut self, base: TRef<'_, T, Shared>) {
+        self.instance_id = Some(base.as_raw().instance_id());
+    }
+
+    /// Returns a reference to the base object, or `None` if not attached yet or if the object
+    /// has been freed.
+    ///
+    /// The base object outlives its script instance, so the returned reference is bound to the
+    /// borrow of the field, like the `owner` reference of exported methods.
+    #[inline]
+    pub fn try_get(&self) -> Option<TRef<'_, T, Shared>> {
+        // SAFETY: The object was checked to be alive, and the reference can't outlive `self`.
+        unsafe { TRef::try_from_instance_id(self.instance_id?) }
+    }
+
+    /// Returns `true` if the base object is attached and still alive.
+    #[inline]
+    pub fn is_attached(&self) -> bool {
+        self.try_get().is_some()
+    }
+
+    /// Returns a reference to the base object. See [`try_get`](Self::try_get).
+    ///
+    /// # Panics
+    ///
+    /// If the script instance hasn't been created yet, for example when called from the
+    /// constructor, or if the base object has been freed.
+    #[inline]
+    pub fn get(&self) -> TRef<'_, T, Shared> {
+        self.try_get().unwrap_or_else(|| {
+            if self.instance_id.is_none() {
+                panic!(
+                    "Base<{}> is not attached yet (is it accessed from the constructor?)",
+                    T::class_name(),
+                )
+            } else {
+                panic!("Base<{}> refers to a freed object", T::class_name())
+            }
+        })
+    }
+}
+
+impl<T: GodotObject> Default for Base<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: GodotObject> Debug for Base<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Base<{}>({:?})", T::class_name(), self.instance_id)
+    }
+}
@@ -24,6 +24,10 @@ pub trait MemorySpec: Sized {
         Self: Memory;
 
     #[doc(hidden)]
+    #[cfg_attr(
+        any(feature = "checked-refs", feature = "main-thread-checks"),
+        track_caller
+    )]
     unsafe fn impl_assume_safe<'a, T: GodotObject<Memory = Self>>(
         this: &Ref<T, Shared>,
     ) -> TRef<'a, T, Shared>
@@ -31,6 +35,10 @@ pub trait MemorySpec: Sized {
         Self: Memory;
 
     #[doc(hidden)]
+    #[cfg_attr(
+        any(feature = "checked-refs", feature = "main-thread-checks"),
+        track_caller
+    )]
     unsafe fn impl_assume_unique<T: GodotObject<Memory = Self>>(
         this: Ref<T, Shared>,
     ) -> Ref<T, Unique>
@@ -71,20 +79,30 @@ impl MemorySpec for ManuallyManaged {
     }
 
     #[inline(always)]
+    #[cfg_attr(
+        any(feature = "checked-refs", feature = "main-thread-checks"),
+        track_caller
+    )]
     unsafe fn impl_assume_safe<'a, T: GodotObject<Memory = Self>>(
         this: &Ref<T, Shared>,
     ) -> TRef<'a, T, Shared> {
+        this.id.check::<T>(this.ptr.as_non_null(), "assume_safe");
         debug_assert!(
             this.is_instance_sane(),
             "assume_safe called on an invalid pointer"
         );
-        this.assume_safe_unchecked()
+        TRef::new(T::cast_ref(this.as_raw_unchecked()))
     }
 
     #[inline(always)]
+    #[cfg_attr(
+        any(feature = "checked-refs", feature = "main-thread-checks"),
+        track_caller
+    )]
     unsafe fn impl_assume_unique<T: GodotObject<Memory = Self>>(
         this: Ref<T, Shared>,
     ) -> Ref<T, Unique> {
+        this.id.check::<T>(this.ptr.as_non_null(), "assume_unique");
         debug_assert!(
             this.is_instance_sane(),
             "assume_unique called on an invalid pointer"
@@ -120,16 +138,26 @@ impl MemorySpec for RefCounted {
     }
 
     #[inline(always)]
+    #[cfg_attr(
+        any(feature = "checked-refs", feature = "main-thread-checks"),
+        track_caller
+    )]
     unsafe fn impl_assume_safe<'a, T: GodotObject<Memory = Self>>(
         this: &Ref<T, Shared>,
     ) -> TRef<'a, T, Shared> {
-        this.assume_safe_unchecked()
+        this.id.check::<T>(this.ptr.as_non_null(), "assume_safe");
+        TRef::new(T::cast_ref(this.as_raw_unchecked()))
     }
 
     #[inline(always)]
+    #[cfg_attr(
+        any(feature = "checked-refs", feature = "main-thread-checks"),
+        track_caller
+    )]
     unsafe fn impl_assume_unique<T: GodotObject<Memory = Self>>(
         this: Ref<T, Shared>,
     ) -> Ref<T, Unique> {
+        this.id.check::<T>(this.ptr.as_non_null(), "assume_unique");
         this.cast_access()
     }
 
@@ -220,6 +248,10 @@ impl<'a, 'r: 'a> LifetimeConstraint<RefCounted> for AssumeSafeLifetime<'a, 'r> {
 pub unsafe trait SafeDeref<Kind: Memory, Own: Ownership> {
     /// Returns a safe reference to the underlying object.
     #[doc(hidden)]
+    #[cfg_attr(
+        any(feature = "checked-refs", feature = "main-thread-checks"),
+        track_caller
+    )]
     fn impl_as_ref<T: GodotObject<Memory = Kind>>(this: &Ref<T, Own>) -> TRef<'_, T, Own>;
 }
 
@@ -240,6 +272,10 @@ pub struct RefImplBound {
 
 unsafe impl SafeDeref<ManuallyManaged, Unique> for RefImplBound {
     #[inline]
+    #[cfg_attr(
+        any(feature = "checked-refs", feature = "main-thread-checks"),
+        track_caller
+    )]
     fn impl_as_ref<T: GodotObject<Memory = ManuallyManaged>>(
         this: &Ref<T, Unique>,
     ) -> TRef<'_, T, Unique> {
@@ -249,6 +285,10 @@ unsafe impl SafeDeref<ManuallyManaged, Unique> for RefImplBound {
 
 unsafe impl<Own: LocalThreadOwnership> SafeDeref<RefCounted, Own> for RefImplBound {
     #[inline]
+    #[cfg_attr(
+        any(feature = "checked-refs", feature = "main-thread-checks"),
+        track_caller
+    )]
     fn impl_as_ref<T: GodotObject<Memory = RefCounted>>(this: &Ref<T, Own>) -> TRef<'_, T, Own> {
         unsafe { this.assume_safe_unchecked() }
     }
@@ -269,3 +309,173 @@ unsafe impl<Own: Ownership> SafeAsRaw<RefCounted, Own> for RefImplBound {
         unsafe { this.as_raw_unchecked() }
     }
 }
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Checked references
+
+/// Instance ID recorded by every `Ref` when the `checked-refs` feature is enabled, used to detect
+/// uses of freed objects. Zero-sized otherwise. This is an internal interface.
+#[doc(hidden)]
+#[derive(Copy, Clone, Debug)]
+pub struct RefId {
+    #[cfg(feature = "checked-refs")]
+    instance_id: i64,
+}
+
+impl RefId {
+    /// Records the instance ID of the object at `ptr`, which must be valid.
+    #[inline(always)]
+    pub(crate) unsafe fn of(ptr: NonNull<sys::godot_object>) -> Self {
+        #[cfg(feature = "checked-refs")]
+        {
+            RefId {
+                instance_id: RawObject::<ManuallyManagedClassPlaceholder>::from_sys_ref_unchecked(
+                    ptr,
+                )
+                .instance_id(),
+            }
+        }
+
+        #[cfg(not(feature = "checked-refs"))]
+        {
+            let _ = ptr;
+            RefId {}
+        }
+    }
+
+    /// Returns `false` if the object recorded in `self` is known to be freed, even if another
+    /// object now lives at `ptr`. Always returns `true` without `checked-refs`.
+    #[inline(always)]
+    pub(crate) unsafe fn is_current(self, ptr: NonNull<sys::godot_object>) -> bool {
+        #[cfg(feature = "checked-refs")]
+        {
+            (get_api().godot_is_instance_valid)(ptr.as_ptr())
+                && RawObject::<ManuallyManagedClassPlaceholder>::from_sys_ref_unchecked(ptr)
+                    .instance_id()
+                    == self.instance_id
+        }
+
+        #[cfg(not(feature = "checked-refs"))]
+        {
+            let _ = ptr;
+            true
+        }
+    }
+
+    /// Panics with the caller site if the object recorded in `self` was freed, or reports if it's
+    /// used from the wrong thread. No-op without `checked-refs` or `main-thread-checks`.
+    #[inline(always)]
+    #[cfg_attr(
+        any(feature = "checked-refs", feature = "main-thread-checks"),
+        track_caller
+    )]
+    pub(crate) unsafe fn check<T: GodotObject>(self, ptr: NonNull<sys::godot_object>, op: &str) {
+        #[cfg(feature = "checked-refs")]
+        check_alive::<T>(ptr, Some(self.instance_id), op);
+
+        #[cfg(feature = "main-thread-checks")]
+        check_thread::<T>(ptr, op);
+
+        #[cfg(not(any(feature = "checked-refs", feature = "main-thread-checks")))]
+        let _ = (ptr, op);
+    }
+}
+
+/// Panics with the caller site if `obj` was freed. Used by the `unsafe` methods of the generated
+/// bindings when the `checked-refs` feature is enabled. This is an internal interface.
+///
+/// # Safety
+///
+/// `obj` must have been a valid object at some point. It's fine if it has been freed since.
+#[doc(hidden)]
+#[cfg(feature = "checked-refs")]
+#[inline]
+#[track_caller]
+pub unsafe fn check_object_access<T: GodotObject>(obj: &RawObject<T>) {
+    check_alive::<T>(obj.sys(), None, "method call");
+}
+
+/// Reports if `obj` is a node inside the scene tree, and is used from a thread other than the
+/// main thread. Used by the methods of `Node` and its subclasses in the generated bindings when
+/// the `main-thread-checks` feature is enabled. This is an internal interface.
+///
+/// # Safety
+///
+/// `obj` must be a valid object.
+#[doc(hidden)]
+#[cfg(feature = "main-thread-checks")]
+#[inline]
+#[track_caller]
+pub unsafe fn check_main_thread_affinity<T: GodotObject>(obj: &RawObject<T>) {
+    check_thread::<T>(obj.sys(), "method call");
+}
+
+#[cfg(feature = "checked-refs")]
+fn describe_object(ptr: NonNull<sys::godot_object>, instance_id: Option<i64>) -> String {
+    match instance_id {
+        Some(id) => format!("instance ID {id}"),
+        None => format!("address {:p}", ptr.as_ptr()),
+    }
+}
+
+/// Panics if the object at `ptr` was freed, or if it's no longer the object with `instance_id`.
+#[cfg(feature = "checked-refs")]
+#[track_caller]
+unsafe fn check_alive<T: GodotObject>(
+    ptr: NonNull<sys::godot_object>,
+    instance_id: Option<i64>,
+    op: &str,
+) {
+    let site = std::panic::Location::caller();
+    let class_name = T::class_name();
+
+    if !(get_api().godot_is_instance_valid)(ptr.as_ptr()) {
+        panic!(
+            "{op} on a freed object of class {class_name} ({}) at {site}",
+            describe_object(ptr, instance_id),
+        );
+    }
+
+    if let Some(expected) = instance_id {
+        let actual =
+            RawObject::<ManuallyManagedClassPlaceholder>::from_sys_ref_unchecked(ptr).instance_id();
+        if actual != expected {
+            panic!(
+                "{op} on a freed object of class {class_name} (instance ID {expected}), whose \
+                 memory is now used by instance ID {actual}, at {site}",
+            );
+        }
+    }
+}
+
+/// Reports if the object at `ptr`, which must be valid, is a node inside the scene tree and the
+/// current thread isn't the main thread.
+#[cfg(feature = "main-thread-checks")]
+#[track_caller]
+unsafe fn check_thread<T: GodotObject>(ptr: NonNull<sys::godot_object>, op: &str) {
+    if crate::init::is_main_thread() {
+        return;
+    }
+
+    let raw = RawObject::<ManuallyManagedClassPlaceholder>::from_sys_ref_unchecked(ptr);
+    if raw.is_class_by_name("Node") && is_inside_tree(ptr) {
+        crate::init::thread_affinity::report_violation(op, T::class_name(), raw.instance_id());
+    }
+}
+
+#[cfg(feature = "main-thread-checks")]
+unsafe fn is_inside_tree(ptr: NonNull<sys::godot_object>) -> bool {
+    let api = get_api();
+    let method_bind = crate::private::NodeMethodTable::get(api).is_inside_tree;
+
+    let mut argument_buffer = [std::ptr::null() as *const libc::c_void; 0];
+    let mut ret = false;
+    (api.godot_method_bind_ptrcall)(
+        method_bind,
+        ptr.as_ptr(),
+        argument_buffer.as_mut_ptr() as *mut _,
+        &mut ret as *mut bool as *mut _,
+    );
+
+    ret
+}
@@ -1,9 +1,10 @@
+use std::ops::{Deref, DerefMut};
 use std::ptr::NonNull;
 
 use crate::core_types::{
     FromVariant, FromVariantError, GodotString, OwnedToVariant, ToVariant, Variant,
 };
-use crate::export::user_data::{Map, MapMut, MapOwned, UserData};
+use crate::export::user_data::{Map, MapMut, MapOwned, TryBorrow, TryBorrowMut, UserData};
 use crate::export::{class_registry, emplace, NativeClass};
 use crate::object::bounds::{
     AssumeSafeLifetime, LifetimeConstraint, RefImplBound, SafeAsRaw, SafeDeref,
@@ -35,6 +36,45 @@ pub struct TInstance<'a, T: NativeClass, Own: Ownership = Shared> {
     script: T::UserData,
 }
 
+/// RAII guard giving access to the script data of an instance, along with its base object.
+///
+/// Returned by the `borrow` and `borrow_mut` methods on [`Instance`] and [`TInstance`]. The
+/// script data is released when the guard is dropped.
+pub struct InstanceGuard<'a, T: NativeClass, Own: Ownership, G> {
+    guard: G,
+    base: TRef<'a, T::Base, Own>,
+}
+
+impl<'a, T: NativeClass, Own: Ownership, G> InstanceGuard<'a, T, Own, G> {
+    /// Returns a reference to the base object.
+    #[inline]
+    pub fn base(&self) -> TRef<'a, T::Base, Own> {
+        self.base
+    }
+}
+
+impl<'a, T: NativeClass, Own: Ownership, G> Deref for InstanceGuard<'a, T, Own, G>
+where
+    G: Deref<Target = T>,
+{
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<'a, T: NativeClass, Own: Ownership, G> DerefMut for InstanceGuard<'a, T, Own, G>
+where
+    G: DerefMut<Target = T>,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
 impl<T: NativeClass> Instance<T, Unique> {
     /// Creates a `T::Base` with the script `T` attached. Both `T::Base` and `T` must have zero
     /// argument constructors.
@@ -313,6 +353,58 @@ where
         self.script
             .map_owned(|script| op(script, self.owner.as_ref()))
     }
+
+    /// Borrows the NativeClass instance immutably, returning a guard that also carries the
+    /// base object. This is an alternative to `map` that allows early returns and `?`.
+    ///
+    /// Only available for user-data wrappers that implement [`TryBorrow`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the user-data wrapper if the instance can't be borrowed.
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    pub fn borrow<'r>(
+        &'r self,
+    ) -> Result<
+        InstanceGuard<'r, T, Own, <T::UserData as TryBorrow<'r>>::Guard>,
+        <T::UserData as TryBorrow<'r>>::Err,
+    >
+    where
+        T::UserData: TryBorrow<'r>,
+    {
+        let guard = self.script.try_borrow()?;
+        Ok(InstanceGuard {
+            guard,
+            base: self.owner.as_ref(),
+        })
+    }
+
+    /// Borrows the NativeClass instance mutably, returning a guard that also carries the
+    /// base object. This is an alternative to `map_mut` that allows early returns and `?`.
+    ///
+    /// Only available for user-data wrappers that implement [`TryBorrowMut`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the user-data wrapper if the instance can't be borrowed.
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    pub fn borrow_mut<'r>(
+        &'r self,
+    ) -> Result<
+        InstanceGuard<'r, T, Own, <T::UserData as TryBorrowMut<'r>>::Guard>,
+        <T::UserData as TryBorrowMut<'r>>::Err,
+    >
+    where
+        T::UserData: TryBorrowMut<'r>,
+    {
+        let guard = self.script.try_borrow_mut()?;
+        Ok(InstanceGuard {
+            guard,
+            base: self.owner.as_ref(),
+        })
+    }
 }
 
 /// Methods for instances with manually-managed base classes.
@@ -335,6 +427,19 @@ impl<T: NativeClass> Instance<T, Shared> {
             script: self.script.clone(),
         }
     }
+
+    /// Assume that `self` is safe to use, without constraining the lifetime of the result.
+    ///
+    /// # Safety
+    ///
+    /// The same constraints as for `Ref::assume_safe_unchecked` apply.
+    #[inline]
+    pub(crate) unsafe fn assume_safe_unchecked<'a>(&self) -> TInstance<'a, T, Shared> {
+        TInstance {
+            owner: self.owner.assume_safe_unchecked(),
+            script: self.script.clone(),
+        }
+    }
 }
 
 impl<T: NativeClass> Instance<T, Shared>
@@ -536,6 +641,58 @@ where
     {
         self.script.map_owned(|script| op(script, self.owner))
     }
+
+    /// Borrows the NativeClass instance immutably, returning a guard that also carries the
+    /// base object. This is an alternative to `map` that allows early returns and `?`.
+    ///
+    /// Only available for user-data wrappers that implement [`TryBorrow`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the user-data wrapper if the instance can't be borrowed.
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    pub fn borrow<'r>(
+        &'r self,
+    ) -> Result<
+        InstanceGuard<'r, T, Own, <T::UserData as TryBorrow<'r>>::Guard>,
+        <T::UserData as TryBorrow<'r>>::Err,
+    >
+    where
+        T::UserData: TryBorrow<'r>,
+    {
+        let guard = self.script.try_borrow()?;
+        Ok(InstanceGuard {
+            guard,
+            base: self.owner,
+        })
+    }
+
+    /// Borrows the NativeClass instance mutably, returning a guard that also carries the
+    /// base object. This is an alternative to `map_mut` that allows early returns and `?`.
+    ///
+    /// Only available for user-data wrappers that implement [`TryBorrowMut`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the user-data wrapper if the instance can't be borrowed.
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    pub fn borrow_mut<'r>(
+        &'r self,
+    ) -> Result<
+        InstanceGuard<'r, T, Own, <T::UserData as TryBorrowMut<'r>>::Guard>,
+        <T::UserData as TryBorrowMut<'r>>::Err,
+    >
+    where
+        T::UserData: TryBorrowMut<'r>,
+    {
+        let guard = self.script.try_borrow_mut()?;
+        Ok(InstanceGuard {
+            guard,
+            base: self.owner,
+        })
+    }
 }
 
 impl<T, Own: Ownership> Clone for Instance<T, Own>
@@ -600,7 +757,9 @@ where
     }
 }
 
-fn try_get_user_data_ptr<T: NativeClass>(owner: &RawObject<T::Base>) -> Option<*mut libc::c_void> {
+pub(crate) fn try_get_user_data_ptr<T: NativeClass>(
+    owner: &RawObject<T::Base>,
+) -> Option<*mut libc::c_void> {
     unsafe {
         let api = get_api();
 
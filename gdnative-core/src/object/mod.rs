@@ -15,7 +15,7 @@ use std::ops::Deref;
 use std::ptr::NonNull;
 
 use bounds::{
-    AssumeSafeLifetime, LifetimeConstraint, MemorySpec, PtrWrapper, RefImplBound, SafeAsRaw,
+    AssumeSafeLifetime, LifetimeConstraint, MemorySpec, PtrWrapper, RefId, RefImplBound, SafeAsRaw,
     SafeDeref,
 };
 use memory::{ManuallyManaged, Memory, RefCounted};
@@ -26,18 +26,26 @@ use crate::private::{get_api, ManuallyManagedClassPlaceholder, ReferenceCountedC
 use crate::sys;
 
 pub use as_arg::*;
+pub use base::Base;
 pub use instance::*;
 pub use new_ref::NewRef;
+pub use node_ref::NodeRef;
+pub use owned_node::OwnedNode;
 pub use raw::RawObject;
+pub use weak::{Upgraded, UpgradedInstance, WeakInstance, WeakRef};
 
 pub mod bounds;
 pub mod memory;
 pub mod ownership;
 
 mod as_arg;
+mod base;
 mod instance;
 mod new_ref;
+mod node_ref;
+mod owned_node;
 mod raw;
+mod weak;
 
 /// Trait for Godot API objects. This trait is sealed, and implemented for generated wrapper
 /// types.
@@ -298,6 +306,7 @@ pub trait QueueFree: GodotObject {
 /// `impl` blocks  for more detailed explanations of the trait bounds.
 pub struct Ref<T: GodotObject, Own: Ownership = Shared> {
     ptr: <T::Memory as MemorySpec>::PtrWrapper,
+    id: RefId,
     _marker: PhantomData<(*const T, Own)>,
 }
 
@@ -324,7 +333,11 @@ where
 {
     #[inline]
     fn clone(&self) -> Self {
-        unsafe { Ref::from_sys(self.ptr.as_non_null()) }
+        unsafe {
+            let ret = Ref::from_parts(self.ptr.as_non_null(), self.id);
+            <T::Memory as MemorySpec>::maybe_add_ref(ret.as_raw_unchecked());
+            ret
+        }
     }
 }
 
@@ -373,6 +386,10 @@ where
     /// - `T` is reference-counted and `Ownership` is not `Shared`,
     /// - or, `T` is manually-managed and `Ownership` is `Unique`.
     #[inline]
+    #[cfg_attr(
+        any(feature = "checked-refs", feature = "main-thread-checks"),
+        track_caller
+    )]
     pub fn as_ref(&self) -> TRef<'_, T, Own> {
         RefImplBound::impl_as_ref(self)
     }
@@ -389,6 +406,10 @@ where
     type Target = T;
 
     #[inline]
+    #[cfg_attr(
+        any(feature = "checked-refs", feature = "main-thread-checks"),
+        track_caller
+    )]
     fn deref(&self) -> &Self::Target {
         RefImplBound::impl_as_ref(self).obj
     }
@@ -403,6 +424,10 @@ where
     RefImplBound: SafeDeref<T::Memory, Own>,
 {
     #[inline]
+    #[cfg_attr(
+        any(feature = "checked-refs", feature = "main-thread-checks"),
+        track_caller
+    )]
     fn borrow(&self) -> &T {
         RefImplBound::impl_as_ref(self).obj
     }
@@ -481,7 +506,7 @@ where
     where
         U: GodotObject<Memory = T::Memory>,
     {
-        let ret = Ref::move_from_sys(self.ptr.as_non_null());
+        let ret = Ref::from_parts(self.ptr.as_non_null(), self.id);
         std::mem::forget(self);
         ret
     }
@@ -520,6 +545,12 @@ impl<T: GodotObject> Ref<T, Shared> {
     /// This is guaranteed to be a no-op at runtime if `debug_assertions` is disabled. Runtime
     /// sanity checks may be added in debug builds to help catch bugs.
     ///
+    /// With the `checked-refs` feature, this panics with the caller location if the object
+    /// has been freed, even if its memory has been reused by another object. With the
+    /// `main-thread-checks` feature, which `checked-refs` implies, calling this outside the main
+    /// thread on a node inside the scene tree is reported according to the
+    /// [`MainThreadCheck`](crate::init::MainThreadCheck) policy.
+    ///
     /// # Safety
     ///
     /// Suppose that the lifetime of the returned reference is `'a`. It's safe to call
@@ -548,6 +579,10 @@ impl<T: GodotObject> Ref<T, Shared> {
     ///
     /// [thread-safety]: https://docs.godotengine.org/en/stable/tutorials/threads/thread_safe_apis.html
     #[inline(always)]
+    #[cfg_attr(
+        any(feature = "checked-refs", feature = "main-thread-checks"),
+        track_caller
+    )]
     pub unsafe fn assume_safe<'a, 'r>(&'r self) -> TRef<'a, T, Shared>
     where
         AssumeSafeLifetime<'a, 'r>: LifetimeConstraint<T::Memory>,
@@ -566,6 +601,10 @@ impl<T: GodotObject> Ref<T, Shared> {
     /// behavior. This is a much stronger assumption than `assume_safe` and should be used with
     /// care.
     #[inline(always)]
+    #[cfg_attr(
+        any(feature = "checked-refs", feature = "main-thread-checks"),
+        track_caller
+    )]
     pub unsafe fn assume_unique(self) -> Ref<T, Unique> {
         T::Memory::impl_assume_unique(self)
     }
@@ -583,7 +622,9 @@ impl<T: GodotObject<Memory = ManuallyManaged>> Ref<T, Shared> {
     #[allow(clippy::trivially_copy_pass_by_ref)]
     pub unsafe fn is_instance_sane(&self) -> bool {
         let api = get_api();
-        if !(api.godot_is_instance_valid)(self.as_ptr()) {
+        if !(api.godot_is_instance_valid)(self.as_ptr())
+            || !self.id.is_current(self.ptr.as_non_null())
+        {
             return false;
         }
 
@@ -598,6 +639,10 @@ impl<T: GodotObject<Memory = ManuallyManaged>> Ref<T, Shared> {
     /// guarantee that the operation is safe.**
     #[inline]
     #[allow(clippy::trivially_copy_pass_by_ref)]
+    #[cfg_attr(
+        any(feature = "checked-refs", feature = "main-thread-checks"),
+        track_caller
+    )]
     pub unsafe fn assume_safe_if_sane<'a>(&self) -> Option<TRef<'a, T, Shared>> {
         if self.is_instance_sane() {
             Some(self.assume_safe_unchecked())
@@ -781,8 +826,16 @@ impl<T: GodotObject, Own: Ownership> Ref<T, Own> {
     #[doc(hidden)]
     #[inline]
     pub unsafe fn move_from_sys(obj: NonNull<sys::godot_object>) -> Self {
+        Self::from_parts(obj, RefId::of(obj))
+    }
+
+    /// Creates a `Ref` from a pointer and an already recorded instance ID, without touching the
+    /// reference count.
+    #[inline(always)]
+    unsafe fn from_parts(obj: NonNull<sys::godot_object>, id: RefId) -> Self {
         Ref {
             ptr: <T::Memory as MemorySpec>::PtrWrapper::new(obj),
+            id,
             _marker: PhantomData,
         }
     }
@@ -820,7 +873,7 @@ impl<T: GodotObject, Own: Ownership> Ref<T, Own> {
     ///
     /// The cast must be valid.
     unsafe fn cast_access<TargetOws: Ownership>(self) -> Ref<T, TargetOws> {
-        let ret = Ref::move_from_sys(self.ptr.as_non_null());
+        let ret = Ref::from_parts(self.ptr.as_non_null(), self.id);
         std::mem::forget(self);
         ret
     }
@@ -828,14 +881,20 @@ impl<T: GodotObject, Own: Ownership> Ref<T, Own> {
     /// Assume that the reference is safe in an `unsafe` context even if it can be used safely.
     /// For internal use in macros.
     ///
-    /// This is guaranteed to be a no-op at runtime.
+    /// This is guaranteed to be a no-op at runtime, unless the `checked-refs` or
+    /// `main-thread-checks` feature is enabled.
     ///
     /// # Safety
     ///
     /// The same safety constraints as `assume_safe` applies.
     #[doc(hidden)]
     #[inline(always)]
+    #[cfg_attr(
+        any(feature = "checked-refs", feature = "main-thread-checks"),
+        track_caller
+    )]
     pub unsafe fn assume_safe_unchecked<'a>(&self) -> TRef<'a, T, Own> {
+        self.id.check::<T>(self.ptr.as_non_null(), "TRef creation");
         TRef::new(T::cast_ref(self.as_raw_unchecked()))
     }
 }
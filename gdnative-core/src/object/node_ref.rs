@@ -0,0 +1,118 @@
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use crate::object::ownership::Shared;
+use crate::object::{GodotObject, TRef};
+
+/// Typed reference to a node, meant to be used as a `#[node("Path")]` field of a `NativeClass`.
+///
+/// Fields with the `#[node]` attribute are resolved by the `NativeClass` derive macro right
+/// before `_ready` is called, relative to the base object of the script. Failures to resolve
+/// are logged with the missing path and the expected class, leaving the field empty:
+///
+/// ```no_run
+/// use gdnative::prelude::*;
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node)]
+/// #[no_constructor]
+/// struct Hud {
+///     #[node("Score/Label")]
+///     score: NodeRef<Label>,
+/// }
+///
+/// #[methods]
+/// impl Hud {
+///     #[method]
+///     fn _ready(&self) {
+///         self.score.get().set_text("0");
+///     }
+/// }
+/// ```
+///
+/// The reference is stored as an instance ID, so access is checked and never dangles, like
+/// [`WeakRef`](super::WeakRef). It can also be assigned manually with [`set`](Self::set).
+pub struct NodeRef<T: GodotObject> {
+    instance_id: AtomicI64,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: GodotObject> NodeRef<T> {
+    /// Creates an empty `NodeRef`.
+    #[inline]
+    pub fn new() -> Self {
+        NodeRef {
+            instance_id: AtomicI64::new(0),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the referenced node.
+    #[inline]
+    pub fn set(&self, node: TRef<'_, T, Shared>) {
+        self.instance_id
+            .store(node.as_raw().instance_id(), Ordering::Release);
+    }
+
+    /// Clears the reference.
+    #[inline]
+    pub fn clear(&self) {
+        self.instance_id.store(0, Ordering::Release);
+    }
+
+    /// Returns a reference to the node, or `None` if it was never set or has been freed.
+    ///
+    /// The node is looked up by its instance ID, so this never returns a dangling reference.
+    /// The returned reference is bound to the borrow of the field, and so to the script
+    /// instance, like the `owner` reference of exported methods.
+    #[inline]
+    pub fn try_get(&self) -> Option<TRef<'_, T, Shared>> {
+        match self.instance_id.load(Ordering::Acquire) {
+            0 => None,
+            // SAFETY: The node was checked to be alive, and the reference can't outlive `self`.
+            instance_id => unsafe { TRef::try_from_instance_id(instance_id) },
+        }
+    }
+
+    /// Returns `true` if the reference is set and the node is still alive.
+    #[inline]
+    pub fn is_resolved(&self) -> bool {
+        self.try_get().is_some()
+    }
+
+    /// Returns a reference to the node. See [`try_get`](Self::try_get).
+    ///
+    /// # Panics
+    ///
+    /// If the reference was never set, for example because the node could not be resolved,
+    /// or if the node has been freed.
+    #[inline]
+    pub fn get(&self) -> TRef<'_, T, Shared> {
+        self.try_get().unwrap_or_else(|| {
+            panic!(
+                "NodeRef<{}> is not set or the node has been freed (see errors logged before `_ready`)",
+                T::class_name(),
+            )
+        })
+    }
+}
+
+impl<T: GodotObject> Default for NodeRef<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: GodotObject> Debug for NodeRef<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "NodeRef<{}>({})",
+            T::class_name(),
+            self.instance_id.load(Ordering::Relaxed)
+        )
+    }
+}
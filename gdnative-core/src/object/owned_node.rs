@@ -0,0 +1,97 @@
+use std::fmt::{self, Debug};
+use std::ops::Deref;
+
+use crate::object::memory::ManuallyManaged;
+use crate::object::ownership::{Shared, Unique};
+use crate::object::{GodotObject, Ref, TRef};
+
+/// A unique reference to a manually-managed object, usually a `Node` that is not yet part of the
+/// scene tree, that frees the object when dropped.
+///
+/// Manually-managed objects created with `new()` are leaked if their `Ref` is dropped before
+/// being handed over to the engine, for example on an early return or an error propagated with
+/// `?`. Wrapping them in an `OwnedNode` frees them instead, until ownership is handed over to the
+/// scene tree with `NodeOwnedChildExt::add_owned_child` from the bindings, or taken back explicitly with
+/// [`into_inner`](Self::into_inner) or [`into_shared`](Self::into_shared).
+///
+/// ```no_run
+/// use gdnative::prelude::*;
+///
+/// fn spawn_label(parent: TRef<Node>, text: Option<&str>) -> Option<()> {
+///     let label = OwnedNode::new(Label::new());
+///     label.set_text(text?); // `label` is freed if `text` is `None`
+///     parent.add_owned_child(label);
+///     Some(())
+/// }
+/// ```
+pub struct OwnedNode<T: GodotObject<Memory = ManuallyManaged>> {
+    node: Option<Ref<T, Unique>>,
+}
+
+impl<T: GodotObject<Memory = ManuallyManaged>> OwnedNode<T> {
+    /// Takes ownership of `node`, freeing it when dropped.
+    #[inline]
+    pub fn new(node: Ref<T, Unique>) -> Self {
+        OwnedNode { node: Some(node) }
+    }
+
+    /// Returns a safe reference to the object.
+    #[inline]
+    pub fn as_ref(&self) -> TRef<'_, T, Unique> {
+        self.inner().as_ref()
+    }
+
+    /// Releases ownership of the object without freeing it.
+    #[inline]
+    pub fn into_inner(mut self) -> Ref<T, Unique> {
+        self.node
+            .take()
+            .expect("OwnedNode should hold an object until consumed")
+    }
+
+    /// Releases ownership of the object without freeing it, converting it into a shared
+    /// reference.
+    #[inline]
+    pub fn into_shared(self) -> Ref<T, Shared> {
+        self.into_inner().into_shared()
+    }
+
+    #[inline]
+    fn inner(&self) -> &Ref<T, Unique> {
+        self.node
+            .as_ref()
+            .expect("OwnedNode should hold an object until consumed")
+    }
+}
+
+impl<T: GodotObject<Memory = ManuallyManaged>> From<Ref<T, Unique>> for OwnedNode<T> {
+    #[inline]
+    fn from(node: Ref<T, Unique>) -> Self {
+        OwnedNode::new(node)
+    }
+}
+
+impl<T: GodotObject<Memory = ManuallyManaged>> Deref for OwnedNode<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.inner()
+    }
+}
+
+impl<T: GodotObject<Memory = ManuallyManaged>> Debug for OwnedNode<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OwnedNode").field(self.inner()).finish()
+    }
+}
+
+impl<T: GodotObject<Memory = ManuallyManaged>> Drop for OwnedNode<T> {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(node) = self.node.take() {
+            node.free();
+        }
+    }
+}
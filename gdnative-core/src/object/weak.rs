@@ -0,0 +1,312 @@
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use crate::export::NativeClass;
+use crate::object::ownership::{Ownership, Shared};
+use crate::object::{GodotObject, Instance, Ref, TInstance, TRef};
+
+/// A weak reference to an engine object of type `T`, stored as its instance ID.
+///
+/// Unlike `Ref`, a `WeakRef` never dangles, and doesn't keep reference-counted objects alive.
+/// It can be freely stored and sent across threads, and has to be upgraded before the object
+/// can be used. Upgrading checks that the object is still alive and of the expected class, and
+/// returns an [`Upgraded`] reference bound to the current thread.
+///
+/// Instance IDs are never reused during the lifetime of the engine, so a `WeakRef` to a freed
+/// object will never upgrade to another object created later.
+///
+/// This works for both manually-managed and reference-counted objects. For the latter, the
+/// Godot class `WeakRef` may be used as well.
+///
+/// ```no_run
+/// use gdnative::prelude::*;
+/// use gdnative::object::WeakRef;
+///
+/// struct Ai {
+///     target: Option<WeakRef<Spatial>>,
+/// }
+///
+/// impl Ai {
+///     fn process(&mut self) {
+///         match self.target.as_ref().and_then(|t| t.upgrade()) {
+///             Some(target) => godot_print!("chasing {:?}", target.translation()),
+///             None => self.target = None,
+///         }
+///     }
+/// }
+/// ```
+pub struct WeakRef<T: GodotObject> {
+    instance_id: i64,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: GodotObject> WeakRef<T> {
+    /// Creates a weak reference to `obj`.
+    #[inline]
+    pub fn new(obj: &T) -> Self {
+        Self::from_instance_id(obj.as_raw().instance_id())
+    }
+
+    /// Creates a weak reference from an instance ID previously returned by
+    /// `Object::get_instance_id`. The ID is only checked when the reference is upgraded.
+    #[inline]
+    pub fn from_instance_id(instance_id: i64) -> Self {
+        WeakRef {
+            instance_id,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the instance ID of the referenced object.
+    #[inline]
+    pub fn instance_id(&self) -> i64 {
+        self.instance_id
+    }
+
+    /// Returns `true` if the object is still alive and of the class `T`.
+    ///
+    /// Note that the object may be freed from another thread right after this returns.
+    #[inline]
+    pub fn is_alive(&self) -> bool {
+        self.upgrade().is_some()
+    }
+
+    /// Returns a reference to the object if it is still alive and of the class `T`, or `None`
+    /// otherwise.
+    ///
+    /// The returned reference can't be sent to other threads. For reference-counted objects,
+    /// it keeps the object alive until dropped.
+    #[inline]
+    pub fn upgrade(&self) -> Option<Upgraded<T>> {
+        // SAFETY: The temporary reference is only used to increment the reference count.
+        let obj = unsafe { TRef::<T, Shared>::try_from_instance_id(self.instance_id)? };
+        Some(Upgraded {
+            obj: obj.claim(),
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<T: GodotObject> Clone for WeakRef<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::from_instance_id(self.instance_id)
+    }
+}
+
+impl<T: GodotObject> Copy for WeakRef<T> {}
+
+impl<T: GodotObject> PartialEq for WeakRef<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.instance_id == other.instance_id
+    }
+}
+
+impl<T: GodotObject> Eq for WeakRef<T> {}
+
+impl<T: GodotObject> Hash for WeakRef<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.instance_id.hash(state)
+    }
+}
+
+impl<T: GodotObject> Debug for WeakRef<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WeakRef<{}>({})", T::class_name(), self.instance_id)
+    }
+}
+
+/// A weak reference to an engine object with the NativeScript `C` attached, stored as its
+/// instance ID.
+///
+/// Upgrading checks that the object is still alive, of the base class of `C`, and that the
+/// script attached to it is still `C`. See [`WeakRef`] for more information.
+pub struct WeakInstance<C: NativeClass> {
+    base: WeakRef<C::Base>,
+}
+
+impl<C: NativeClass> WeakInstance<C> {
+    /// Creates a weak reference to `instance`.
+    #[inline]
+    pub fn new<Own: Ownership>(instance: &TInstance<'_, C, Own>) -> Self {
+        WeakInstance {
+            base: WeakRef::new(instance.base().as_ref()),
+        }
+    }
+
+    /// Creates a weak reference from an instance ID previously returned by
+    /// `Object::get_instance_id`. The ID is only checked when the reference is upgraded.
+    #[inline]
+    pub fn from_instance_id(instance_id: i64) -> Self {
+        WeakInstance {
+            base: WeakRef::from_instance_id(instance_id),
+        }
+    }
+
+    /// Returns the instance ID of the referenced object.
+    #[inline]
+    pub fn instance_id(&self) -> i64 {
+        self.base.instance_id()
+    }
+
+    /// Returns `true` if the object is still alive and has `C` attached.
+    ///
+    /// Note that the object may be freed from another thread right after this returns.
+    #[inline]
+    pub fn is_alive(&self) -> bool {
+        self.upgrade().is_some()
+    }
+
+    /// Returns a reference to the instance if the object is still alive and has `C` attached,
+    /// or `None` otherwise. See [`WeakRef::upgrade`].
+    #[inline]
+    pub fn upgrade(&self) -> Option<UpgradedInstance<C>> {
+        let instance = Instance::try_from_base(self.base.upgrade()?.claim()).ok()?;
+        Some(UpgradedInstance {
+            instance,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns a weak reference to the base object.
+    #[inline]
+    pub fn base(&self) -> WeakRef<C::Base> {
+        self.base
+    }
+}
+
+impl<C: NativeClass> Clone for WeakInstance<C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        WeakInstance { base: self.base }
+    }
+}
+
+impl<C: NativeClass> Copy for WeakInstance<C> {}
+
+impl<C: NativeClass> PartialEq for WeakInstance<C> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.base == other.base
+    }
+}
+
+impl<C: NativeClass> Eq for WeakInstance<C> {}
+
+impl<C: NativeClass> Hash for WeakInstance<C> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.base.hash(state)
+    }
+}
+
+impl<C: NativeClass> Debug for WeakInstance<C> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "WeakInstance<{}>({})",
+            std::any::type_name::<C>(),
+            self.base.instance_id
+        )
+    }
+}
+
+/// Reference to an object returned by [`WeakRef::upgrade`], bound to the current thread.
+///
+/// The object can be used through `Deref` or [`get`](Self::get). For reference-counted
+/// objects, the reference keeps the object alive until dropped.
+pub struct Upgraded<T: GodotObject> {
+    obj: Ref<T, Shared>,
+    /// Remove Send and Sync
+    _marker: PhantomData<*const ()>,
+}
+
+impl<T: GodotObject> Upgraded<T> {
+    /// Returns a `TRef` to the object, valid as long as this reference.
+    #[inline]
+    pub fn get(&self) -> TRef<'_, T, Shared> {
+        // SAFETY: The object was alive when upgraded, and this can't leave the current thread.
+        unsafe { self.obj.assume_safe_unchecked() }
+    }
+
+    /// Persists this reference into a `Ref`, which may outlive the object if it is manually
+    /// managed.
+    #[inline]
+    pub fn claim(self) -> Ref<T, Shared> {
+        self.obj
+    }
+}
+
+impl<T: GodotObject> Deref for Upgraded<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.get().as_ref()
+    }
+}
+
+impl<T: GodotObject> Debug for Upgraded<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Upgraded").field(&self.obj).finish()
+    }
+}
+
+/// Reference to a script instance returned by [`WeakInstance::upgrade`], bound to the current
+/// thread.
+///
+/// The instance can be used through [`get`](Self::get). For reference-counted objects, the
+/// reference keeps the object alive until dropped.
+pub struct UpgradedInstance<C: NativeClass> {
+    instance: Instance<C, Shared>,
+    /// Remove Send and Sync
+    _marker: PhantomData<*const ()>,
+}
+
+impl<C: NativeClass> UpgradedInstance<C> {
+    /// Returns a `TInstance` to the instance, valid as long as this reference.
+    #[inline]
+    pub fn get(&self) -> TInstance<'_, C, Shared> {
+        // SAFETY: The object was alive when upgraded, and this can't leave the current thread.
+        unsafe { self.instance.assume_safe_unchecked() }
+    }
+
+    /// Persists this reference into an `Instance`, which may outlive the object if it is
+    /// manually managed.
+    #[inline]
+    pub fn claim(self) -> Instance<C, Shared> {
+        self.instance
+    }
+}
+
+impl<C: NativeClass> Debug for UpgradedInstance<C> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("UpgradedInstance")
+            .field(self.instance.base())
+            .finish()
+    }
+}
+
+impl<'a, T: GodotObject, Own: Ownership> TRef<'a, T, Own> {
+    /// Creates a weak reference to this object. See [`WeakRef`].
+    #[inline]
+    pub fn downgrade(self) -> WeakRef<T> {
+        WeakRef::new(self.as_ref())
+    }
+}
+
+impl<'a, C: NativeClass, Own: Ownership> TInstance<'a, C, Own> {
+    /// Creates a weak reference to this instance. See [`WeakInstance`].
+    #[inline]
+    pub fn downgrade(&self) -> WeakInstance<C> {
+        WeakInstance::new(self)
+    }
+}
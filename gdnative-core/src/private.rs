@@ -144,6 +144,14 @@ pub fn get_gdnative_library_sys() -> *mut sys::godot_object {
     unsafe { GDNATIVE_LIBRARY_SYS.expect("GDNativeLibrary not bound") }
 }
 
+/// Records the current thread as the main thread. Called from `godot_nativescript_init`.
+///
+/// This is intended to be an internal interface.
+#[inline]
+pub fn record_main_thread() {
+    crate::init::thread_affinity::record_main_thread();
+}
+
 /// Performs library-wide cleanup during `terminate`.
 ///
 /// # Safety
@@ -153,6 +161,9 @@ pub fn get_gdnative_library_sys() -> *mut sys::godot_object {
 pub unsafe fn cleanup_internal_state() {
     crate::export::type_tag::cleanup();
     crate::export::class_registry::cleanup();
+    crate::init::panic_policy::cleanup();
+    crate::export::lock_diagnostics::cleanup();
+    crate::init::thread_affinity::cleanup();
 
     GODOT_API = None;
 }
@@ -194,8 +205,7 @@ pub fn report_panics(context: &str, callback: impl FnOnce() + UnwindSafe) {
     let __result = catch_unwind(callback);
 
     if let Err(e) = __result {
-        godot_error!("gdnative-core: {} callback panicked", context);
-        print_panic_error(e);
+        crate::init::panic_policy::report_panic(&format!("{context} callback"), None, None, e);
     }
 }
 
@@ -245,6 +255,72 @@ pub mod mixin {
     }
 }
 
+/// Autoref-based dispatch for `#[variant(coerce)]` in the `FromVariant` derive macro. Types that
+/// implement `CoerceFromVariant` use `try_coerce_from_variant`, and all other types fall back to
+/// `FromVariant`. Usage: `(&&Coerce::<T>::new()).lenient_from_variant(variant)`.
+pub mod coerce {
+    use std::marker::PhantomData;
+
+    use crate::core_types::{CoerceFromVariant, FromVariant, FromVariantError, Variant};
+
+    pub struct Coerce<T>(PhantomData<T>);
+
+    #[allow(clippy::new_without_default)]
+    impl<T> Coerce<T> {
+        #[inline]
+        pub fn new() -> Self {
+            Coerce(PhantomData)
+        }
+    }
+
+    pub trait ViaCoerce<T> {
+        fn lenient_from_variant(&self, variant: &Variant) -> Result<T, FromVariantError>;
+    }
+
+    impl<T: CoerceFromVariant + FromVariant> ViaCoerce<T> for &Coerce<T> {
+        #[inline]
+        fn lenient_from_variant(&self, variant: &Variant) -> Result<T, FromVariantError> {
+            T::try_coerce_from_variant(variant)
+        }
+    }
+
+    pub trait ViaFromVariant<T> {
+        fn lenient_from_variant(&self, variant: &Variant) -> Result<T, FromVariantError>;
+    }
+
+    impl<T: FromVariant> ViaFromVariant<T> for Coerce<T> {
+        #[inline]
+        fn lenient_from_variant(&self, variant: &Variant) -> Result<T, FromVariantError> {
+            T::from_variant(variant)
+        }
+    }
+}
+
+/// Helpers for `#[variant(flatten)]` in the `FromVariant` derive macro.
+pub mod flatten {
+    use crate::core_types::{FromVariantError, VariantType};
+
+    /// Returns `true` if `err` was caused by a key missing from the dictionary, possibly in a
+    /// nested flattened field. Missing keys are read as `Nil`. Flattened `Option`s are converted
+    /// to `None` on such errors.
+    #[inline]
+    pub fn is_missing_field(err: &FromVariantError) -> bool {
+        match err {
+            FromVariantError::InvalidField { error, .. } => {
+                matches!(
+                    **error,
+                    FromVariantError::InvalidNil
+                        | FromVariantError::InvalidVariantType {
+                            variant_type: VariantType::Nil,
+                            ..
+                        }
+                ) || is_missing_field(error)
+            }
+            _ => false,
+        }
+    }
+}
+
 pub(crate) struct ManuallyManagedClassPlaceholder;
 
 unsafe impl crate::object::GodotObject for ManuallyManagedClassPlaceholder {
@@ -312,6 +388,13 @@ macro_rules! make_method_table {
 make_method_table!(struct ObjectMethodTable for Object {
     get_class,
     is_class,
+    get_instance_id,
+    call,
+});
+
+make_method_table!(struct ScriptMethodTable for Script {
+    set_source_code,
+    reload,
 });
 
 make_method_table!(struct ReferenceMethodTable for Reference {
@@ -320,6 +403,11 @@ make_method_table!(struct ReferenceMethodTable for Reference {
     init_ref,
 });
 
+#[cfg(feature = "main-thread-checks")]
+make_method_table!(struct NodeMethodTable for Node {
+    is_inside_tree,
+});
+
 // Add this one here too. It's not easy to use this macro from the
 // export module without making this macro public.
 make_method_table!(struct NativeScriptMethodTable for NativeScript {
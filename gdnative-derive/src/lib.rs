@@ -193,6 +193,10 @@ pub fn profiled(meta: TokenStream, input: TokenStream) -> TokenStream {
 /// Use the given type as the user-data wrapper. See the module-level docs on
 /// `gdnative::user_data` for more information.
 ///
+/// `#[user_data(reentrant)]` is a shorthand for
+/// `#[user_data(gdnative::export::user_data::ReentrantCellData<Self>)]`, which queues
+/// re-entrant method calls until the outer borrow ends, instead of failing them.
+///
 /// ### `#[register_with(path::to::function)]`
 ///
 /// Use a custom function to register signals, properties or methods, in addition
@@ -274,6 +278,70 @@ pub fn profiled(meta: TokenStream, input: TokenStream) -> TokenStream {
 ///   Sets the [Multiplayer API RPC Mode](https://docs.godotengine.org/en/stable/classes/class_multiplayerapi.html?highlight=RPC#enumerations) for the property.
 ///   See the `#[method]` documentation below for possible values and their semantics.
 ///
+/// ### `#[node("path/to/node")]`
+///
+/// Resolves a field of type [`NodeRef<T>`][gdnative::object::NodeRef] to the node at the given
+/// path, relative to the base object, right before `_ready` is called. If the node is missing or
+/// isn't a `T`, an error naming the field, the path and the expected class is logged, and the
+/// field is left empty.
+///
+/// ```
+/// use gdnative::prelude::*;
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node)]
+/// #[no_constructor]
+/// struct Hud {
+///     #[node("Score/Label")]
+///     score: NodeRef<Label>,
+/// }
+///
+/// #[methods]
+/// impl Hud {
+///     #[method]
+///     fn _ready(&self) {
+///         self.score.get().set_text("0");
+///     }
+/// }
+/// ```
+///
+/// The class must inherit `Node`. A `_ready` method is registered even if the class doesn't
+/// export one. The fields are resolved before any `_ready` method, including one registered
+/// manually with `#[register_with]`.
+///
+/// ### `#[base]`
+///
+/// Marks a field of type [`Base<T>`][gdnative::object::Base], where `T` is the base class of the
+/// script, to be attached to the base object right after construction. The derive macro then
+/// generates a `base()` method, so the base object is available in all methods without a
+/// `#[base]` parameter:
+///
+/// ```
+/// use gdnative::prelude::*;
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node)]
+/// struct Player {
+///     #[base]
+///     base: Base<Node>,
+/// }
+///
+/// #[methods]
+/// impl Player {
+///     fn new(_base: &Node) -> Self {
+///         Player { base: Base::new() }
+///     }
+///
+///     #[method]
+///     fn _ready(&self) {
+///         self.base().set_name("Player");
+///     }
+/// }
+/// ```
+///
+/// The field is not attached yet in the constructor. At most one such field is allowed. Fields
+/// of type `Base<T>` without the attribute are left alone.
+///
 /// ### `#[methods]`
 /// Adds the necessary information to a an `impl` block to register the properties and methods with Godot.
 ///
@@ -496,7 +564,15 @@ pub fn profiled(meta: TokenStream, input: TokenStream) -> TokenStream {
 /// <br><br>
 #[proc_macro_derive(
     NativeClass,
-    attributes(inherit, register_with, no_constructor, user_data, property)
+    attributes(
+        inherit,
+        register_with,
+        no_constructor,
+        user_data,
+        property,
+        node,
+        base
+    )
 )]
 pub fn derive_native_class(input: TokenStream) -> TokenStream {
     // Converting the proc_macro::TokenStream into non proc_macro types so that tests
@@ -3,8 +3,8 @@ use proc_macro2::TokenStream as TokenStream2;
 use syn::spanned::Spanned;
 use syn::visit::Visit;
 use syn::{
-    AttributeArgs, Data, DeriveInput, Expr, Fields, Ident, ItemType, Meta, MetaList, NestedMeta,
-    Path, Stmt, Type,
+    AttributeArgs, Data, DeriveInput, Expr, Fields, Ident, ItemType, LitStr, Meta, MetaList,
+    NestedMeta, Path, Stmt, Type,
 };
 
 mod property_args;
@@ -19,6 +19,8 @@ pub(crate) struct DeriveData {
     pub(crate) register_callback: Option<Path>,
     pub(crate) user_data: Type,
     pub(crate) properties: Vec<(Ident, PropertyAttrArgs)>,
+    pub(crate) nodes: Vec<(Ident, LitStr)>,
+    pub(crate) base_field: Option<Ident>,
     pub(crate) no_constructor: bool,
 }
 
@@ -204,6 +206,71 @@ pub(crate) fn derive_native_class(derive_input: &DeriveInput) -> Result<TokenStr
             }
         });
 
+        let pre_ready = if data.nodes.is_empty() {
+            None
+        } else {
+            let gdnative_bindings = crate::crate_gdnative_bindings();
+            let class_name = name.to_string();
+            let resolve = data.nodes.iter().map(|(ident, path)| {
+                let field_name = ident.to_string();
+                quote! {
+                    #gdnative_bindings::utils::resolve_node_ref(
+                        &*__base, #path, &__this.#ident, #class_name, #field_name,
+                    );
+                }
+            });
+
+            Some(quote! {
+                fn nativeclass_pre_ready(this: #gdnative_core::object::TInstance<'_, Self, #gdnative_core::object::ownership::Shared>) {
+                    if let Err(err) = this.map(|__this, __base| {
+                        #(#resolve)*
+                    }) {
+                        #gdnative_core::godot_error!(
+                            "{}: failed to resolve #[node] fields: {:?}", #class_name, err,
+                        );
+                    }
+                }
+            })
+        };
+
+        // A placeholder `_ready` so that `#[node]` fields are resolved even if the class doesn't
+        // export one. A `_ready` method registered later, by `#[methods]` or `#[register_with]`,
+        // replaces it, and resolves the fields as well.
+        let register_ready = (!data.nodes.is_empty()).then(|| {
+            quote! {
+                builder
+                    .method("_ready", #gdnative_core::export::EmptyReady)
+                    .done_stateless();
+            }
+        });
+
+        let (attach_base, base_accessor) = match &data.base_field {
+            Some(ident) => (
+                Some(quote! {
+                    fn nativeclass_attach_base(
+                        &mut self,
+                        base: #gdnative_core::object::TRef<'_, Self::Base, #gdnative_core::object::ownership::Shared>,
+                    ) {
+                        self.#ident.attach(base);
+                    }
+                }),
+                Some(quote! {
+                    #derived
+                    impl #impl_generics #name #ty_generics #where_clause {
+                        /// Returns a reference to the base object. See [`Base::get`].
+                        #[inline]
+                        #[allow(dead_code)]
+                        pub(crate) fn base(
+                            &self,
+                        ) -> #gdnative_core::object::TRef<'_, <Self as #gdnative_core::export::NativeClass>::Base, #gdnative_core::object::ownership::Shared> {
+                            self.#ident.get()
+                        }
+                    }
+                }),
+            ),
+            None => (None, None),
+        };
+
         let init = if data.no_constructor {
             None
         } else {
@@ -222,12 +289,19 @@ pub(crate) fn derive_native_class(derive_input: &DeriveInput) -> Result<TokenStr
 
                 #init
 
+                #attach_base
+
+                #pre_ready
+
                 fn nativeclass_register_properties(builder: &#gdnative_core::export::ClassBuilder<Self>) {
+                    #register_ready
                     #(#properties)*;
                     #register_callback
                 }
             }
 
+            #base_accessor
+
             #maybe_statically_named
         )
     };
@@ -269,7 +343,18 @@ fn parse_derive_input(input: &DeriveInput) -> Result<DeriveData, syn::Error> {
         .attrs
         .iter()
         .find(|a| a.path.is_ident("user_data"))
-        .map(|attr| attr.parse_args::<Type>())
+        .map(|attr| {
+            let ty = attr.parse_args::<Type>()?;
+
+            // `reentrant` is a shorthand for the re-entrancy-tolerant wrapper.
+            if matches!(&ty, Type::Path(p) if p.qself.is_none() && p.path.is_ident("reentrant")) {
+                return syn::parse2::<Type>(
+                    quote! { #gdnative_core::export::user_data::ReentrantCellData<Self> },
+                );
+            }
+
+            Ok(ty)
+        })
         .unwrap_or_else(|| {
             Ok(syn::parse2::<Type>(
                 quote! { #gdnative_core::export::user_data::DefaultUserData<Self> },
@@ -292,8 +377,10 @@ fn parse_derive_input(input: &DeriveInput) -> Result<DeriveData, syn::Error> {
         ));
     };
 
-    // Find all fields with a `#[property]` attribute
+    // Find all fields with a `#[property]`, `#[node]` or `#[base]` attribute
     let mut properties = Vec::new();
+    let mut nodes = Vec::new();
+    let mut base_field = None;
 
     if let Fields::Named(names) = &struct_data.fields {
         for field in &names.named {
@@ -333,6 +420,35 @@ fn parse_derive_input(input: &DeriveInput) -> Result<DeriveData, syn::Error> {
                 }
             }
 
+            if let Some(attr) = field.attrs.iter().find(|a| a.path.is_ident("base")) {
+                if !attr.tokens.is_empty() {
+                    return Err(syn::Error::new(
+                        attr.tokens.span(),
+                        "#[base] expects no arguments",
+                    ));
+                }
+
+                if base_field.is_some() {
+                    return Err(syn::Error::new(
+                        attr.span(),
+                        "A NativeClass can have at most one `#[base]` field",
+                    ));
+                }
+
+                base_field = Some(field.ident.clone().ok_or_else(|| {
+                    syn::Error::new(field.ident.span(), "Fields should be named")
+                })?);
+            }
+
+            for attr in field.attrs.iter().filter(|a| a.path.is_ident("node")) {
+                let path = attr.parse_args::<LitStr>()?;
+                let ident = field
+                    .ident
+                    .clone()
+                    .ok_or_else(|| syn::Error::new(field.ident.span(), "Fields should be named"))?;
+                nodes.push((ident, path));
+            }
+
             if let Some(builder) = property_args {
                 let ident = field
                     .ident
@@ -350,6 +466,8 @@ fn parse_derive_input(input: &DeriveInput) -> Result<DeriveData, syn::Error> {
         register_callback,
         user_data,
         properties,
+        nodes,
+        base_field,
         no_constructor,
     })
 }
@@ -437,6 +555,111 @@ mod tests {
         parse_derive_input(&input).unwrap();
     }
 
+    #[test]
+    fn derive_node() {
+        let input = parse_quote! {
+            #[inherit(Node)]
+            struct Foo {
+                #[node("HUD/ScoreLabel")]
+                score: NodeRef<Label>,
+            }
+        };
+        let data = parse_derive_input(&input).unwrap();
+        assert_eq!(1, data.nodes.len());
+        assert_eq!("score", data.nodes[0].0.to_string());
+        assert_eq!("HUD/ScoreLabel", data.nodes[0].1.value());
+
+        let output = derive_native_class(&input).unwrap().to_string();
+        assert!(output.contains("nativeclass_pre_ready"));
+        assert!(output.contains("EmptyReady"));
+    }
+
+    #[test]
+    fn derive_node_invalid_path() {
+        let input = parse_quote! {
+            #[inherit(Node)]
+            struct Foo {
+                #[node(HUD)]
+                score: NodeRef<Label>,
+            }
+        };
+        assert!(parse_derive_input(&input).is_err());
+    }
+
+    #[test]
+    fn derive_base_field() {
+        let input = parse_quote! {
+            #[inherit(Node)]
+            struct Foo {
+                #[base]
+                owner: Base<Node>,
+                bar: i64,
+            }
+        };
+        let data = parse_derive_input(&input).unwrap();
+        assert_eq!("owner", data.base_field.unwrap().to_string());
+
+        let output = derive_native_class(&input).unwrap().to_string();
+        assert!(output.contains("nativeclass_attach_base"));
+    }
+
+    #[test]
+    fn derive_base_field_opt_in() {
+        let input = parse_quote! {
+            #[inherit(Node)]
+            struct Foo {
+                base: Base<Node>,
+            }
+        };
+        let data = parse_derive_input(&input).unwrap();
+        assert!(data.base_field.is_none());
+
+        let output = derive_native_class(&input).unwrap().to_string();
+        assert!(!output.contains("nativeclass_attach_base"));
+    }
+
+    #[test]
+    fn derive_base_field_arguments() {
+        let input = parse_quote! {
+            #[inherit(Node)]
+            struct Foo {
+                #[base(Node)]
+                base: Base<Node>,
+            }
+        };
+        assert!(parse_derive_input(&input).is_err());
+    }
+
+    #[test]
+    fn derive_base_field_duplicate() {
+        let input = parse_quote! {
+            #[inherit(Node)]
+            struct Foo {
+                #[base]
+                base: Base<Node>,
+                #[base]
+                other: gdnative::object::Base<Node>,
+            }
+        };
+        assert!(parse_derive_input(&input).is_err());
+    }
+
+    #[test]
+    fn derive_user_data_reentrant() {
+        let input = parse_quote! {
+            #[inherit(Node)]
+            #[user_data(reentrant)]
+            struct Foo;
+        };
+        let data = parse_derive_input(&input).unwrap();
+        let user_data = data.user_data;
+        let gdnative_core = crate::crate_gdnative_core();
+        assert_eq!(
+            quote! { #gdnative_core::export::user_data::ReentrantCellData<Self> }.to_string(),
+            quote! { #user_data }.to_string(),
+        );
+    }
+
     #[test]
     fn derive_property_get_set() {
         let input = parse_quote! {
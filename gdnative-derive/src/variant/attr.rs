@@ -85,6 +85,10 @@ pub trait AttrBuilder: FromIterator<syn::Meta> {
 
 pub mod field;
 pub mod item;
+pub mod rename;
+pub mod var;
 
-pub use field::{FieldAttr, FieldAttrBuilder};
+pub use field::{FieldAttr, FieldAttrBuilder, FieldDefault};
 pub use item::{ItemAttr, ItemAttrBuilder};
+pub use rename::RenameRule;
+pub use var::VarAttrBuilder;
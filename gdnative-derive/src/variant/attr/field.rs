@@ -13,6 +13,27 @@ pub struct FieldAttr {
     pub skip_from_variant: bool,
     pub to_variant_with: Option<syn::Path>,
     pub from_variant_with: Option<syn::Path>,
+    pub rename: Option<syn::LitStr>,
+    pub default: Option<FieldDefault>,
+    pub flatten: Option<syn::Path>,
+    pub coerce: bool,
+}
+
+/// Value used for a field that is missing when converting from `Variant`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum FieldDefault {
+    /// `#[variant(default)]`, using `Default::default()`. Holds the flag for error reporting.
+    Trait(syn::Path),
+    /// `#[variant(default = "path::to::func")]`.
+    Func(syn::Path),
+}
+
+impl FieldDefault {
+    pub fn span(&self) -> Span {
+        match self {
+            FieldDefault::Trait(path) | FieldDefault::Func(path) => path.span(),
+        }
+    }
 }
 
 impl FieldAttr {
@@ -30,6 +51,10 @@ pub struct FieldAttrBuilder {
     skip_from_variant: bool,
     to_variant_with: Option<syn::Path>,
     from_variant_with: Option<syn::Path>,
+    rename: Option<syn::LitStr>,
+    default: Option<FieldDefault>,
+    flatten: Option<syn::Path>,
+    coerce: Option<syn::Path>,
     errors: Vec<syn::Error>,
 }
 
@@ -80,13 +105,43 @@ impl FieldAttrBuilder {
             }
         }
 
-        #[allow(clippy::single_match)]
         match name.as_str() {
             "skip" => {
                 self.skip_to_variant = true;
                 self.skip_from_variant = true;
                 return Ok(());
             }
+            "default" => {
+                if self
+                    .default
+                    .replace(FieldDefault::Trait(flag.clone()))
+                    .is_some()
+                {
+                    return Err(syn::Error::new(
+                        flag.span(),
+                        "the argument default is already set",
+                    ));
+                }
+                return Ok(());
+            }
+            "coerce" => {
+                if self.coerce.replace(flag.clone()).is_some() {
+                    return Err(syn::Error::new(
+                        flag.span(),
+                        "the argument coerce is already set",
+                    ));
+                }
+                return Ok(());
+            }
+            "flatten" => {
+                if self.flatten.replace(flag.clone()).is_some() {
+                    return Err(syn::Error::new(
+                        flag.span(),
+                        "the argument flatten is already set",
+                    ));
+                }
+                return Ok(());
+            }
             _ => {}
         }
 
@@ -106,7 +161,7 @@ impl FieldAttrBuilder {
         let syn::MetaNameValue { path, lit, .. } = pair;
 
         const VALID_KEYS: &str =
-            "to_variant_with, from_variant_with, with, skip_to_variant, skip_from_variant, skip";
+            "to_variant_with, from_variant_with, with, skip_to_variant, skip_from_variant, skip, rename, default, flatten, coerce";
 
         let name = path
             .get_ident()
@@ -172,6 +227,41 @@ impl FieldAttrBuilder {
 
                 return Ok(());
             }
+            "rename" => {
+                let lit_str = match lit {
+                    syn::Lit::Str(lit_str) => lit_str.clone(),
+                    _ => return Err(syn::Error::new(lit.span(), "expected string literal")),
+                };
+
+                if self.rename.replace(lit_str).is_some() {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "the argument rename is already set",
+                    ));
+                }
+
+                return Ok(());
+            }
+            "default" => {
+                let path = match lit {
+                    syn::Lit::Str(lit_str) => lit_str.parse::<syn::Path>()?,
+                    _ => {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "expecting a path to a function in double quotes: #[variant(default = \"path::to::func\")]",
+                        ))
+                    }
+                };
+
+                if self.default.replace(FieldDefault::Func(path)).is_some() {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "the argument default is already set",
+                    ));
+                }
+
+                return Ok(());
+            }
             _ => {}
         }
 
@@ -198,12 +288,38 @@ impl FromIterator<syn::Meta> for FieldAttrBuilder {
 impl AttrBuilder for FieldAttrBuilder {
     type Attr = FieldAttr;
     fn done(mut self) -> Result<FieldAttr, syn::Error> {
+        if let Some(flatten) = &self.flatten {
+            if let Some(rename) = &self.rename {
+                self.errors.push(syn::Error::new(
+                    rename.span(),
+                    "`rename` cannot be used on a `flatten` field",
+                ));
+            }
+            if self.default.is_some() {
+                self.errors.push(syn::Error::new(
+                    flatten.span(),
+                    "`default` cannot be used on a `flatten` field",
+                ));
+            }
+        }
+
+        if let (Some(coerce), Some(_)) = (&self.coerce, &self.from_variant_with) {
+            self.errors.push(syn::Error::new(
+                coerce.span(),
+                "`coerce` cannot be used together with `from_variant_with`",
+            ));
+        }
+
         if self.errors.is_empty() {
             Ok(FieldAttr {
                 skip_to_variant: self.skip_to_variant,
                 skip_from_variant: self.skip_from_variant,
                 to_variant_with: self.to_variant_with,
                 from_variant_with: self.from_variant_with,
+                rename: self.rename,
+                default: self.default,
+                flatten: self.flatten,
+                coerce: self.coerce.is_some(),
             })
         } else {
             let first_error = self.errors.remove(0);
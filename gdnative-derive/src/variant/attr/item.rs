@@ -5,16 +5,24 @@ use syn::spanned::Spanned;
 
 use crate::variant::{attr::generate_error_with_docs, repr::EnumReprKind};
 
+use super::rename::{parse_rename_rule, RenameRule};
 use super::AttrBuilder;
 
 #[derive(Clone, Debug)]
 pub struct ItemAttr {
     pub enum_repr_kind: Option<(EnumReprKind, Span)>,
+    pub rename_all: Option<RenameRule>,
+    pub coerce: bool,
 }
 
 #[derive(Debug, Default)]
 pub struct ItemAttrBuilder {
     enum_repr_kind: Option<syn::Ident>,
+    rename_all: Option<RenameRule>,
+    tag: Option<syn::LitStr>,
+    content: Option<syn::LitStr>,
+    untagged: Option<Span>,
+    coerce: bool,
 
     errors: Vec<syn::Error>,
 }
@@ -44,6 +52,22 @@ impl ItemAttrBuilder {
     }
 
     fn try_set_flag(&mut self, flag: &syn::Path) -> Result<(), syn::Error> {
+        if flag.is_ident("untagged") {
+            if self.untagged.replace(flag.span()).is_some() {
+                return Err(syn::Error::new(
+                    flag.span(),
+                    "the argument untagged is already set",
+                ));
+            }
+
+            return Ok(());
+        }
+
+        if flag.is_ident("coerce") {
+            self.coerce = true;
+            return Ok(());
+        }
+
         Err(generate_error_with_docs(
             flag.span(),
             "Unknown flag, or missing macro arguments",
@@ -59,7 +83,7 @@ impl ItemAttrBuilder {
     fn try_set_pair(&mut self, pair: &syn::MetaNameValue) -> Result<(), syn::Error> {
         let syn::MetaNameValue { path, lit, .. } = pair;
 
-        const VALID_KEYS: &str = "enum";
+        const VALID_KEYS: &str = "enum, rename_all, tag, content, untagged, coerce";
 
         let name = path
             .get_ident()
@@ -88,6 +112,42 @@ impl ItemAttrBuilder {
             }
         }
 
+        match name.as_str() {
+            "rename_all" => {
+                let rule = parse_rename_rule(lit)?;
+                if self.rename_all.replace(rule).is_some() {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "the argument rename_all is already set",
+                    ));
+                }
+
+                return Ok(());
+            }
+            "tag" | "content" => {
+                let lit_str = match lit {
+                    syn::Lit::Str(lit_str) => lit_str.clone(),
+                    _ => return Err(syn::Error::new(lit.span(), "expected string literal")),
+                };
+
+                let slot = if name == "tag" {
+                    &mut self.tag
+                } else {
+                    &mut self.content
+                };
+
+                if slot.replace(lit_str).is_some() {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        format!("the argument {name} is already set"),
+                    ));
+                }
+
+                return Ok(());
+            }
+            _ => {}
+        }
+
         Err(syn::Error::new(
             path.span(),
             format!("unknown argument, expected one of:\n\t{VALID_KEYS}"),
@@ -124,7 +184,52 @@ impl AttrBuilder for ItemAttrBuilder {
                 })
                 .transpose()?;
 
-            Ok(ItemAttr { enum_repr_kind })
+            let tagging = match (self.tag, self.content, self.untagged) {
+                (None, None, None) => None,
+                (Some(tag), None, None) => {
+                    let span = tag.span();
+                    Some((EnumReprKind::Internal { tag: tag.value() }, span))
+                }
+                (Some(tag), Some(content), None) => {
+                    let span = tag.span();
+                    Some((
+                        EnumReprKind::Adjacent {
+                            tag: tag.value(),
+                            content: content.value(),
+                        },
+                        span,
+                    ))
+                }
+                (None, None, Some(span)) => Some((EnumReprKind::Untagged, span)),
+                (None, Some(content), _) => {
+                    return Err(syn::Error::new(
+                        content.span(),
+                        "`content` can only be used together with `tag`",
+                    ));
+                }
+                (Some(_), _, Some(span)) => {
+                    return Err(syn::Error::new(
+                        span,
+                        "`untagged` cannot be used together with `tag`",
+                    ));
+                }
+            };
+
+            let enum_repr_kind = match (enum_repr_kind, tagging) {
+                (Some(_), Some((_, span))) => {
+                    return Err(syn::Error::new(
+                        span,
+                        "enum tagging cannot be combined with `enum = \"...\"` representations",
+                    ));
+                }
+                (kind, tagging) => kind.or(tagging),
+            };
+
+            Ok(ItemAttr {
+                enum_repr_kind,
+                rename_all: self.rename_all,
+                coerce: self.coerce,
+            })
         } else {
             let first_error = self.errors.remove(0);
             let errors = self
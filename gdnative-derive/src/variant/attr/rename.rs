@@ -0,0 +1,162 @@
+use std::str::FromStr;
+
+/// Case conventions that can be used with `#[variant(rename_all = "...")]`.
+#[allow(clippy::enum_variant_names)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum RenameRule {
+    LowerCase,
+    UpperCase,
+    PascalCase,
+    CamelCase,
+    SnakeCase,
+    ScreamingSnakeCase,
+    KebabCase,
+    ScreamingKebabCase,
+}
+
+impl RenameRule {
+    pub const VALID_VALUES: &'static str = "lowercase, UPPERCASE, PascalCase, camelCase, snake_case, SCREAMING_SNAKE_CASE, kebab-case, SCREAMING-KEBAB-CASE";
+
+    /// Applies the rule to an enum variant name, which is expected to be `PascalCase`.
+    pub fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            RenameRule::PascalCase => variant.to_owned(),
+            RenameRule::LowerCase => variant.to_ascii_lowercase(),
+            RenameRule::UpperCase => variant.to_ascii_uppercase(),
+            RenameRule::CamelCase => {
+                let mut chars = variant.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            }
+            RenameRule::SnakeCase => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
+            }
+            RenameRule::ScreamingSnakeCase => RenameRule::SnakeCase
+                .apply_to_variant(variant)
+                .to_ascii_uppercase(),
+            RenameRule::KebabCase => RenameRule::SnakeCase
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+            RenameRule::ScreamingKebabCase => RenameRule::ScreamingSnakeCase
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+        }
+    }
+
+    /// Applies the rule to a struct field name, which is expected to be `snake_case`.
+    pub fn apply_to_field(self, field: &str) -> String {
+        match self {
+            RenameRule::LowerCase | RenameRule::SnakeCase => field.to_owned(),
+            RenameRule::UpperCase | RenameRule::ScreamingSnakeCase => field.to_ascii_uppercase(),
+            RenameRule::PascalCase => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            RenameRule::CamelCase => {
+                let pascal = RenameRule::PascalCase.apply_to_field(field);
+                RenameRule::CamelCase.apply_to_variant(&pascal)
+            }
+            RenameRule::KebabCase => field.replace('_', "-"),
+            RenameRule::ScreamingKebabCase => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
+
+impl FromStr for RenameRule {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rule = match s {
+            "lowercase" => RenameRule::LowerCase,
+            "UPPERCASE" => RenameRule::UpperCase,
+            "PascalCase" => RenameRule::PascalCase,
+            "camelCase" => RenameRule::CamelCase,
+            "snake_case" => RenameRule::SnakeCase,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnakeCase,
+            "kebab-case" => RenameRule::KebabCase,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebabCase,
+            _ => return Err(()),
+        };
+
+        Ok(rule)
+    }
+}
+
+/// Parses the value of a `rename_all` argument, reporting unknown rules at the literal.
+pub fn parse_rename_rule(lit: &syn::Lit) -> Result<RenameRule, syn::Error> {
+    let lit_str = match lit {
+        syn::Lit::Str(lit_str) => lit_str,
+        _ => return Err(syn::Error::new(lit.span(), "expected string literal")),
+    };
+
+    lit_str.value().parse().map_err(|_| {
+        syn::Error::new(
+            lit_str.span(),
+            format!(
+                "unknown case convention, expected one of:\n\t{}",
+                RenameRule::VALID_VALUES
+            ),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rename_variants() {
+        let cases = [
+            (RenameRule::LowerCase, "veryfastcar"),
+            (RenameRule::UpperCase, "VERYFASTCAR"),
+            (RenameRule::PascalCase, "VeryFastCar"),
+            (RenameRule::CamelCase, "veryFastCar"),
+            (RenameRule::SnakeCase, "very_fast_car"),
+            (RenameRule::ScreamingSnakeCase, "VERY_FAST_CAR"),
+            (RenameRule::KebabCase, "very-fast-car"),
+            (RenameRule::ScreamingKebabCase, "VERY-FAST-CAR"),
+        ];
+
+        for (rule, expected) in cases {
+            assert_eq!(expected, rule.apply_to_variant("VeryFastCar"), "{rule:?}");
+        }
+    }
+
+    #[test]
+    fn rename_fields() {
+        let cases = [
+            (RenameRule::LowerCase, "very_fast_car"),
+            (RenameRule::UpperCase, "VERY_FAST_CAR"),
+            (RenameRule::PascalCase, "VeryFastCar"),
+            (RenameRule::CamelCase, "veryFastCar"),
+            (RenameRule::SnakeCase, "very_fast_car"),
+            (RenameRule::ScreamingSnakeCase, "VERY_FAST_CAR"),
+            (RenameRule::KebabCase, "very-fast-car"),
+            (RenameRule::ScreamingKebabCase, "VERY-FAST-CAR"),
+        ];
+
+        for (rule, expected) in cases {
+            assert_eq!(expected, rule.apply_to_field("very_fast_car"), "{rule:?}");
+        }
+    }
+}
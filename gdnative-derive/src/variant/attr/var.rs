@@ -0,0 +1,126 @@
+use std::iter::FromIterator;
+
+use syn::spanned::Spanned;
+
+use crate::variant::attr::generate_error_with_docs;
+
+use super::rename::{parse_rename_rule, RenameRule};
+use super::AttrBuilder;
+
+/// Attributes on enum variants.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct VarAttr {
+    pub rename: Option<syn::LitStr>,
+    pub rename_all: Option<RenameRule>,
+}
+
+#[derive(Debug, Default)]
+pub struct VarAttrBuilder {
+    rename: Option<syn::LitStr>,
+    rename_all: Option<RenameRule>,
+
+    errors: Vec<syn::Error>,
+}
+
+impl VarAttrBuilder {
+    fn extend_meta(&mut self, meta: &syn::Meta) {
+        match meta {
+            syn::Meta::Path(flag) => self.errors.push(generate_error_with_docs(
+                flag.span(),
+                "Unknown flag, or missing macro arguments",
+            )),
+            syn::Meta::NameValue(pair) => self.set_pair(pair),
+            syn::Meta::List(list) => {
+                for nested in list.nested.iter() {
+                    match nested {
+                        syn::NestedMeta::Meta(meta) => self.extend_meta(meta),
+                        _ => {
+                            self.errors
+                                .push(syn::Error::new(nested.span(), "unexpected nested meta"));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn set_pair(&mut self, pair: &syn::MetaNameValue) {
+        let err = self.try_set_pair(pair).err();
+        self.errors.extend(err);
+    }
+
+    fn try_set_pair(&mut self, pair: &syn::MetaNameValue) -> Result<(), syn::Error> {
+        let syn::MetaNameValue { path, lit, .. } = pair;
+
+        const VALID_KEYS: &str = "rename, rename_all";
+
+        if path.is_ident("rename") {
+            let lit_str = match lit {
+                syn::Lit::Str(lit_str) => lit_str.clone(),
+                _ => return Err(syn::Error::new(lit.span(), "expected string literal")),
+            };
+
+            if self.rename.replace(lit_str).is_some() {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "the argument rename is already set",
+                ));
+            }
+
+            return Ok(());
+        }
+
+        if path.is_ident("rename_all") {
+            let rule = parse_rename_rule(lit)?;
+            if self.rename_all.replace(rule).is_some() {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "the argument rename_all is already set",
+                ));
+            }
+
+            return Ok(());
+        }
+
+        Err(syn::Error::new(
+            path.span(),
+            format!("unknown argument, expected one of:\n\t{VALID_KEYS}"),
+        ))
+    }
+}
+
+impl FromIterator<syn::Meta> for VarAttrBuilder {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = syn::Meta>,
+    {
+        let mut builder = VarAttrBuilder::default();
+        for meta in iter {
+            builder.extend_meta(&meta);
+        }
+        builder
+    }
+}
+
+impl AttrBuilder for VarAttrBuilder {
+    type Attr = VarAttr;
+    fn done(mut self) -> Result<VarAttr, syn::Error> {
+        if self.errors.is_empty() {
+            Ok(VarAttr {
+                rename: self.rename,
+                rename_all: self.rename_all,
+            })
+        } else {
+            let first_error = self.errors.remove(0);
+            let errors = self
+                .errors
+                .into_iter()
+                .fold(first_error, |mut errors, error| {
+                    errors.combine(error);
+                    errors
+                });
+
+            Err(errors)
+        }
+    }
+}
@@ -42,8 +42,8 @@ pub(crate) fn extend_bounds(
 
         match repr {
             Repr::Enum(EnumRepr { ref variants, .. }) => {
-                for (_, var_repr) in variants.iter() {
-                    visit_var_repr(visitor, var_repr, dir);
+                for var in variants.iter() {
+                    visit_var_repr(visitor, &var.repr, dir);
                 }
             }
             Repr::Struct(StructRepr(var_repr)) => {
@@ -4,7 +4,7 @@ use crate::variant::bounds;
 use crate::variant::repr::VariantRepr;
 use syn::Ident;
 
-use super::repr::{EnumRepr, EnumReprKind, Repr, StructRepr};
+use super::repr::{EnumRepr, EnumReprKind, EnumVariant, Repr, StructRepr};
 use super::DeriveData;
 
 pub(crate) fn expand_from_variant(derive_data: DeriveData) -> Result<TokenStream2, syn::Error> {
@@ -37,30 +37,32 @@ pub(crate) fn expand_from_variant(derive_data: DeriveData) -> Result<TokenStream
             primitive_repr,
         }) => match kind {
             EnumReprKind::External => expand_external(&ident, &input_ident, variants)?,
+            EnumReprKind::Internal { tag } => {
+                expand_internal(&ident, &input_ident, variants, &tag)?
+            }
+            EnumReprKind::Adjacent { tag, content } => {
+                expand_adjacent(&ident, &input_ident, variants, &tag, &content)?
+            }
+            EnumReprKind::Untagged => expand_untagged(&ident, &input_ident, variants)?,
             EnumReprKind::Str => {
-                if let Some((var_ident, _)) = variants
+                if let Some(var) = variants
                     .iter()
-                    .find(|(_, var_repr)| !matches!(var_repr, VariantRepr::Unit(_)))
+                    .find(|var| !matches!(var.repr, VariantRepr::Unit(_)))
                 {
                     return Err(syn::Error::new(
-                        var_ident.span(),
+                        var.ident.span(),
                         "`str` representation can only be used for fieldless enums",
                     ));
                 }
 
-                let var_ident_strings: Vec<String> = variants
-                    .iter()
-                    .map(|(var_ident, _)| format!("{var_ident}"))
-                    .collect();
-
-                let var_ident_string_literals = var_ident_strings
+                let var_ident_string_literals = variants
                     .iter()
-                    .map(|string| Literal::string(string))
+                    .map(|var| Literal::string(&var.name))
                     .collect::<Vec<_>>();
 
                 let ref_var_ident_string_literals = &var_ident_string_literals;
 
-                let variant_idents = variants.iter().map(|(var_ident, _)| var_ident);
+                let variant_idents = variants.iter().map(|var| &var.ident);
 
                 let early_return = variants.is_empty().then(|| {
                     quote! {
@@ -100,7 +102,12 @@ pub(crate) fn expand_from_variant(derive_data: DeriveData) -> Result<TokenStream
                 let mut clauses = Vec::new();
                 let mut hints = Vec::new();
                 let mut discriminant = quote! { 0 };
-                for (var_ident, var_repr) in variants.iter() {
+                for EnumVariant {
+                    ident: var_ident,
+                    repr: var_repr,
+                    ..
+                } in variants.iter()
+                {
                     if let VariantRepr::Unit(expr) = var_repr {
                         if let Some(expr) = expr {
                             discriminant = quote!(#expr);
@@ -163,27 +170,28 @@ pub(crate) fn expand_from_variant(derive_data: DeriveData) -> Result<TokenStream
 fn expand_external(
     ident: &syn::Ident,
     input_ident: &syn::Ident,
-    variants: Vec<(Ident, super::repr::VariantRepr)>,
+    variants: Vec<EnumVariant>,
 ) -> Result<TokenStream2, syn::Error> {
     let var_input_ident = Ident::new("__enum_variant", Span::call_site());
 
-    let var_ident_strings: Vec<String> = variants
-        .iter()
-        .map(|(var_ident, _)| format!("{var_ident}"))
-        .collect();
-
-    let var_ident_string_literals = var_ident_strings
+    let var_ident_string_literals = variants
         .iter()
-        .map(|string| Literal::string(string))
+        .map(|var| Literal::string(&var.name))
         .collect::<Vec<_>>();
 
     let ref_var_ident_string_literals = &var_ident_string_literals;
 
     let var_from_variants = variants
         .iter()
-        .map(|(var_ident, var_repr)| {
-            var_repr.make_from_variant_expr(&var_input_ident, &quote! { #ident::#var_ident })
-        })
+        .map(
+            |EnumVariant {
+                 ident: var_ident,
+                 repr: var_repr,
+                 ..
+             }| {
+                var_repr.make_from_variant_expr(&var_input_ident, &quote! { #ident::#var_ident })
+            },
+        )
         .collect::<Result<Vec<_>, _>>()?;
 
     let var_input_ident_iter = std::iter::repeat(&var_input_ident);
@@ -238,3 +246,185 @@ fn expand_external(
         }
     })
 }
+
+/// Reads the tag of an internally or adjacently tagged enum from `__dict`, returning early on
+/// errors.
+fn read_tag(input_ident: &syn::Ident, tag: &str, expected: TokenStream2) -> TokenStream2 {
+    let tag_string_literal = Literal::string(tag);
+    quote! {
+        let __dict = ::gdnative::core_types::Dictionary::from_variant(#input_ident)
+            .map_err(|__err| FVE::InvalidEnumRepr {
+                expected: #expected,
+                error: std::boxed::Box::new(__err),
+            })?;
+
+        let __tag = String::from_variant(&__dict.get_or_nil(#tag_string_literal))
+            .map_err(|__err| FVE::InvalidEnumRepr {
+                expected: #expected,
+                error: std::boxed::Box::new(FVE::InvalidField {
+                    field_name: #tag_string_literal,
+                    error: std::boxed::Box::new(__err),
+                }),
+            })?;
+    }
+}
+
+fn expand_internal(
+    ident: &syn::Ident,
+    input_ident: &syn::Ident,
+    variants: Vec<EnumVariant>,
+    tag: &str,
+) -> Result<TokenStream2, syn::Error> {
+    let read_tag = read_tag(input_ident, tag, quote!(VariantEnumRepr::InternallyTagged));
+
+    let var_ident_string_literals = variants
+        .iter()
+        .map(|var| Literal::string(&var.name))
+        .collect::<Vec<_>>();
+
+    let ref_var_ident_string_literals = &var_ident_string_literals;
+
+    // The remaining entries of the dictionary are the content of the variant, so the input can
+    // be passed as-is to the struct and newtype conversions.
+    let var_from_variants = variants
+        .iter()
+        .map(
+            |EnumVariant {
+                 ident: var_ident,
+                 repr: var_repr,
+                 ..
+             }| match var_repr {
+                VariantRepr::Unit(_) => Ok(quote! { Ok(#ident::#var_ident) }),
+                VariantRepr::Tuple(fields) if fields.len() != 1 => Err(syn::Error::new(
+                    var_ident.span(),
+                    "internally tagged enums cannot contain tuple variants",
+                )),
+                _ => var_repr.make_from_variant_expr(input_ident, &quote! { #ident::#var_ident }),
+            },
+        )
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(quote! {
+        #read_tag
+
+        match __tag.as_str() {
+            #(
+                #ref_var_ident_string_literals => {
+                    (#var_from_variants).map_err(|err| FVE::InvalidEnumVariant {
+                        variant: #ref_var_ident_string_literals,
+                        error: std::boxed::Box::new(err),
+                    })
+                },
+            )*
+            variant => Err(FVE::UnknownEnumVariant {
+                variant: variant.to_string(),
+                expected: &[#(#ref_var_ident_string_literals),*],
+            }),
+        }
+    })
+}
+
+fn expand_adjacent(
+    ident: &syn::Ident,
+    input_ident: &syn::Ident,
+    variants: Vec<EnumVariant>,
+    tag: &str,
+    content: &str,
+) -> Result<TokenStream2, syn::Error> {
+    let var_input_ident = Ident::new("__enum_variant", Span::call_site());
+    let content_string_literal = Literal::string(content);
+    let read_tag = read_tag(input_ident, tag, quote!(VariantEnumRepr::AdjacentlyTagged));
+
+    let var_ident_string_literals = variants
+        .iter()
+        .map(|var| Literal::string(&var.name))
+        .collect::<Vec<_>>();
+
+    let ref_var_ident_string_literals = &var_ident_string_literals;
+
+    let var_from_variants = variants
+        .iter()
+        .map(
+            |EnumVariant {
+                 ident: var_ident,
+                 repr: var_repr,
+                 ..
+             }| match var_repr {
+                // Content is omitted for unit variants
+                VariantRepr::Unit(_) => Ok(quote! { Ok(#ident::#var_ident) }),
+                _ => var_repr
+                    .make_from_variant_expr(&var_input_ident, &quote! { #ident::#var_ident }),
+            },
+        )
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let var_input_ident_iter = std::iter::repeat(&var_input_ident);
+
+    Ok(quote! {
+        #read_tag
+
+        match __tag.as_str() {
+            #(
+                #ref_var_ident_string_literals => {
+                    let #var_input_ident_iter = &__dict.get_or_nil(#content_string_literal);
+                    (#var_from_variants).map_err(|err| FVE::InvalidEnumVariant {
+                        variant: #ref_var_ident_string_literals,
+                        error: std::boxed::Box::new(err),
+                    })
+                },
+            )*
+            variant => Err(FVE::UnknownEnumVariant {
+                variant: variant.to_string(),
+                expected: &[#(#ref_var_ident_string_literals),*],
+            }),
+        }
+    })
+}
+
+fn expand_untagged(
+    ident: &syn::Ident,
+    input_ident: &syn::Ident,
+    variants: Vec<EnumVariant>,
+) -> Result<TokenStream2, syn::Error> {
+    let attempts = variants
+        .iter()
+        .map(
+            |EnumVariant {
+                 ident: var_ident,
+                 repr: var_repr,
+                 ..
+             }| {
+                let tokens = match var_repr {
+                    VariantRepr::Unit(_) => quote! {
+                        if #input_ident.is_nil() {
+                            return Ok(#ident::#var_ident);
+                        }
+                    },
+                    _ => {
+                        let from_variant = var_repr
+                            .make_from_variant_expr(input_ident, &quote! { #ident::#var_ident })?;
+                        quote! {
+                            if let Ok(__value) = #from_variant {
+                                return Ok(__value);
+                            }
+                        }
+                    }
+                };
+                Ok(tokens)
+            },
+        )
+        .collect::<Result<Vec<_>, syn::Error>>()?;
+
+    let message = Literal::string(&format!(
+        "value did not match any variant of untagged enum {ident}"
+    ));
+
+    Ok(quote! {
+        #( #attempts )*
+
+        Err(FVE::InvalidEnumRepr {
+            expected: VariantEnumRepr::Untagged,
+            error: std::boxed::Box::new(FVE::custom(#message)),
+        })
+    })
+}
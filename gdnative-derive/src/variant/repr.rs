@@ -1,7 +1,10 @@
 use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
+use syn::spanned::Spanned;
 use syn::{DataEnum, Fields, Ident, Type};
 
-use super::attr::{FieldAttr, FieldAttrBuilder, ItemAttr};
+use super::attr::{
+    FieldAttr, FieldAttrBuilder, FieldDefault, ItemAttr, RenameRule, VarAttrBuilder,
+};
 use super::{parse_attrs, ToVariantTrait};
 
 // Shouldn't matter since this is immediately unpacked anyway.
@@ -24,6 +27,8 @@ pub(crate) enum VariantRepr {
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub(crate) struct Field {
     pub ident: Ident,
+    /// Dictionary key of the field, after renaming. Unused for tuple fields.
+    pub name: String,
     pub ty: Type,
     pub attr: FieldAttr,
 }
@@ -35,13 +40,27 @@ pub(crate) struct StructRepr(pub VariantRepr);
 pub(crate) struct EnumRepr {
     pub kind: EnumReprKind,
     pub primitive_repr: Option<Type>,
-    pub variants: Vec<(Ident, VariantRepr)>,
+    pub variants: Vec<EnumVariant>,
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub(crate) struct EnumVariant {
+    pub ident: Ident,
+    /// Name of the variant used as tag, after renaming.
+    pub name: String,
+    pub repr: VariantRepr,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
 pub enum EnumReprKind {
     /// Externally-tagged objects, i.e. the original behavior.
     External,
+    /// Internally-tagged objects, i.e. `{ "tag": "Variant", "field": value }`.
+    Internal { tag: String },
+    /// Adjacently-tagged objects, i.e. `{ "tag": "Variant", "content": value }`.
+    Adjacent { tag: String, content: String },
+    /// The variant content without any tag. Variants are tried in order.
+    Untagged,
     /// The integer type specified by the `repr` attribute of the enum.
     Repr,
     /// Represent as strings.
@@ -58,14 +77,24 @@ impl EnumRepr {
             .variants
             .iter()
             .map(|variant| {
-                let mut repr = VariantRepr::repr_for(&variant.fields)?;
+                let var_attr = parse_attrs::<VarAttrBuilder, _>(&variant.attrs)?;
+
+                let mut repr =
+                    VariantRepr::repr_for(&variant.fields, var_attr.rename_all, attr.coerce)?;
                 if let VariantRepr::Unit(discriminant) = &mut repr {
                     if let Some((_, expr)) = &variant.discriminant {
                         *discriminant = Some(expr.clone());
                     }
                 }
 
-                Ok((variant.ident.clone(), repr))
+                let ident = variant.ident.clone();
+                let name = match (&var_attr.rename, attr.rename_all) {
+                    (Some(rename), _) => rename.value(),
+                    (None, Some(rule)) => rule.apply_to_variant(&ident.to_string()),
+                    (None, None) => format!("{ident}"),
+                };
+
+                Ok(EnumVariant { ident, name, repr })
             })
             .collect::<Result<_, syn::Error>>()?;
 
@@ -81,19 +110,26 @@ impl EnumRepr {
 
 impl StructRepr {
     pub(crate) fn repr_for(attr: ItemAttr, fields: &Fields) -> Result<Self, syn::Error> {
-        if let Some((_, span)) = attr.enum_repr_kind {
-            return Err(syn::Error::new(
-                span,
-                "`enum` representation can only be set for enums",
-            ));
+        if let Some((kind, span)) = attr.enum_repr_kind {
+            let message = match kind {
+                EnumReprKind::Repr | EnumReprKind::Str => {
+                    "`enum` representation can only be set for enums"
+                }
+                _ => "enum tagging can only be set for enums",
+            };
+            return Err(syn::Error::new(span, message));
         }
 
-        VariantRepr::repr_for(fields).map(StructRepr)
+        VariantRepr::repr_for(fields, attr.rename_all, attr.coerce).map(StructRepr)
     }
 }
 
 impl VariantRepr {
-    pub(crate) fn repr_for(fields: &Fields) -> Result<Self, syn::Error> {
+    pub(crate) fn repr_for(
+        fields: &Fields,
+        rename_all: Option<RenameRule>,
+        coerce: bool,
+    ) -> Result<Self, syn::Error> {
         let this = match fields {
             Fields::Named(fields) => VariantRepr::Struct(
                 fields
@@ -102,8 +138,19 @@ impl VariantRepr {
                     .map(|f| {
                         let ident = f.ident.clone().expect("fields should be named");
                         let ty = f.ty.clone();
-                        let attr = parse_attrs::<FieldAttrBuilder, _>(&f.attrs)?;
-                        Ok(Field { ident, ty, attr })
+                        let mut attr = parse_attrs::<FieldAttrBuilder, _>(&f.attrs)?;
+                        attr.coerce |= coerce;
+                        let name = match (&attr.rename, rename_all) {
+                            (Some(rename), _) => rename.value(),
+                            (None, Some(rule)) => rule.apply_to_field(&ident.to_string()),
+                            (None, None) => format!("{ident}"),
+                        };
+                        Ok(Field {
+                            ident,
+                            name,
+                            ty,
+                            attr,
+                        })
                     })
                     .collect::<Result<Vec<_>, syn::Error>>()?,
             ),
@@ -114,9 +161,31 @@ impl VariantRepr {
                     .enumerate()
                     .map(|(n, f)| {
                         let ident = Ident::new(&format!("__field_{n}"), Span::call_site());
+                        let name = format!("{n}");
                         let ty = f.ty.clone();
-                        let attr = parse_attrs::<FieldAttrBuilder, _>(&f.attrs)?;
-                        Ok(Field { ident, ty, attr })
+                        let mut attr = parse_attrs::<FieldAttrBuilder, _>(&f.attrs)?;
+                        attr.coerce |= coerce;
+
+                        let named_only = [
+                            ("rename", attr.rename.as_ref().map(|lit| lit.span())),
+                            ("default", attr.default.as_ref().map(|d| d.span())),
+                            ("flatten", attr.flatten.as_ref().map(|p| p.span())),
+                        ];
+                        for (arg, span) in named_only {
+                            if let Some(span) = span {
+                                return Err(syn::Error::new(
+                                    span,
+                                    format!("`{arg}` can only be used on named fields"),
+                                ));
+                            }
+                        }
+
+                        Ok(Field {
+                            ident,
+                            name,
+                            ty,
+                            attr,
+                        })
                     })
                     .collect::<Result<_, syn::Error>>()?,
             ),
@@ -183,27 +252,12 @@ impl VariantRepr {
                     }
                 }
             }
-            VariantRepr::Struct(fields) => {
-                let fields: Vec<&Field> =
-                    fields.iter().filter(|f| !f.attr.skip_to_variant).collect();
-
-                let name_strings: Vec<String> =
-                    fields.iter().map(|f| format!("{}", &f.ident)).collect();
-
-                let name_string_literals =
-                    name_strings.iter().map(|string| Literal::string(string));
-
-                let exprs = fields.iter().map(|f| f.make_to_variant_expr(trait_kind));
-
+            VariantRepr::Struct(_) => {
+                let insert_fields = self.make_dict_insert_stmts(trait_kind);
                 quote! {
                     {
                         let __dict = ::gdnative::core_types::Dictionary::new();
-                        #(
-                            {
-                                let __key = ::gdnative::core_types::GodotString::from(#name_string_literals).to_variant();
-                                __dict.insert(&__key, &#exprs);
-                            }
-                        )*
+                        #insert_fields
                         __dict.into_shared().to_variant()
                     }
                 }
@@ -213,6 +267,37 @@ impl VariantRepr {
         Ok(tokens)
     }
 
+    /// Returns statements inserting the fields of a struct representation into `__dict`.
+    /// Produces no statements for other representations.
+    pub(crate) fn make_dict_insert_stmts(&self, trait_kind: ToVariantTrait) -> TokenStream2 {
+        let fields = match self {
+            VariantRepr::Struct(fields) => fields,
+            _ => return quote! {},
+        };
+
+        let stmts = fields
+            .iter()
+            .filter(|f| !f.attr.skip_to_variant)
+            .map(|f| {
+                let expr = f.make_to_variant_expr(trait_kind);
+                if f.attr.flatten.is_some() {
+                    merge_flattened_stmts(&expr, &f.ident.to_string())
+                } else {
+                    let name_string_literal = Literal::string(&f.name);
+                    quote! {
+                        {
+                            let __key = ::gdnative::core_types::GodotString::from(#name_string_literal).to_variant();
+                            __dict.insert(&__key, &#expr);
+                        }
+                    }
+                }
+            });
+
+        quote! {
+            #( #stmts )*
+        }
+    }
+
     pub(crate) fn make_from_variant_expr(
         &self,
         variant: &Ident,
@@ -302,31 +387,77 @@ impl VariantRepr {
                 }
             }
             VariantRepr::Struct(fields) => {
-                let skipped_fields: Vec<&Field> =
-                    fields.iter().filter(|f| f.attr.skip_from_variant).collect();
+                let field_stmts = fields.iter().map(|f| {
+                    let ident = &f.ident;
+                    if f.attr.skip_from_variant {
+                        let default_expr = f.make_default_expr();
+                        return quote! {
+                            let #ident = #default_expr;
+                        };
+                    }
 
-                let non_skipped_fields: Vec<&Field> = fields
-                    .iter()
-                    .filter(|f| !f.attr.skip_from_variant)
-                    .collect();
+                    let name_string_literal = Literal::string(&f.name);
 
-                let skipped_idents = skipped_fields.iter().map(|f| &f.ident);
-                let non_skipped_idents: Vec<&Ident> =
-                    non_skipped_fields.iter().map(|f| &f.ident).collect();
-                let ctor_idents = fields.iter().map(|f| &f.ident);
+                    if f.attr.flatten.is_some() {
+                        // Flattened fields are read from the same dictionary as their parent.
+                        let expr = f.make_from_variant_expr(&quote!(#variant));
 
-                let name_strings: Vec<String> = non_skipped_idents
-                    .iter()
-                    .map(|ident| format!("{ident}"))
-                    .collect();
+                        // `None` is skipped when converting to `Variant`, so a flattened `Option`
+                        // is `None` if the keys of its value are missing.
+                        if f.attr.from_variant_with.is_none() && is_option(&f.ty) {
+                            return quote! {
+                                let #ident = match #expr {
+                                    Ok(__value) => __value,
+                                    Err(err) if ::gdnative::private::flatten::is_missing_field(&err) => {
+                                        std::option::Option::None
+                                    }
+                                    Err(err) => {
+                                        return Err(FVE::InvalidField {
+                                            field_name: #name_string_literal,
+                                            error: std::boxed::Box::new(err),
+                                        });
+                                    }
+                                };
+                            };
+                        }
 
-                let name_string_literals =
-                    name_strings.iter().map(|string| Literal::string(string));
+                        return quote! {
+                            let #ident = #expr.map_err(|err| FVE::InvalidField {
+                                field_name: #name_string_literal,
+                                error: std::boxed::Box::new(err),
+                            })?;
+                        };
+                    }
 
-                let expr_variant = &quote!(&__dict.get_or_nil(&__key));
-                let exprs = non_skipped_fields
-                    .iter()
-                    .map(|f| f.make_from_variant_expr(expr_variant));
+                    let expr = f.make_from_variant_expr(&quote!(&__dict.get_or_nil(&__key)));
+                    let convert = quote! {
+                        #expr.map_err(|err| FVE::InvalidField {
+                            field_name: __field_name,
+                            error: std::boxed::Box::new(err),
+                        })?
+                    };
+
+                    let value = if f.attr.default.is_some() {
+                        let default_expr = f.make_default_expr();
+                        quote! {
+                            if __dict.contains(&__key) {
+                                #convert
+                            } else {
+                                #default_expr
+                            }
+                        }
+                    } else {
+                        convert
+                    };
+
+                    quote! {
+                        let __field_name = #name_string_literal;
+                        let __key = ::gdnative::core_types::GodotString::from(__field_name).to_variant();
+                        let #ident = #value;
+                    }
+                });
+
+                let ctor_idents = fields.iter().map(|f| &f.ident);
 
                 quote! {
                     {
@@ -336,18 +467,7 @@ impl VariantRepr {
                                 error: std::boxed::Box::new(__err),
                             })
                             .and_then(|__dict| {
-                                #(
-                                    let __field_name = #name_string_literals;
-                                    let __key = ::gdnative::core_types::GodotString::from(__field_name).to_variant();
-                                    let #non_skipped_idents = #exprs
-                                        .map_err(|err| FVE::InvalidField {
-                                            field_name: __field_name,
-                                            error: std::boxed::Box::new(err),
-                                        })?;
-                                )*
-                                #(
-                                    let #skipped_idents = std::default::Default::default();
-                                )*
+                                #( #field_stmts )*
                                 Ok(#ctor { #( #ctor_idents ),* })
                             })
                     }
@@ -359,6 +479,56 @@ impl VariantRepr {
     }
 }
 
+/// Returns statements merging the entries of `expr` into `__dict`. Values that don't convert to
+/// a `Dictionary` are skipped, so that `to_variant` never panics.
+pub(crate) fn merge_dict_stmts(expr: &TokenStream2) -> TokenStream2 {
+    quote! {
+        {
+            if let Ok(__flattened) = ::gdnative::core_types::Dictionary::from_variant(&#expr) {
+                for (__key, __value) in __flattened.iter() {
+                    __dict.insert(&__key, &__value);
+                }
+            }
+        }
+    }
+}
+
+/// Returns statements merging the entries of the flattened field `name` into `__dict`. `Nil`,
+/// e.g. from `None`, is skipped. Other values that aren't a `Dictionary` are reported as errors.
+fn merge_flattened_stmts(expr: &TokenStream2, name: &str) -> TokenStream2 {
+    let merge = merge_dict_stmts(&quote!(__flattened));
+    quote! {
+        {
+            let __flattened = #expr;
+            match __flattened.get_type() {
+                ::gdnative::core_types::VariantType::Nil => {}
+                ::gdnative::core_types::VariantType::Dictionary => #merge
+                __variant_type => {
+                    ::gdnative::godot_error!(
+                        "flattened field `{}` is not represented as a Dictionary, but as {:?}",
+                        #name,
+                        __variant_type,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Returns `true` if `ty` is syntactically an `Option`.
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => type_path
+            .path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident == "Option"),
+        Type::Group(group) => is_option(&group.elem),
+        Type::Paren(paren) => is_option(&paren.elem),
+        _ => false,
+    }
+}
+
 impl Field {
     fn make_to_variant_expr(&self, trait_kind: ToVariantTrait) -> TokenStream2 {
         let Field { ident, attr, .. } = self;
@@ -373,8 +543,23 @@ impl Field {
     fn make_from_variant_expr(&self, variant: &TokenStream2) -> TokenStream2 {
         if let Some(from_variant_with) = &self.attr.from_variant_with {
             quote!(#from_variant_with(#variant))
+        } else if self.attr.coerce {
+            let ty = &self.ty;
+            quote! {
+                {
+                    use ::gdnative::private::coerce::{ViaCoerce as _, ViaFromVariant as _};
+                    (&&::gdnative::private::coerce::Coerce::<#ty>::new()).lenient_from_variant(#variant)
+                }
+            }
         } else {
             quote!(::gdnative::core_types::FromVariant::from_variant(#variant))
         }
     }
+
+    fn make_default_expr(&self) -> TokenStream2 {
+        match &self.attr.default {
+            Some(FieldDefault::Func(path)) => quote!(#path()),
+            Some(FieldDefault::Trait(_)) | None => quote!(std::default::Default::default()),
+        }
+    }
 }
@@ -1,7 +1,7 @@
 use crate::variant::bounds;
 use proc_macro2::{Literal, TokenStream as TokenStream2};
 
-use crate::variant::repr::{EnumReprKind, VariantRepr};
+use crate::variant::repr::{merge_dict_stmts, EnumReprKind, EnumVariant, VariantRepr};
 
 use super::repr::{EnumRepr, Repr, StructRepr};
 use super::{DeriveData, ToVariantTrait};
@@ -50,11 +50,10 @@ pub(crate) fn expand_to_variant(
                     EnumReprKind::External => {
                         let match_arms = variants
                             .iter()
-                            .map(|(var_ident, var_repr)| {
+                            .map(|EnumVariant { ident: var_ident, name, repr: var_repr }| {
                                 let destructure_pattern = var_repr.destructure_pattern();
                                 let to_variant = var_repr.make_to_variant_expr(trait_kind)?;
-                                let var_ident_string = format!("{var_ident}");
-                                let var_ident_string_literal = Literal::string(&var_ident_string);
+                                let var_ident_string_literal = Literal::string(name);
                                 let tokens = quote! {
                                     #ident::#var_ident #destructure_pattern => {
                                         let __dict = ::gdnative::core_types::Dictionary::new();
@@ -76,16 +75,122 @@ pub(crate) fn expand_to_variant(
                             }
                         }
                     }
+                    EnumReprKind::Internal { tag } => {
+                        let tag_string_literal = Literal::string(&tag);
+                        let match_arms = variants
+                            .iter()
+                            .map(|EnumVariant { ident: var_ident, name, repr: var_repr }| {
+                                let destructure_pattern = var_repr.destructure_pattern();
+                                let var_ident_string_literal = Literal::string(name);
+                                let insert_content = match var_repr {
+                                    VariantRepr::Unit(_) => quote! {},
+                                    VariantRepr::Struct(_) => var_repr.make_dict_insert_stmts(trait_kind),
+                                    VariantRepr::Tuple(fields) if fields.len() == 1 => {
+                                        let to_variant = var_repr.make_to_variant_expr(trait_kind)?;
+                                        merge_dict_stmts(&to_variant)
+                                    }
+                                    VariantRepr::Tuple(_) => {
+                                        return Err(syn::Error::new(
+                                            var_ident.span(),
+                                            "internally tagged enums cannot contain tuple variants",
+                                        ));
+                                    }
+                                };
+
+                                let tokens = quote! {
+                                    #ident::#var_ident #destructure_pattern => {
+                                        let __dict = ::gdnative::core_types::Dictionary::new();
+                                        __dict.insert(#tag_string_literal, #var_ident_string_literal);
+                                        #insert_content
+                                        ::gdnative::core_types::ToVariant::to_variant(&__dict.into_shared())
+                                    }
+                                };
+                                Ok(tokens)
+                            })
+                            .collect::<Result<Vec<_>, syn::Error>>()?;
+
+                        quote! {
+                            match #to_variant_receiver {
+                                #( #match_arms ),*
+                            }
+                        }
+                    }
+                    EnumReprKind::Adjacent { tag, content } => {
+                        let tag_string_literal = Literal::string(&tag);
+                        let content_string_literal = Literal::string(&content);
+                        let match_arms = variants
+                            .iter()
+                            .map(|EnumVariant { ident: var_ident, name, repr: var_repr }| {
+                                let destructure_pattern = var_repr.destructure_pattern();
+                                let var_ident_string_literal = Literal::string(name);
+                                let insert_content = if matches!(var_repr, VariantRepr::Unit(_)) {
+                                    quote! {}
+                                } else {
+                                    let to_variant = var_repr.make_to_variant_expr(trait_kind)?;
+                                    quote! {
+                                        __dict.insert(#content_string_literal, #to_variant);
+                                    }
+                                };
+
+                                let tokens = quote! {
+                                    #ident::#var_ident #destructure_pattern => {
+                                        let __dict = ::gdnative::core_types::Dictionary::new();
+                                        __dict.insert(#tag_string_literal, #var_ident_string_literal);
+                                        #insert_content
+                                        ::gdnative::core_types::ToVariant::to_variant(&__dict.into_shared())
+                                    }
+                                };
+                                Ok(tokens)
+                            })
+                            .collect::<Result<Vec<_>, syn::Error>>()?;
+
+                        quote! {
+                            match #to_variant_receiver {
+                                #( #match_arms ),*
+                            }
+                        }
+                    }
+                    EnumReprKind::Untagged => {
+                        let match_arms = variants
+                            .iter()
+                            .map(
+                                |EnumVariant {
+                                     ident: var_ident,
+                                     repr: var_repr,
+                                     ..
+                                 }| {
+                                    let destructure_pattern = var_repr.destructure_pattern();
+                                    let to_variant = if matches!(var_repr, VariantRepr::Unit(_)) {
+                                        quote! { ::gdnative::core_types::Variant::nil() }
+                                    } else {
+                                        var_repr.make_to_variant_expr(trait_kind)?
+                                    };
+
+                                    let tokens = quote! {
+                                        #ident::#var_ident #destructure_pattern => {
+                                            #to_variant
+                                        }
+                                    };
+                                    Ok(tokens)
+                                },
+                            )
+                            .collect::<Result<Vec<_>, syn::Error>>()?;
+
+                        quote! {
+                            match #to_variant_receiver {
+                                #( #match_arms ),*
+                            }
+                        }
+                    }
                     EnumReprKind::Str => {
                         let match_arms = variants
                             .iter()
-                            .map(|(var_ident, var_repr)| {
+                            .map(|EnumVariant { ident: var_ident, name, repr: var_repr }| {
                                 if !matches!(var_repr, VariantRepr::Unit(_)) {
                                     return Err(syn::Error::new(var_ident.span(), "`str` representation can only be used for fieldless enums"));
                                 }
 
-                                let var_ident_string = format!("{var_ident}");
-                                let var_ident_string_literal = Literal::string(&var_ident_string);
+                                let var_ident_string_literal = Literal::string(name);
                                 let tokens = quote! {
                                     #ident::#var_ident => {
                                         ::gdnative::core_types::ToVariant::to_variant(#var_ident_string_literal)
@@ -110,12 +215,12 @@ pub(crate) fn expand_to_variant(
                             )
                         })?;
 
-                        if let Some((var_ident, _)) = variants
+                        if let Some(var) = variants
                             .iter()
-                            .find(|(_, var_repr)| !matches!(var_repr, VariantRepr::Unit(_)))
+                            .find(|var| !matches!(var.repr, VariantRepr::Unit(_)))
                         {
                             return Err(syn::Error::new(
-                                var_ident.span(),
+                                var.ident.span(),
                                 "`repr` representation can only be used for fieldless enums",
                             ));
                         }
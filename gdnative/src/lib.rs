@@ -50,6 +50,12 @@
 //!
 //! * **`serde`**<br>
 //!   Enable for `serde` support of several core types. See also [`Variant`](core_types::Variant).
+//!   Also enables the [`serde`](crate::serde) module, which converts any `serde` types into
+//!   `Variant`s and back.
+//!
+//! * **`serde_json`**<br>
+//!   Enables lossless conversion between `Variant` and `serde_json::Value`, see
+//!   [`Variant::to_json_value`](core_types::Variant::to_json_value). Implies `serde`.
 //!
 //! * **`inventory`**<br>
 //!   Enables automatic class registration via `inventory`.
@@ -61,6 +67,24 @@
 //!   Please refer to [the `rust-ctor` README][ctor-repo] for an up-to-date listing of platforms
 //!   that *do* support automatic registration.
 //!
+//! * **`checked-refs`**<br>
+//!   Debugging aid that turns misuse of `unsafe` object references into panics, reporting the
+//!   caller location. With this feature, [`Ref::assume_safe`](object::Ref::assume_safe),
+//!   `TRef` creation and the `unsafe` methods of the [`api`] types check the object's instance ID
+//!   before dereferencing, catching uses of freed objects even if their memory has been reused.
+//!   Implies `main-thread-checks`.
+//!
+//!   The API stays the same, but the checks have a significant run-time cost. It's intended to
+//!   be enabled in debug builds only.
+//!
+//! * **`main-thread-checks`**<br>
+//!   Debugging aid that detects access to nodes inside the scene tree from threads other than
+//!   the main thread, which Godot's [thread-safety guidelines][thread-safety] forbid. With this
+//!   feature, `Ref::assume_safe` and the methods of `Node` and its subclasses check the calling
+//!   thread, and warn or panic depending on [`init::MainThreadCheck`].
+//!
+//!   As with `checked-refs`, this has a run-time cost and is intended for debug builds.
+//!
 //! Bindings generation:
 //!
 //! * **`custom-godot`**<br>
@@ -124,3 +148,7 @@ pub use gdnative_bindings as api;
 #[doc(inline)]
 #[cfg(feature = "async")]
 pub use gdnative_async as tasks;
+
+#[doc(inline)]
+#[cfg(feature = "serde")]
+pub use gdnative_core::core_types::serde_format as serde;
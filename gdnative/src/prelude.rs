@@ -20,8 +20,8 @@ pub use gdnative_core::init::InitHandle;
 pub use gdnative_core::object::{
     memory::{ManuallyManaged, RefCounted},
     ownership::{Shared, ThreadLocal, Unique},
-    AsArg, GodotObject, Instance, Instanciable, NewRef, Null, QueueFree, Ref, SubClass, TInstance,
-    TRef,
+    AsArg, Base, GodotObject, Instance, Instanciable, NewRef, NodeRef, Null, OwnedNode, QueueFree,
+    Ref, SubClass, TInstance, TRef,
 };
 pub use gdnative_core::{godot_dbg, godot_error, godot_init, godot_print, godot_warn};
 pub use gdnative_derive::*;
@@ -30,7 +30,7 @@ pub use gdnative_derive::*;
 pub mod user_data {
     // Re-export selected user_data types, but keep qualified due to rather generic names
     pub use gdnative_core::export::user_data::{
-        Aether, ArcData, LocalCellData, MutexData, RwLockData,
+        Aether, ArcData, ArcSwapData, LocalCellData, MutexData, ReentrantCellData, RwLockData,
     };
 }
 #[doc(inline)]
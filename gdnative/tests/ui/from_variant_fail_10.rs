@@ -0,0 +1,18 @@
+use gdnative::prelude::*;
+
+#[derive(FromVariant)]
+pub struct Foo(#[variant(rename = "bar")] String, #[variant(default)] String);
+
+#[derive(FromVariant)]
+pub struct Bar {
+    #[variant(flatten, rename = "baz")]
+    baz: Baz,
+}
+
+#[derive(FromVariant)]
+pub struct Baz {
+    #[variant(rename = 42)]
+    quux: String,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use gdnative::prelude::*;
+
+#[derive(FromVariant)]
+pub struct Foo {
+    #[variant(coerce, from_variant_with = "from_variant")]
+    bar: f64,
+}
+
+fn from_variant(_variant: &Variant) -> Result<f64, FromVariantError> {
+    Ok(0.0)
+}
+
+fn main() {}
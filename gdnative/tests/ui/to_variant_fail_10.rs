@@ -0,0 +1,34 @@
+use gdnative::prelude::*;
+
+#[derive(ToVariant)]
+// Tagging should only be allowed on enums
+#[variant(tag = "type")]
+pub struct Foo {
+    bar: String,
+}
+
+#[derive(ToVariant)]
+#[variant(content = "data")]
+pub enum Bar {
+    A,
+}
+
+#[derive(ToVariant)]
+#[variant(untagged, tag = "type")]
+pub enum Baz {
+    A,
+}
+
+#[derive(ToVariant)]
+#[variant(tag = "type")]
+pub enum Quux {
+    A(String, String),
+}
+
+#[derive(ToVariant)]
+#[variant(rename_all = "Title Case")]
+pub struct Corge {
+    bar: String,
+}
+
+fn main() {}
@@ -44,4 +44,78 @@ pub struct Bar {
     owned: Owned,
 }
 
+fn default_level() -> i64 {
+    1
+}
+
+#[derive(ToVariant, FromVariant)]
+#[variant(rename_all = "camelCase")]
+pub struct Renamed {
+    player_name: String,
+    #[variant(rename = "HP")]
+    hit_points: i64,
+    #[variant(default = "default_level")]
+    level: i64,
+    #[variant(default)]
+    title: Option<String>,
+    #[variant(flatten)]
+    position: Position,
+}
+
+#[derive(ToVariant, FromVariant)]
+pub struct Position {
+    x: i64,
+    y: i64,
+}
+
+#[derive(ToVariant, FromVariant)]
+#[variant(tag = "type", rename_all = "snake_case")]
+pub enum Internal {
+    Ping,
+    MoveTo { x: i64, y: i64 },
+    Teleport(Position),
+    #[variant(rename = "say", rename_all = "UPPERCASE")]
+    Chat { text: String },
+}
+
+#[derive(ToVariant, FromVariant)]
+#[variant(tag = "type", content = "data")]
+pub enum Adjacent {
+    Ping,
+    Move(i64, i64),
+    Chat { text: String },
+}
+
+#[derive(ToVariant, FromVariant)]
+#[variant(untagged)]
+pub enum Untagged {
+    Nothing,
+    Number(i64),
+    Text(String),
+}
+
+#[derive(FromVariant)]
+#[variant(coerce)]
+pub struct Coerced<T> {
+    volume: f32,
+    target: NodePath,
+    points: Vec<f64>,
+    generic: T,
+    #[variant(from_variant_with = "from_variant_zero")]
+    with: i64,
+}
+
+fn from_variant_zero(_variant: &Variant) -> Result<i64, FromVariantError> {
+    Ok(0)
+}
+
+#[derive(FromVariant)]
+pub enum CoercedFields {
+    Move(#[variant(coerce)] f64, i64),
+    Chat {
+        #[variant(coerce)]
+        text: String,
+    },
+}
+
 fn main() {}
@@ -2,6 +2,10 @@ class_name Tests
 extends Node
 
 var gdn
+var _cancelled_result = "pending"
+
+signal stream_value(value)
+signal stream_done
 
 func run():
 	var version = Engine.get_version_info()
@@ -21,6 +25,13 @@ func run():
 		status = status && _test_generic_class()
 		status = status && _test_optional_args()
 		status = status && yield(_test_async_resume(), "completed")
+		status = status && yield(_test_async_timers(), "completed")
+		status = status && yield(_test_async_await_call(), "completed")
+		status = status && yield(_test_async_load(), "completed")
+		status = status && yield(_test_async_owned_task(), "completed")
+		status = status && yield(_test_async_signal_stream(), "completed")
+		status = status && yield(_test_run_on_main_thread(), "completed")
+		status = status && yield(_test_async_frame_executor(), "completed")
 
 		# Godot needs another frame to dispose the executor driver node. Otherwise the process
 		# aborts due to `_process` being called after `terminate` (`get_api` fail, not UB).
@@ -163,10 +174,320 @@ func _test_async_resume():
 
 	return status
 
+func _test_async_timers():
+	print(" -- _test_async_timers")
+
+	var driver_script = NativeScript.new()
+	driver_script.set_library(gdn.library)
+	driver_script.set_class_name("AsyncExecutorDriver")
+	var driver = driver_script.new()
+	add_child(driver)
+
+	var script = NativeScript.new()
+	script.set_library(gdn.library)
+	script.set_class_name("AsyncMethods")
+	var obj = script.new()
+
+	var status = true
+
+	var fn_state = obj.timed_add(41)
+	if !fn_state:
+		printerr("   !! _test_async_timers failed")
+		remove_child(driver)
+		driver.queue_free()
+		yield(get_tree(), "idle_frame")
+		return false
+
+	var result = yield(fn_state, "completed")
+
+	status = status && (result == 42)
+
+	if !status:
+		printerr("   !! _test_async_timers failed")
+
+	remove_child(driver)
+	driver.queue_free()
+
+	return status
+
+func _test_async_load():
+	print(" -- _test_async_load")
+
+	var driver_script = NativeScript.new()
+	driver_script.set_library(gdn.library)
+	driver_script.set_class_name("AsyncExecutorDriver")
+	var driver = driver_script.new()
+	add_child(driver)
+
+	var script = NativeScript.new()
+	script.set_library(gdn.library)
+	script.set_class_name("AsyncMethods")
+	var obj = script.new()
+
+	var status = true
+
+	var fn_state = obj.load_resources()
+	if !fn_state:
+		printerr("   !! _test_async_load failed")
+		remove_child(driver)
+		driver.queue_free()
+		yield(get_tree(), "idle_frame")
+		return false
+
+	var result = yield(fn_state, "completed")
+
+	status = status && (result == true)
+
+	if !status:
+		printerr("   !! _test_async_load failed")
+
+	remove_child(driver)
+	driver.queue_free()
+
+	return status
+
+func _test_async_owned_task():
+	print(" -- _test_async_owned_task")
+
+	var driver_script = NativeScript.new()
+	driver_script.set_library(gdn.library)
+	driver_script.set_class_name("AsyncExecutorDriver")
+	var driver = driver_script.new()
+	add_child(driver)
+
+	var script = NativeScript.new()
+	script.set_library(gdn.library)
+	script.set_class_name("AsyncOwnedTask")
+	var owner = script.new()
+	var probe = script.new()
+
+	var status = true
+
+	owner.start()
+	var fn_state = owner.wait_forever()
+	fn_state.connect("completed", self, "_on_cancelled_completed")
+	yield(get_tree(), "idle_frame")
+	status = status && !probe.is_task_dropped()
+
+	# Copies of the owner don't share its guard
+	var copy = owner.duplicate()
+	status = status && owner.get_meta_list().empty()
+
+	owner.free()
+	yield(get_tree(), "idle_frame")
+	yield(get_tree(), "idle_frame")
+	status = status && probe.is_task_dropped()
+
+	# Cancelled async methods complete with null
+	status = status && _cancelled_result == null
+
+	if !status:
+		printerr("   !! _test_async_owned_task failed")
+
+	copy.free()
+	probe.free()
+	remove_child(driver)
+	driver.queue_free()
+
+	return status
+
+func _on_cancelled_completed(result):
+	_cancelled_result = result
+
+func _test_async_signal_stream():
+	print(" -- _test_async_signal_stream")
+
+	var driver_script = NativeScript.new()
+	driver_script.set_library(gdn.library)
+	driver_script.set_class_name("AsyncExecutorDriver")
+	var driver = driver_script.new()
+	add_child(driver)
+
+	var script = NativeScript.new()
+	script.set_library(gdn.library)
+	script.set_class_name("AsyncMethods")
+	var obj = script.new()
+
+	var status = true
+
+	var fn_state = obj.sum_signals(self)
+	if !fn_state:
+		printerr("   !! _test_async_signal_stream failed")
+		remove_child(driver)
+		driver.queue_free()
+		yield(get_tree(), "idle_frame")
+		return false
+
+	# Streams don't leave any state on the source that could be saved or duplicated
+	status = status && get_meta_list().empty()
+
+	emit_signal("stream_value", 1)
+	emit_signal("stream_value", 2)
+	yield(get_tree(), "idle_frame")
+	emit_signal("stream_value", 39)
+	emit_signal("stream_done")
+
+	var result = yield(fn_state, "completed")
+
+	status = status && (result == 42)
+
+	# Streams end when the source is freed
+	var source = Node.new()
+	source.add_user_signal("stream_value")
+	fn_state = obj.count_until_freed(source)
+	source.emit_signal("stream_value", 1)
+	source.emit_signal("stream_value", 2)
+	source.free()
+
+	result = yield(fn_state, "completed")
+
+	status = status && (result == 2)
+
+	if !status:
+		printerr("   !! _test_async_signal_stream failed")
+
+	remove_child(driver)
+	driver.queue_free()
+
+	return status
+
+func _test_run_on_main_thread():
+	print(" -- _test_run_on_main_thread")
+
+	var script = NativeScript.new()
+	script.set_library(gdn.library)
+	script.set_class_name("AsyncDispatch")
+	var obj = script.new()
+	var freed = Node.new()
+
+	obj.dispatch_from_thread(freed)
+	freed.free()
+
+	var status = !obj.is_dispatched()
+
+	yield(get_tree(), "idle_frame")
+	yield(get_tree(), "idle_frame")
+
+	status = status && obj.is_dispatched()
+	status = status && obj.name == "Dispatched"
+
+	if !status:
+		printerr("   !! _test_run_on_main_thread failed")
+
+	obj.free()
+
+	return status
+
+func _test_async_frame_executor():
+	print(" -- _test_async_frame_executor")
+
+	var script = NativeScript.new()
+	script.set_library(gdn.library)
+	script.set_class_name("AsyncFrameExecutor")
+	var obj = script.new()
+
+	var status = true
+	var finished = obj.finished()
+	var pending = obj.pending()
+	var completed = obj.completed()
+
+	# Tasks are polled once per frame until they complete
+	obj.spawn_yielding(3)
+	status = status && obj.pending() == pending + 1
+	yield(get_tree(), "idle_frame")
+	status = status && obj.finished() == finished
+	yield(_wait_frames(4), "completed")
+	status = status && obj.finished() == finished + 1
+	status = status && obj.pending() == pending
+	status = status && obj.completed() == completed + 1
+
+	# Panicking tasks are dropped without affecting other tasks
+	obj.spawn_panicking()
+	obj.spawn_yielding(1)
+	yield(_wait_frames(3), "completed")
+	status = status && obj.finished() == finished + 2
+	status = status && obj.pending() == pending
+	status = status && obj.completed() == completed + 3
+
+	# Pending tasks resume once the next spawn reconnects a dropped pump
+	obj.spawn_yielding(1)
+	_disconnect_executor_pump()
+	yield(_wait_frames(3), "completed")
+	status = status && obj.finished() == finished + 2
+	status = status && obj.pending() == pending + 1
+	obj.spawn_yielding(1)
+	yield(_wait_frames(3), "completed")
+	status = status && obj.finished() == finished + 4
+	status = status && obj.pending() == pending
+
+	if !status:
+		printerr("   !! _test_async_frame_executor failed")
+
+	return status
+
+func _wait_frames(count):
+	for _i in range(count):
+		yield(get_tree(), "idle_frame")
+
+func _disconnect_executor_pump():
+	for signal_name in ["idle_frame", "physics_frame"]:
+		for connection in get_tree().get_signal_connection_list(signal_name):
+			if connection["method"] == "_pump":
+				get_tree().disconnect(signal_name, connection["target"], "_pump")
+
 func _get_async_number():
 	yield(get_tree().create_timer(0.1), "timeout")
 	return 39
 
+func _get_sync_number():
+	return 3
+
+func _get_idle_frame_number():
+	yield(get_tree(), "idle_frame")
+	return 39
+
+func _test_async_await_call():
+	print(" -- _test_async_await_call")
+
+	var driver_script = NativeScript.new()
+	driver_script.set_library(gdn.library)
+	driver_script.set_class_name("AsyncExecutorDriver")
+	var driver = driver_script.new()
+	add_child(driver)
+
+	var script = NativeScript.new()
+	script.set_library(gdn.library)
+	script.set_class_name("AsyncMethods")
+	var obj = script.new()
+
+	var status = true
+
+	var fn_state = obj.await_gdscript(self)
+	if !fn_state:
+		printerr("   !! _test_async_await_call failed")
+		remove_child(driver)
+		driver.queue_free()
+		yield(get_tree(), "idle_frame")
+		return false
+
+	var result = yield(fn_state, "completed")
+
+	status = status && (result == 42)
+
+	# The coroutine completes before the call is awaited
+	fn_state = obj.await_gdscript_late(self)
+	result = yield(fn_state, "completed")
+
+	status = status && (result == 42)
+
+	if !status:
+		printerr("   !! _test_async_await_call failed")
+
+	remove_child(driver)
+	driver.queue_free()
+
+	return status
+
 func _test_generic_class():
 	print(" -- _test_generic_class")
 
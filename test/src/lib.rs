@@ -4,20 +4,34 @@
 use gdnative::prelude::*;
 use gdnative_core::godot_itest;
 
+mod test_arc_swap_data;
 mod test_as_arg;
 mod test_async;
+mod test_base_field;
+#[cfg(feature = "checked-refs")]
+mod test_checked_refs;
 mod test_constructor;
 mod test_derive;
 mod test_free_ub;
 mod test_generic_class;
 mod test_indexed_props;
+mod test_instance_borrow;
+mod test_lock_diagnostics;
 mod test_map_owned;
+mod test_marshal;
+mod test_node_ref;
+mod test_owned_node;
+mod test_panic_policy;
+mod test_reentrant;
 mod test_register;
 mod test_return_leak;
 mod test_serde;
+mod test_text;
+mod test_thread_affinity;
 mod test_vararray_return;
 mod test_variant_call_args;
 mod test_variant_ops;
+mod test_weak;
 
 #[no_mangle]
 pub extern "C" fn run_tests(
@@ -49,6 +63,11 @@ pub extern "C" fn run_tests(
     status &= gdnative::core_types::test_variant_hash_map();
     status &= gdnative::core_types::test_variant_hash_set();
     status &= gdnative::core_types::test_variant_vec();
+    status &= gdnative::core_types::test_variant_btree();
+    status &= gdnative::core_types::test_variant_index_map();
+    status &= gdnative::core_types::test_variant_std_sequences();
+    status &= gdnative::core_types::test_variant_std_wrappers();
+    status &= gdnative::core_types::test_variant_try_coerce();
     status &= gdnative::core_types::test_to_variant_iter();
     status &= gdnative::core_types::test_variant_tuple();
     status &= gdnative::core_types::test_variant_dispatch();
@@ -67,20 +86,36 @@ pub extern "C" fn run_tests(
     status &= test_rust_class_construction();
     status &= test_underscore_method_binding();
 
+    status &= test_arc_swap_data::run_tests();
     status &= test_as_arg::run_tests();
     status &= test_async::run_tests();
+    status &= test_base_field::run_tests();
+    #[cfg(feature = "checked-refs")]
+    {
+        status &= test_checked_refs::run_tests();
+    }
     status &= test_constructor::run_tests();
     status &= test_derive::run_tests();
     status &= test_free_ub::run_tests();
     status &= test_generic_class::run_tests();
     status &= test_indexed_props::run_tests();
+    status &= test_instance_borrow::run_tests();
+    status &= test_lock_diagnostics::run_tests();
     status &= test_map_owned::run_tests();
+    status &= test_marshal::run_tests();
+    status &= test_node_ref::run_tests();
+    status &= test_owned_node::run_tests();
+    status &= test_panic_policy::run_tests();
+    status &= test_reentrant::run_tests();
     status &= test_register::run_tests();
     status &= test_return_leak::run_tests();
     status &= test_serde::run_tests();
+    status &= test_text::run_tests();
+    status &= test_thread_affinity::run_tests();
     status &= test_vararray_return::run_tests();
     status &= test_variant_call_args::run_tests();
     status &= test_variant_ops::run_tests();
+    status &= test_weak::run_tests();
 
     Variant::new(status).leak()
 }
@@ -253,19 +288,29 @@ fn init(handle: InitHandle) {
 }
 
 fn delegate_init(handle: InitHandle) {
+    test_arc_swap_data::register(handle);
     test_as_arg::register(handle);
     test_async::register(handle);
+    test_base_field::register(handle);
     test_constructor::register(handle);
     test_derive::register(handle);
     test_free_ub::register(handle);
     test_generic_class::register(handle);
     test_indexed_props::register(handle);
+    test_instance_borrow::register(handle);
+    test_lock_diagnostics::register(handle);
     test_map_owned::register(handle);
+    test_marshal::register(handle);
+    test_node_ref::register(handle);
+    test_panic_policy::register(handle);
+    test_reentrant::register(handle);
     test_register::register(handle);
     test_return_leak::register(handle);
+    test_text::register(handle);
     test_vararray_return::register(handle);
     test_variant_call_args::register(handle);
     test_variant_ops::register(handle);
+    test_weak::register(handle);
 }
 
 fn terminate(_term_info: &gdnative::init::TerminateInfo) {
@@ -0,0 +1,125 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use gdnative::export::user_data::{ArcSwapData, Map, MapMut};
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_arc_swap_data_reentrant();
+    status &= test_arc_swap_data_concurrent_readers();
+    status &= test_arc_swap_data_concurrent_writers();
+
+    status
+}
+
+pub(crate) fn register(handle: InitHandle) {
+    handle.add_class::<ArcSwapCounter>();
+}
+
+#[derive(NativeClass, Clone, Default)]
+#[inherit(Reference)]
+#[user_data(ArcSwapData<Self>)]
+#[no_constructor]
+struct ArcSwapCounter {
+    value: i64,
+}
+
+#[methods]
+impl ArcSwapCounter {
+    #[method]
+    fn outer(&mut self, #[base] base: TRef<Reference>) -> bool {
+        self.value += 1;
+        let ret = unsafe { base.call("increment", &[]) };
+        ret.is_nil()
+    }
+
+    #[method]
+    fn increment(&mut self) -> i64 {
+        self.value += 1;
+        self.value
+    }
+}
+
+crate::godot_itest! { test_arc_swap_data_reentrant {
+    let instance = ArcSwapCounter::default().emplace();
+    let base = instance.base();
+
+    assert_eq!(Some(1), unsafe { base.call("increment", &[]).to::<i64>() });
+
+    // The inner call fails instead of deadlocking, and the outer call still completes.
+    assert_eq!(Some(true), unsafe { base.call("outer", &[]).to::<bool>() });
+    assert_eq!(2, instance.map(|counter, _| counter.value).unwrap());
+
+    let script = instance.script();
+    let nested = script.map_mut(|_| script.map_mut(|counter| counter.value += 1));
+    assert!(nested.unwrap().is_err());
+    assert_eq!(2, script.map(|counter| counter.value).unwrap());
+
+    // The writer is released after a failed nested update.
+    script.map_mut(|counter| counter.value += 1).unwrap();
+    assert_eq!(3, script.map(|counter| counter.value).unwrap());
+}}
+
+crate::godot_itest! { test_arc_swap_data_concurrent_readers {
+    const WRITES: i64 = 1000;
+
+    let instance = ArcSwapCounter::default().emplace();
+    let script = instance.script().clone();
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let readers = (0..4)
+        .map(|_| {
+            let script = script.clone();
+            let stop = Arc::clone(&stop);
+            thread::spawn(move || {
+                let mut last = 0;
+                while !stop.load(Ordering::Acquire) {
+                    let value = script.map(|counter| counter.value).unwrap();
+                    assert!(value >= last, "readers should never see older versions");
+                    last = value;
+                }
+                last
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for _ in 0..WRITES {
+        script.map_mut(|counter| counter.value += 1).unwrap();
+    }
+    stop.store(true, Ordering::Release);
+
+    for reader in readers {
+        assert!(reader.join().expect("reader should not panic") <= WRITES);
+    }
+    assert_eq!(WRITES, script.map(|counter| counter.value).unwrap());
+}}
+
+crate::godot_itest! { test_arc_swap_data_concurrent_writers {
+    const THREADS: i64 = 4;
+    const WRITES: i64 = 1000;
+
+    let instance = ArcSwapCounter::default().emplace();
+    let script = instance.script().clone();
+
+    // Writers on different threads take turns instead of failing, so no updates are lost.
+    let writers = (0..THREADS)
+        .map(|_| {
+            let script = script.clone();
+            thread::spawn(move || {
+                for _ in 0..WRITES {
+                    script
+                        .map_mut(|counter| counter.value += 1)
+                        .expect("writers on other threads should block, not fail");
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for writer in writers {
+        writer.join().expect("writer should not panic");
+    }
+    assert_eq!(THREADS * WRITES, script.map(|counter| counter.value).unwrap());
+}}
@@ -1,6 +1,12 @@
-use std::{cell::RefCell, sync::Arc};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::{cell::RefCell, sync::Arc, time::Duration};
 
-use gdnative::{prelude::*, tasks::Context};
+use futures::future::{select, Either};
+use futures::StreamExt;
+use gdnative::{
+    prelude::*,
+    tasks::{Context, LoadError},
+};
 
 pub(crate) fn run_tests() -> bool {
     // Relevant tests in GDScript
@@ -20,6 +26,9 @@ pub(crate) fn register(handle: InitHandle) {
 
     handle.add_class::<AsyncMethods>();
     handle.add_class::<AsyncExecutorDriver>();
+    handle.add_class::<AsyncOwnedTask>();
+    handle.add_class::<AsyncDispatch>();
+    handle.add_class::<AsyncFrameExecutor>();
 }
 
 #[cfg(feature = "no-manual-register")]
@@ -92,4 +101,283 @@ impl AsyncMethods {
             a + b + c
         }
     }
+
+    #[method(async)]
+    fn sum_signals(
+        &self,
+        #[async_ctx] ctx: Arc<Context>,
+        obj: Ref<Object>,
+    ) -> impl std::future::Future<Output = i64> + 'static {
+        let obj = unsafe { obj.assume_safe() };
+        let mut values = ctx.signal_stream::<(i64,)>(obj, "stream_value").unwrap();
+        let mut done = ctx.signal_stream::<()>(obj, "stream_done").unwrap();
+
+        async move {
+            let mut sum = 0;
+            while let Either::Left((Some((value,)), _)) = select(values.next(), done.next()).await {
+                sum += value;
+            }
+            sum
+        }
+    }
+
+    #[method(async)]
+    fn count_until_freed(
+        &self,
+        #[async_ctx] ctx: Arc<Context>,
+        obj: Ref<Object>,
+    ) -> impl std::future::Future<Output = i64> + 'static {
+        let obj = unsafe { obj.assume_safe() };
+        let values = ctx.signal_stream::<(i64,)>(obj, "stream_value").unwrap();
+
+        // The stream ends when `obj` is freed.
+        async move { values.count().await as i64 }
+    }
+
+    #[method(async)]
+    fn load_resources(
+        &self,
+        #[async_ctx] ctx: Arc<Context>,
+    ) -> impl std::future::Future<Output = bool> + 'static {
+        async move {
+            let progress = std::rc::Rc::new(RefCell::new(Vec::new()));
+            let scene = ctx
+                .load_with_progress::<PackedScene, _>("res://Scene.tscn", {
+                    let progress = std::rc::Rc::clone(&progress);
+                    move |p| progress.borrow_mut().push(p)
+                })
+                .await;
+            assert!(scene.is_ok(), "scene should load: {:?}", scene.err());
+
+            {
+                let progress = progress.borrow();
+                assert!(
+                    progress.windows(2).all(|w| w[0] <= w[1]),
+                    "progress should be monotonic: {progress:?}"
+                );
+                assert_eq!(Some(&1.0), progress.last(), "progress should end at 1.0");
+            }
+
+            let missing = ctx.load::<PackedScene>("res://does_not_exist.tscn").await;
+            assert!(matches!(missing, Err(LoadError::Open { .. })));
+
+            let wrong_type = ctx.load::<Texture>("res://Scene.tscn").await;
+            assert!(
+                matches!(wrong_type, Err(LoadError::WrongType { .. })),
+                "expected WrongType: {:?}",
+                wrong_type.err()
+            );
+
+            true
+        }
+    }
+
+    #[method(async)]
+    fn await_gdscript(
+        &self,
+        #[async_ctx] ctx: Arc<Context>,
+        obj: Ref<Object>,
+    ) -> impl std::future::Future<Output = i32> + 'static {
+        let obj = unsafe { obj.assume_safe() };
+        let yielding = unsafe { ctx.await_call(obj, "_get_async_number", &[]) };
+        let immediate = unsafe { ctx.await_call(obj, "_get_sync_number", &[]) };
+
+        async move {
+            let a = i32::from_variant(&yielding.await).unwrap();
+            let b = i32::from_variant(&immediate.await).unwrap();
+            a + b
+        }
+    }
+
+    #[method(async)]
+    fn await_gdscript_late(
+        &self,
+        #[async_ctx] ctx: Arc<Context>,
+        obj: Ref<Object>,
+    ) -> impl std::future::Future<Output = i32> + 'static {
+        let obj = unsafe { obj.assume_safe() };
+        let call = unsafe { ctx.await_call(obj, "_get_idle_frame_number", &[]) };
+
+        async move {
+            // The coroutine completes on the next idle frame, before the timer does.
+            ctx.sleep(0.1).await;
+            i32::from_variant(&call.await).unwrap() + 3
+        }
+    }
+
+    #[method(async)]
+    fn timed_add(
+        &self,
+        #[async_ctx] ctx: Arc<Context>,
+        a: i32,
+    ) -> impl std::future::Future<Output = i32> + 'static {
+        async move {
+            ctx.sleep(0.05).await;
+            ctx.next_idle_frame().await;
+            ctx.next_physics_frame().await;
+
+            let never_resumed = ctx.timeout(Duration::from_millis(50), ctx.until_resume());
+            assert!(never_resumed.await.is_err());
+
+            let ready = ctx.timeout(Duration::from_secs(5), async { 1 });
+            a + ready.await.unwrap()
+        }
+    }
+}
+
+static OWNED_TASK_DROPPED: AtomicBool = AtomicBool::new(false);
+
+struct SetOnDrop(&'static AtomicBool);
+
+impl Drop for SetOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Release);
+    }
+}
+
+#[derive(NativeClass)]
+#[inherit(Node)]
+struct AsyncOwnedTask;
+
+#[methods]
+impl AsyncOwnedTask {
+    fn new(_owner: &Node) -> Self {
+        AsyncOwnedTask
+    }
+
+    #[method]
+    fn start(&self, #[base] base: TRef<Node>) {
+        OWNED_TASK_DROPPED.store(false, Ordering::Release);
+        let flag = SetOnDrop(&OWNED_TASK_DROPPED);
+
+        gdnative::tasks::spawn_local_for(base, async move {
+            let _flag = flag;
+            futures::future::pending::<()>().await;
+        })
+        .unwrap();
+    }
+
+    #[method(async)]
+    fn wait_forever(
+        &self,
+        #[async_ctx] _ctx: Arc<Context>,
+    ) -> impl std::future::Future<Output = i32> + 'static {
+        futures::future::pending()
+    }
+
+    #[method]
+    fn is_task_dropped(&self) -> bool {
+        OWNED_TASK_DROPPED.load(Ordering::Acquire)
+    }
+}
+
+static DISPATCHED: AtomicBool = AtomicBool::new(false);
+
+#[derive(NativeClass)]
+#[inherit(Node)]
+struct AsyncDispatch;
+
+#[methods]
+impl AsyncDispatch {
+    fn new(_owner: &Node) -> Self {
+        AsyncDispatch
+    }
+
+    #[method]
+    fn dispatch_from_thread(&self, #[base] base: TRef<Node>, freed: Ref<Node>) {
+        DISPATCHED.store(false, Ordering::Release);
+
+        let base = base.downgrade();
+        let freed = unsafe { freed.assume_safe() }.downgrade();
+
+        std::thread::spawn(move || {
+            gdnative::tasks::defer_for(freed, |_| {
+                panic!("closure for freed object should be skipped")
+            });
+            gdnative::tasks::defer_for(base, |base| base.set_name("Dispatched"));
+            gdnative::tasks::run_on_main_thread(|| panic!("closure panicked on purpose"));
+            gdnative::tasks::run_on_main_thread(|| DISPATCHED.store(true, Ordering::Release));
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[method]
+    fn is_dispatched(&self) -> bool {
+        DISPATCHED.load(Ordering::Acquire)
+    }
+}
+
+/// Future that wakes itself and returns `Pending` the given number of times.
+struct YieldTimes(u32);
+
+impl std::future::Future for YieldTimes {
+    type Output = ();
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<()> {
+        if self.0 == 0 {
+            return std::task::Poll::Ready(());
+        }
+
+        self.0 -= 1;
+        cx.waker().wake_by_ref();
+        std::task::Poll::Pending
+    }
+}
+
+thread_local! {
+    static FRAME_TASKS_FINISHED: std::cell::Cell<u32> = std::cell::Cell::new(0);
+}
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+struct AsyncFrameExecutor;
+
+#[methods]
+impl AsyncFrameExecutor {
+    fn new(_owner: &Reference) -> Self {
+        AsyncFrameExecutor
+    }
+
+    #[method]
+    fn spawn_yielding(&self, times: u32) {
+        use futures::task::LocalSpawnExt;
+
+        gdnative::tasks::builtin_executor()
+            .spawn_local(async move {
+                YieldTimes(times).await;
+                FRAME_TASKS_FINISHED.with(|finished| finished.set(finished.get() + 1));
+            })
+            .unwrap();
+    }
+
+    #[method]
+    fn spawn_panicking(&self) {
+        use futures::task::LocalSpawnExt;
+
+        gdnative::tasks::builtin_executor()
+            .spawn_local(async {
+                YieldTimes(1).await;
+                panic!("task panicked on purpose");
+            })
+            .unwrap();
+    }
+
+    #[method]
+    fn finished(&self) -> u32 {
+        FRAME_TASKS_FINISHED.with(std::cell::Cell::get)
+    }
+
+    #[method]
+    fn pending(&self) -> usize {
+        gdnative::tasks::executor_stats().pending
+    }
+
+    #[method]
+    fn completed(&self) -> u64 {
+        gdnative::tasks::executor_stats().completed
+    }
 }
@@ -0,0 +1,89 @@
+use gdnative::export::user_data::Map;
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_base_field_constructor();
+    status &= test_base_field_emplace();
+    status &= test_base_field_freed();
+
+    status
+}
+
+pub(crate) fn register(handle: InitHandle) {
+    handle.add_class::<BaseFieldNode>();
+}
+
+#[derive(NativeClass)]
+#[inherit(Node)]
+struct BaseFieldNode {
+    #[base]
+    base: Base<Node>,
+    attached_in_new: bool,
+}
+
+#[methods]
+impl BaseFieldNode {
+    fn new(_base: &Node) -> Self {
+        let base = Base::new();
+        let attached_in_new = base.is_attached();
+        BaseFieldNode {
+            base,
+            attached_in_new,
+        }
+    }
+
+    #[method]
+    fn rename(&self, name: String) {
+        self.set_base_name(&name);
+    }
+
+    fn set_base_name(&self, name: &str) {
+        self.base().set_name(name);
+    }
+}
+
+crate::godot_itest! { test_base_field_constructor {
+    let instance = BaseFieldNode::new_instance();
+
+    unsafe { instance.base().call("rename", &["constructed".to_variant()]) };
+    assert_eq!(instance.base().name().to_string(), "constructed");
+
+    instance
+        .map(|script, base| {
+            assert!(!script.attached_in_new);
+            assert_eq!(
+                script.base().get_instance_id(),
+                base.get_instance_id(),
+            );
+        })
+        .unwrap();
+
+    instance.into_base().free();
+}}
+
+crate::godot_itest! { test_base_field_emplace {
+    let instance = BaseFieldNode {
+        base: Base::new(),
+        attached_in_new: false,
+    }
+    .emplace();
+
+    instance
+        .map(|script, _| script.set_base_name("emplaced"))
+        .unwrap();
+    assert_eq!(instance.base().name().to_string(), "emplaced");
+
+    instance.into_base().free();
+}}
+
+crate::godot_itest! { test_base_field_freed {
+    let instance = BaseFieldNode::new_instance();
+    let (base, script) = instance.decouple();
+
+    base.free();
+    script
+        .map(|script| assert!(!script.base.is_attached(), "freed base should not be accessible"))
+        .unwrap();
+}}
@@ -0,0 +1,48 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_checked_refs_assume_safe_freed();
+    status &= test_checked_refs_method_call_freed();
+
+    status
+}
+
+/// Runs `f`, expecting it to panic with a message reporting a freed object.
+fn expect_freed_panic(f: impl FnOnce()) {
+    let payload = catch_unwind(AssertUnwindSafe(f)).expect_err("access should panic");
+    let msg = payload
+        .downcast_ref::<String>()
+        .map(String::as_str)
+        .or_else(|| payload.downcast_ref::<&str>().copied())
+        .unwrap_or_default();
+
+    assert!(msg.contains("on a freed object of class Node"), "{msg}");
+    assert!(
+        msg.contains(file!()),
+        "panic should report the caller: {msg}"
+    );
+}
+
+crate::godot_itest! { test_checked_refs_assume_safe_freed {
+    let node = Node::new().into_shared();
+    let freed = node.clone();
+    unsafe { node.assume_unique().free() };
+
+    expect_freed_panic(|| {
+        let _ = unsafe { freed.assume_safe() };
+    });
+}}
+
+crate::godot_itest! { test_checked_refs_method_call_freed {
+    let node = Node::new().into_shared();
+    let node_ref = unsafe { node.assume_safe() };
+    unsafe { node.clone().assume_unique().free() };
+
+    expect_freed_panic(|| {
+        unsafe { node_ref.call("get_name", &[]) };
+    });
+}}
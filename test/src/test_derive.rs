@@ -11,6 +11,9 @@ pub(crate) fn run_tests() -> bool {
     status &= test_derive_to_variant();
     status &= test_derive_to_variant_repr();
     status &= test_derive_to_variant_str();
+    status &= test_derive_to_variant_rename();
+    status &= test_derive_to_variant_tagged();
+    status &= test_derive_from_variant_coerce();
     status &= test_derive_owned_to_variant();
     status &= test_derive_nativeclass();
     status &= test_derive_nativeclass_without_constructor();
@@ -271,6 +274,300 @@ crate::godot_itest! { test_derive_to_variant_str {
 
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 
+crate::godot_itest! { test_derive_to_variant_rename {
+    fn default_speed() -> f64 {
+        2.5
+    }
+
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    #[variant(rename_all = "camelCase")]
+    struct Save {
+        player_name: String,
+        #[variant(rename = "HP")]
+        hit_points: i64,
+        #[variant(default)]
+        level: i64,
+        #[variant(default = "default_speed")]
+        move_speed: f64,
+        #[variant(flatten)]
+        position: Position,
+    }
+
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    struct Position {
+        x: i64,
+        y: i64,
+    }
+
+    let data = Save {
+        player_name: "foo".into(),
+        hit_points: 42,
+        level: 3,
+        move_speed: 1.0,
+        position: Position { x: 1, y: 2 },
+    };
+
+    let variant = data.to_variant();
+    let dictionary = variant.to::<Dictionary>().expect("should be dictionary");
+    assert_eq!(Some("foo".into()), dictionary.get("playerName").and_then(|v| v.to::<String>()));
+    assert_eq!(Some(42), dictionary.get("HP").and_then(|v| v.to::<i64>()));
+    assert_eq!(Some(3), dictionary.get("level").and_then(|v| v.to::<i64>()));
+    assert_eq!(Some(1), dictionary.get("x").and_then(|v| v.to::<i64>()));
+    assert_eq!(Some(2), dictionary.get("y").and_then(|v| v.to::<i64>()));
+    assert!(!dictionary.contains("position"));
+    assert_eq!(Ok(data), Save::from_variant(&variant));
+
+    let dictionary = Dictionary::new();
+    dictionary.insert("playerName", "bar");
+    dictionary.insert("HP", 7);
+    dictionary.insert("x", 5);
+    dictionary.insert("y", 6);
+    assert_eq!(
+        Ok(Save {
+            player_name: "bar".into(),
+            hit_points: 7,
+            level: 0,
+            move_speed: 2.5,
+            position: Position { x: 5, y: 6 },
+        }),
+        Save::from_variant(&dictionary.duplicate().into_shared().to_variant())
+    );
+
+    dictionary.erase("HP");
+    assert_eq!(
+        Err(FromVariantError::InvalidField {
+            field_name: "HP",
+            error: Box::new(FromVariantError::InvalidVariantType {
+                variant_type: VariantType::Nil,
+                expected: VariantType::I64,
+            }),
+        }),
+        Save::from_variant(&dictionary.into_shared().to_variant())
+    );
+
+    let dictionary = Dictionary::new();
+    dictionary.insert("playerName", "bar");
+    dictionary.insert("HP", 7);
+    dictionary.insert("x", 5);
+    assert_eq!(
+        Err(FromVariantError::InvalidField {
+            field_name: "position",
+            error: Box::new(FromVariantError::InvalidField {
+                field_name: "y",
+                error: Box::new(FromVariantError::InvalidVariantType {
+                    variant_type: VariantType::Nil,
+                    expected: VariantType::I64,
+                }),
+            }),
+        }),
+        Save::from_variant(&dictionary.into_shared().to_variant())
+    );
+
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    struct Spawn {
+        name: String,
+        #[variant(flatten)]
+        position: Option<Position>,
+    }
+
+    for data in [
+        Spawn { name: "foo".into(), position: None },
+        Spawn { name: "bar".into(), position: Some(Position { x: 1, y: 2 }) },
+    ] {
+        assert_eq!(Ok(data.clone()), Spawn::from_variant(&data.to_variant()));
+    }
+
+    let variant = Spawn { name: "foo".into(), position: None }.to_variant();
+    let dictionary = variant.to::<Dictionary>().expect("should be dictionary");
+    assert_eq!(1, dictionary.len());
+
+    let dictionary = Dictionary::new();
+    dictionary.insert("name", "bar");
+    dictionary.insert("x", "not a number");
+    dictionary.insert("y", 2);
+    assert_eq!(
+        Err(FromVariantError::InvalidField {
+            field_name: "position",
+            error: Box::new(FromVariantError::InvalidField {
+                field_name: "x",
+                error: Box::new(FromVariantError::InvalidVariantType {
+                    variant_type: VariantType::GodotString,
+                    expected: VariantType::I64,
+                }),
+            }),
+        }),
+        Spawn::from_variant(&dictionary.into_shared().to_variant())
+    );
+}}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+crate::godot_itest! { test_derive_to_variant_tagged {
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    #[variant(tag = "type", rename_all = "snake_case")]
+    enum Internal {
+        Ping,
+        MoveTo { x: i64, y: i64 },
+        #[variant(rename = "say")]
+        Chat { text: String },
+    }
+
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    #[variant(tag = "t", content = "c")]
+    enum Adjacent {
+        Ping,
+        Move(i64, i64),
+    }
+
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    #[variant(untagged)]
+    enum Untagged {
+        Nothing,
+        Number(i64),
+        Text(String),
+    }
+
+    let variant = Internal::MoveTo { x: 1, y: 2 }.to_variant();
+    let dictionary = variant.to::<Dictionary>().expect("should be dictionary");
+    assert_eq!(Some("move_to".into()), dictionary.get("type").and_then(|v| v.to::<String>()));
+    assert_eq!(Some(1), dictionary.get("x").and_then(|v| v.to::<i64>()));
+    assert_eq!(Some(2), dictionary.get("y").and_then(|v| v.to::<i64>()));
+
+    for data in [
+        Internal::Ping,
+        Internal::MoveTo { x: 1, y: 2 },
+        Internal::Chat { text: "hello".into() },
+    ] {
+        assert_eq!(Ok(data.clone()), Internal::from_variant(&data.to_variant()));
+    }
+
+    let dictionary = Dictionary::new();
+    dictionary.insert("type", "say");
+    dictionary.insert("text", "hi");
+    assert_eq!(
+        Ok(Internal::Chat { text: "hi".into() }),
+        Internal::from_variant(&dictionary.into_shared().to_variant())
+    );
+
+    let dictionary = Dictionary::new();
+    dictionary.insert("type", "unknown");
+    assert_eq!(
+        Err(FromVariantError::UnknownEnumVariant {
+            variant: "unknown".into(),
+            expected: &["ping", "move_to", "say"],
+        }),
+        Internal::from_variant(&dictionary.into_shared().to_variant())
+    );
+
+    let variant = Adjacent::Move(3, 4).to_variant();
+    let dictionary = variant.to::<Dictionary>().expect("should be dictionary");
+    assert_eq!(Some("Move".into()), dictionary.get("t").and_then(|v| v.to::<String>()));
+    assert_eq!(Some(vec![3, 4]), dictionary.get("c").and_then(|v| v.to::<Vec<i64>>()));
+    assert_eq!(Ok(Adjacent::Move(3, 4)), Adjacent::from_variant(&variant));
+
+    let variant = Adjacent::Ping.to_variant();
+    let dictionary = variant.to::<Dictionary>().expect("should be dictionary");
+    assert!(!dictionary.contains("c"));
+    assert_eq!(Ok(Adjacent::Ping), Adjacent::from_variant(&variant));
+
+    assert!(Untagged::Nothing.to_variant().is_nil());
+    assert_eq!(Some(42), Untagged::Number(42).to_variant().to::<i64>());
+    assert_eq!(Ok(Untagged::Nothing), Untagged::from_variant(&Variant::nil()));
+    assert_eq!(Ok(Untagged::Number(42)), Untagged::from_variant(&42.to_variant()));
+    assert_eq!(Ok(Untagged::Text("foo".into())), Untagged::from_variant(&"foo".to_variant()));
+    assert!(Untagged::from_variant(&1.5f64.to_variant()).is_err());
+
+    #[derive(Clone, PartialEq, Debug, ToVariant)]
+    #[variant(tag = "type")]
+    enum Newtype {
+        Number(i64),
+    }
+
+    let variant = Newtype::Number(42).to_variant();
+    let dictionary = variant.to::<Dictionary>().expect("should be dictionary");
+    assert_eq!(1, dictionary.len());
+    assert_eq!(Some("Number".into()), dictionary.get("type").and_then(|v| v.to::<String>()));
+}}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+crate::godot_itest! { test_derive_from_variant_coerce {
+    #[derive(Clone, PartialEq, Debug, FromVariant)]
+    #[variant(coerce)]
+    struct Settings {
+        volume: f32,
+        target: NodePath,
+        name: String,
+        waypoints: PoolArray<Vector3>,
+        spawn: Spawn,
+    }
+
+    #[derive(Clone, PartialEq, Debug, FromVariant)]
+    struct Spawn {
+        #[variant(coerce)]
+        delay: f64,
+        position: Vector3,
+    }
+
+    let points = VariantArray::new();
+    points.push(Vector3::new(1.0, 2.0, 3.0));
+
+    let spawn = Dictionary::new();
+    spawn.insert("delay", 2);
+    spawn.insert("position", Vector3::new(4.0, 5.0, 6.0));
+
+    let dictionary = Dictionary::new();
+    dictionary.insert("volume", 1);
+    dictionary.insert("target", "foo/bar");
+    dictionary.insert("name", 42);
+    dictionary.insert("waypoints", points.into_shared());
+    dictionary.insert("spawn", spawn.duplicate().into_shared());
+
+    let settings = Settings::from_variant(&dictionary.duplicate().into_shared().to_variant())
+        .expect("should coerce");
+    assert_eq!(1.0, settings.volume);
+    assert_eq!("foo/bar", settings.target.to_string());
+    assert_eq!("42", settings.name);
+    assert_eq!(1, settings.waypoints.len());
+    assert_eq!(Vector3::new(1.0, 2.0, 3.0), settings.waypoints.get(0));
+    assert_eq!(2.0, settings.spawn.delay);
+
+    spawn.insert("delay", Dictionary::new().into_shared());
+    dictionary.insert("spawn", spawn.duplicate().into_shared());
+    assert_eq!(
+        Err(FromVariantError::InvalidField {
+            field_name: "spawn",
+            error: Box::new(FromVariantError::InvalidField {
+                field_name: "delay",
+                error: Box::new(FromVariantError::InvalidVariantType {
+                    variant_type: VariantType::Dictionary,
+                    expected: VariantType::F64,
+                }),
+            }),
+        }),
+        Settings::from_variant(&dictionary.duplicate().into_shared().to_variant())
+    );
+
+    spawn.insert("delay", 2);
+    spawn.insert("position", "1, 2, 3");
+    dictionary.insert("spawn", spawn.into_shared());
+    assert_eq!(
+        Err(FromVariantError::InvalidField {
+            field_name: "spawn",
+            error: Box::new(FromVariantError::InvalidField {
+                field_name: "position",
+                error: Box::new(FromVariantError::InvalidVariantType {
+                    variant_type: VariantType::GodotString,
+                    expected: VariantType::Vector3,
+                }),
+            }),
+        }),
+        Settings::from_variant(&dictionary.into_shared().to_variant())
+    );
+}}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
 crate::godot_itest! { test_derive_owned_to_variant {
     #[derive(OwnedToVariant)]
     struct ToVar {
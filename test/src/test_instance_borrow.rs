@@ -0,0 +1,85 @@
+use gdnative::export::user_data::{LocalCellError, MutexData, RwLockData};
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_instance_borrow_local_cell();
+    status &= test_instance_borrow_locks();
+
+    status
+}
+
+pub(crate) fn register(handle: InitHandle) {
+    handle.add_class::<Counter>();
+    handle.add_class::<MutexCounter>();
+    handle.add_class::<RwLockCounter>();
+}
+
+#[derive(NativeClass, Default)]
+#[inherit(Reference)]
+#[no_constructor]
+struct Counter {
+    value: i64,
+}
+
+#[methods]
+impl Counter {}
+
+#[derive(NativeClass, Default)]
+#[inherit(Reference)]
+#[user_data(MutexData<Self>)]
+#[no_constructor]
+struct MutexCounter {
+    value: i64,
+}
+
+#[methods]
+impl MutexCounter {}
+
+#[derive(NativeClass, Default)]
+#[inherit(Reference)]
+#[user_data(RwLockData<Self>)]
+#[no_constructor]
+struct RwLockCounter {
+    value: i64,
+}
+
+#[methods]
+impl RwLockCounter {}
+
+crate::godot_itest! { test_instance_borrow_local_cell {
+    let instance = Counter::default().emplace().into_shared();
+
+    {
+        let mut counter = instance.borrow_mut().expect("should be able to borrow mutably");
+        counter.value += 1;
+        assert_eq!(instance.base().get_instance_id(), counter.base().get_instance_id());
+
+        assert!(matches!(instance.borrow(), Err(LocalCellError::BorrowFailed)));
+    }
+
+    {
+        let a = instance.borrow().expect("should be able to borrow");
+        let b = instance.borrow().expect("should be able to borrow twice");
+        assert_eq!(1, a.value);
+        assert_eq!(1, b.value);
+        assert!(instance.borrow_mut().is_err());
+    }
+
+    let instance = unsafe { instance.assume_safe() };
+    instance.borrow_mut().unwrap().value += 1;
+    assert_eq!(2, instance.borrow().unwrap().value);
+}}
+
+crate::godot_itest! { test_instance_borrow_locks {
+    let mutex = MutexCounter::default().emplace().into_shared();
+    mutex.borrow_mut().unwrap().value = 42;
+    assert_eq!(42, mutex.borrow().unwrap().value);
+
+    let rw_lock = RwLockCounter::default().emplace().into_shared();
+    rw_lock.borrow_mut().unwrap().value = 42;
+    let a = rw_lock.borrow().unwrap();
+    let b = rw_lock.borrow().unwrap();
+    assert_eq!(84, a.value + b.value);
+}}
@@ -0,0 +1,118 @@
+use gdnative::export::user_data::{
+    lock_contention, reset_lock_contention, DeadlockPolicy, LockOptions, MutexData,
+};
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_lock_diagnostics_reentrant();
+    status &= test_lock_diagnostics_disabled();
+
+    status
+}
+
+pub(crate) fn register(handle: InitHandle) {
+    handle.add_class::<DiagnosedLock>();
+    handle.add_class::<UndiagnosedLock>();
+}
+
+struct PessimisticDiagnosed;
+
+impl LockOptions for PessimisticDiagnosed {
+    const DEADLOCK_POLICY: DeadlockPolicy = DeadlockPolicy::Pessimistic;
+    const DIAGNOSTICS: bool = true;
+}
+
+struct Pessimistic;
+
+impl LockOptions for Pessimistic {
+    const DEADLOCK_POLICY: DeadlockPolicy = DeadlockPolicy::Pessimistic;
+}
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+#[user_data(MutexData<Self, PessimisticDiagnosed>)]
+#[no_constructor]
+struct DiagnosedLock;
+
+#[methods]
+impl DiagnosedLock {
+    #[method]
+    fn outer(&self, #[base] base: TRef<Reference>) -> bool {
+        unsafe { base.call("inner", &[]) }.is_nil()
+    }
+
+    #[method]
+    fn inner(&self) -> bool {
+        true
+    }
+}
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+#[user_data(MutexData<Self, Pessimistic>)]
+#[no_constructor]
+struct UndiagnosedLock;
+
+#[methods]
+impl UndiagnosedLock {
+    #[method]
+    fn outer(&self, #[base] base: TRef<Reference>) -> bool {
+        unsafe { base.call("inner", &[]) }.is_nil()
+    }
+
+    #[method]
+    fn inner(&self) -> bool {
+        true
+    }
+}
+
+crate::godot_itest! { test_lock_diagnostics_reentrant {
+    reset_lock_contention();
+
+    let instance = DiagnosedLock.emplace();
+    let base = instance.base();
+
+    assert_eq!(Some(true), unsafe { base.call("inner", &[]).to::<bool>() });
+    assert!(lock_contention().is_empty(), "uncontended locks should not be recorded");
+
+    assert_eq!(
+        Some(true),
+        unsafe { base.call("outer", &[]).to::<bool>() },
+        "re-entrant call should fail with the pessimistic policy",
+    );
+
+    let stats = lock_contention();
+    assert_eq!(1, stats.len());
+    let stats = &stats[0];
+    assert_eq!("DiagnosedLock", stats.class_name);
+    assert_eq!(1, stats.contended);
+    assert_eq!(1, stats.failed);
+
+    let report = stats.last_failure.as_deref().expect("failure should be reported");
+    let (waiter, holder) = report
+        .split_once("\n  holder: ")
+        .expect("report should name the holder");
+    assert!(waiter.contains("waiter: write access from method"), "{report}");
+    assert!(waiter.contains("inner"), "{report}");
+    assert!(holder.contains("write access from method"), "{report}");
+    assert!(holder.contains("outer"), "{report}");
+
+    reset_lock_contention();
+    assert!(lock_contention().is_empty());
+}}
+
+crate::godot_itest! { test_lock_diagnostics_disabled {
+    reset_lock_contention();
+
+    let instance = UndiagnosedLock.emplace();
+    assert_eq!(
+        Some(true),
+        unsafe { instance.base().call("outer", &[]).to::<bool>() },
+    );
+    assert!(
+        lock_contention().is_empty(),
+        "contention should only be recorded with diagnostics enabled",
+    );
+}}
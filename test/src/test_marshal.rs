@@ -0,0 +1,180 @@
+use gdnative::api::{Resource, StreamPeerBuffer};
+use gdnative::core_types::marshal::{self, DecodeError, Value};
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_marshal_matches_godot();
+    status &= test_marshal_objects();
+
+    status
+}
+
+pub(crate) fn register(_handle: InitHandle) {}
+
+/// Encodes a value with Godot, without the length prefix added by `put_var`.
+fn godot_encode(variant: Variant, full_objects: bool) -> Vec<u8> {
+    let buffer = StreamPeerBuffer::new();
+    buffer.put_var(variant, full_objects);
+    let data = buffer.data_array();
+    let bytes = data.read();
+    bytes[4..].to_vec()
+}
+
+/// Decodes a value with Godot, adding the length prefix expected by `get_var`.
+fn godot_decode(bytes: &[u8], allow_objects: bool) -> Variant {
+    let buffer = StreamPeerBuffer::new();
+    buffer.put_32(bytes.len() as i64);
+    buffer
+        .put_data(PoolArray::from_slice(bytes))
+        .expect("should write data");
+    buffer.seek(0);
+    buffer.get_var(allow_objects)
+}
+
+crate::godot_itest! { test_marshal_matches_godot {
+    let dictionary = Dictionary::new();
+    dictionary.insert("a", 1);
+    dictionary.insert(2, Vector2::new(1.0, 2.0));
+
+    let array = VariantArray::new();
+    array.push(1);
+    array.push("a");
+    array.push(Variant::nil());
+
+    let cases = [
+        (Variant::nil(), Value::Nil),
+        (true.to_variant(), Value::Bool(true)),
+        (42.to_variant(), Value::I64(42)),
+        ((-2).to_variant(), Value::I64(-2)),
+        ((1i64 << 40).to_variant(), Value::I64(1 << 40)),
+        (1.5.to_variant(), Value::F64(1.5)),
+        (0.1.to_variant(), Value::F64(0.1)),
+        ("héllo".to_variant(), Value::GodotString("héllo".into())),
+        (
+            Vector2::new(1.0, 2.0).to_variant(),
+            Value::Vector2(Vector2::new(1.0, 2.0)),
+        ),
+        (
+            Rect2::new(Vector2::new(0.0, 1.0), Vector2::new(2.0, 3.0)).to_variant(),
+            Value::Rect2(Rect2::new(Vector2::new(0.0, 1.0), Vector2::new(2.0, 3.0))),
+        ),
+        (
+            Vector3::new(1.0, 2.0, 3.0).to_variant(),
+            Value::Vector3(Vector3::new(1.0, 2.0, 3.0)),
+        ),
+        (
+            Transform2D::IDENTITY.translated(Vector2::new(2.0, 3.0)).to_variant(),
+            Value::Transform2D(Transform2D::IDENTITY.translated(Vector2::new(2.0, 3.0))),
+        ),
+        (
+            Plane::new(Vector3::UP, 2.0).to_variant(),
+            Value::Plane(Plane::new(Vector3::UP, 2.0)),
+        ),
+        (
+            Quat::new(0.0, 0.0, 0.0, 1.0).to_variant(),
+            Value::Quat(Quat::new(0.0, 0.0, 0.0, 1.0)),
+        ),
+        (
+            Aabb::new(Vector3::ZERO, Vector3::new(1.0, 2.0, 3.0)).to_variant(),
+            Value::Aabb(Aabb::new(Vector3::ZERO, Vector3::new(1.0, 2.0, 3.0))),
+        ),
+        (
+            Basis::from_diagonal(Vector3::new(1.0, 2.0, 3.0)).to_variant(),
+            Value::Basis(Basis::from_diagonal(Vector3::new(1.0, 2.0, 3.0))),
+        ),
+        (
+            Transform::IDENTITY.translated(Vector3::new(1.0, 2.0, 3.0)).to_variant(),
+            Value::Transform(Transform::IDENTITY.translated(Vector3::new(1.0, 2.0, 3.0))),
+        ),
+        (
+            Color::from_rgba(1.0, 0.5, 0.0, 1.0).to_variant(),
+            Value::Color(Color::from_rgba(1.0, 0.5, 0.0, 1.0)),
+        ),
+        (
+            NodePath::from_str("/root/Player:position:x").to_variant(),
+            Value::NodePath("/root/Player:position:x".into()),
+        ),
+        (
+            dictionary.into_shared().to_variant(),
+            Value::Dictionary(vec![
+                (Value::GodotString("a".into()), Value::I64(1)),
+                (Value::I64(2), Value::Vector2(Vector2::new(1.0, 2.0))),
+            ]),
+        ),
+        (
+            array.into_shared().to_variant(),
+            Value::VariantArray(vec![
+                Value::I64(1),
+                Value::GodotString("a".into()),
+                Value::Nil,
+            ]),
+        ),
+        (
+            PoolArray::from_slice(&[1u8, 2, 3]).to_variant(),
+            Value::ByteArray(vec![1, 2, 3]),
+        ),
+        (
+            PoolArray::from_slice(&[1i32, -1]).to_variant(),
+            Value::Int32Array(vec![1, -1]),
+        ),
+        (
+            PoolArray::from_slice(&[1.0f32, 2.5]).to_variant(),
+            Value::Float32Array(vec![1.0, 2.5]),
+        ),
+        (
+            PoolArray::from_slice(&[GodotString::from("a"), GodotString::from("bcd")])
+                .to_variant(),
+            Value::StringArray(vec!["a".into(), "bcd".into()]),
+        ),
+        (
+            PoolArray::from_slice(&[Vector2::new(1.0, 2.0)]).to_variant(),
+            Value::Vector2Array(vec![Vector2::new(1.0, 2.0)]),
+        ),
+        (
+            PoolArray::from_slice(&[Vector3::new(1.0, 2.0, 3.0)]).to_variant(),
+            Value::Vector3Array(vec![Vector3::new(1.0, 2.0, 3.0)]),
+        ),
+        (
+            PoolArray::from_slice(&[Color::from_rgba(0.0, 0.5, 1.0, 1.0)]).to_variant(),
+            Value::ColorArray(vec![Color::from_rgba(0.0, 0.5, 1.0, 1.0)]),
+        ),
+    ];
+
+    for (variant, value) in cases {
+        let bytes = godot_encode(variant, false);
+        assert_eq!(bytes, marshal::encode(&value), "{value:?}");
+        assert_eq!(Ok(value.clone()), marshal::decode(&bytes, false));
+
+        let decoded = godot_decode(&bytes, false);
+        assert_eq!(bytes, godot_encode(decoded, false), "{value:?}");
+    }
+}}
+
+crate::godot_itest! { test_marshal_objects {
+    let reference = Reference::new().into_shared();
+    let id = unsafe { reference.assume_safe() }.get_instance_id();
+    let bytes = godot_encode(reference.to_variant(), false);
+
+    assert_eq!(Ok(Value::ObjectId(id as u64)), marshal::decode(&bytes, false));
+    assert_eq!(bytes, marshal::encode(&Value::ObjectId(id as u64)));
+
+    let resource = Resource::new();
+    resource.set_name("foo");
+    let bytes = godot_encode(resource.into_shared().to_variant(), true);
+
+    assert_eq!(Err(DecodeError::ObjectsNotAllowed), marshal::decode(&bytes, false));
+    let object = match marshal::decode(&bytes, true) {
+        Ok(Value::Object(Some(object))) => object,
+        other => panic!("should be an object, got {other:?}"),
+    };
+    assert_eq!("Resource", object.class);
+    assert!(object
+        .properties
+        .contains(&("resource_name".into(), Value::GodotString("foo".into()))));
+    assert_eq!(bytes, marshal::encode(&Value::Object(Some(object))));
+
+    let bytes = marshal::encode(&Value::Object(None));
+    assert_eq!(bytes, godot_encode(godot_decode(&bytes, true), true));
+}}
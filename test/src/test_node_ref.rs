@@ -0,0 +1,186 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use gdnative::export::Varargs;
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_node_ref_resolved_before_ready();
+    status &= test_node_ref_without_ready();
+    status &= test_node_ref_missing();
+    status &= test_node_ref_register_with_ready();
+
+    status
+}
+
+pub(crate) fn register(handle: InitHandle) {
+    handle.add_class::<NodeRefHud>();
+    handle.add_class::<NodeRefNoReady>();
+    handle.add_class::<NodeRefManualReady>();
+}
+
+#[derive(NativeClass)]
+#[inherit(Node)]
+#[no_constructor]
+struct NodeRefHud {
+    #[node("Score/Label")]
+    score: NodeRef<Label>,
+    #[node("Timer")]
+    timer: NodeRef<Timer>,
+    resolved_in_ready: AtomicBool,
+}
+
+#[methods]
+impl NodeRefHud {
+    #[method]
+    fn _ready(&self) {
+        let resolved = self.score.is_resolved() && self.timer.is_resolved();
+        self.resolved_in_ready.store(resolved, Ordering::Release);
+    }
+}
+
+#[derive(NativeClass)]
+#[inherit(Node)]
+#[no_constructor]
+struct NodeRefNoReady {
+    #[node("Label")]
+    label: NodeRef<Label>,
+}
+
+#[methods]
+impl NodeRefNoReady {}
+
+#[derive(NativeClass)]
+#[inherit(Node)]
+#[no_constructor]
+#[register_with(register_manual_ready)]
+struct NodeRefManualReady {
+    #[node("Label")]
+    label: NodeRef<Label>,
+    resolved_in_ready: AtomicBool,
+}
+
+#[methods]
+impl NodeRefManualReady {}
+
+#[derive(Clone, Copy, Default)]
+struct ManualReady;
+
+impl Method<NodeRefManualReady> for ManualReady {
+    fn call(&self, this: TInstance<'_, NodeRefManualReady>, _args: Varargs<'_>) -> Variant {
+        this.map(|node, _| {
+            let resolved = node.label.is_resolved();
+            node.resolved_in_ready.store(resolved, Ordering::Release);
+        })
+        .unwrap();
+        Variant::nil()
+    }
+}
+
+fn register_manual_ready(builder: &ClassBuilder<NodeRefManualReady>) {
+    builder.method("_ready", ManualReady).done_stateless();
+}
+
+fn build_hud_children(base: &Node) {
+    let score = Node::new();
+    score.set_name("Score");
+    let label = Label::new();
+    label.set_name("Label");
+    score.add_child(label, false);
+    base.add_child(score, false);
+
+    let timer = Timer::new();
+    timer.set_name("Timer");
+    base.add_child(timer, false);
+}
+
+crate::godot_itest! { test_node_ref_resolved_before_ready {
+    let hud = NodeRefHud {
+        score: NodeRef::new(),
+        timer: NodeRef::new(),
+        resolved_in_ready: AtomicBool::new(false),
+    }
+    .emplace();
+
+    build_hud_children(hud.base());
+    assert!(hud.map(|hud, _| !hud.score.is_resolved()).unwrap());
+
+    unsafe { hud.base().call("_ready", &[]) };
+
+    hud.map(|hud, base| {
+        assert!(hud.resolved_in_ready.load(Ordering::Acquire), "fields should be resolved before `_ready`");
+
+        let label = base.get_node("Score/Label").unwrap();
+        assert_eq!(
+            unsafe { label.assume_safe() }.get_instance_id(),
+            hud.score.get().get_instance_id(),
+        );
+        assert_eq!(hud.timer.get().name().to_string(), "Timer");
+    })
+    .unwrap();
+
+    hud.into_base().free();
+}}
+
+crate::godot_itest! { test_node_ref_without_ready {
+    let node = NodeRefNoReady { label: NodeRef::new() }.emplace();
+
+    let label = Label::new();
+    label.set_name("Label");
+    node.base().add_child(label, false);
+
+    unsafe { node.base().call("_ready", &[]) };
+
+    assert!(node.map(|node, _| node.label.is_resolved()).unwrap());
+
+    node.into_base().free();
+}}
+
+crate::godot_itest! { test_node_ref_missing {
+    let node = NodeRefNoReady { label: NodeRef::new() }.emplace();
+
+    // Wrong class at the path
+    let timer = Timer::new();
+    timer.set_name("Label");
+    node.base().add_child(timer, false);
+
+    unsafe { node.base().call("_ready", &[]) };
+
+    node.map(|node, _| {
+        assert!(!node.label.is_resolved());
+        assert!(node.label.try_get().is_none());
+    })
+    .unwrap();
+
+    node.into_base().free();
+}}
+
+crate::godot_itest! { test_node_ref_register_with_ready {
+    let node = NodeRefManualReady {
+        label: NodeRef::new(),
+        resolved_in_ready: AtomicBool::new(false),
+    }
+    .emplace();
+
+    let label = Label::new();
+    label.set_name("Label");
+    node.base().add_child(label, false);
+
+    unsafe { node.base().call("_ready", &[]) };
+
+    node.map(|node, _| {
+        assert!(
+            node.resolved_in_ready.load(Ordering::Acquire),
+            "fields should be resolved before a `_ready` registered with `register_with`",
+        );
+    })
+    .unwrap();
+
+    // Freed nodes are not accessible anymore
+    let label = node.map(|node, _| node.label.get().claim()).unwrap();
+    unsafe { label.assume_unique().free() };
+    assert!(node.map(|node, _| node.label.try_get().is_none()).unwrap());
+
+    node.into_base().free();
+}}
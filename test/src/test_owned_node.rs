@@ -0,0 +1,47 @@
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_owned_node_frees_on_drop();
+    status &= test_owned_node_into_inner();
+    status &= test_owned_node_add_owned_child();
+
+    status
+}
+
+crate::godot_itest! { test_owned_node_frees_on_drop {
+    let node = OwnedNode::new(Node::new());
+    let weak = node.as_ref().downgrade();
+    assert!(weak.is_alive());
+
+    drop(node);
+    assert!(!weak.is_alive(), "dropped OwnedNode should free the node");
+}}
+
+crate::godot_itest! { test_owned_node_into_inner {
+    let node = OwnedNode::from(Node2D::new());
+    let weak = node.as_ref().downgrade();
+
+    let node = node.into_inner();
+    assert!(weak.is_alive(), "into_inner should not free the node");
+
+    node.free();
+    assert!(!weak.is_alive());
+}}
+
+crate::godot_itest! { test_owned_node_add_owned_child {
+    let parent = Node::new();
+    let child = OwnedNode::new(Node2D::new());
+    child.set_name("owned");
+    let weak = child.as_ref().downgrade();
+
+    parent.add_owned_child(child);
+    assert!(weak.is_alive(), "added child should not be freed");
+
+    let found = parent.get_node("owned").expect("child should be found");
+    assert_eq!(weak.instance_id(), unsafe { found.assume_safe() }.get_instance_id());
+
+    parent.free();
+    assert!(!weak.is_alive(), "child should be freed with its parent");
+}}
@@ -0,0 +1,74 @@
+use std::sync::{Arc, Mutex};
+
+use gdnative::init::{PanicPolicy, PanicReport};
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_panic_handler_report();
+
+    status
+}
+
+pub(crate) fn register(handle: InitHandle) {
+    handle.add_class::<Panicker>();
+}
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+struct Panicker;
+
+#[methods]
+impl Panicker {
+    fn new(_base: &Reference) -> Self {
+        Panicker
+    }
+
+    #[method]
+    fn explode(&self) -> i64 {
+        panic!("explode was called");
+    }
+}
+
+#[derive(Default)]
+struct Captured {
+    context: String,
+    class_name: Option<String>,
+    message: Option<String>,
+    location: Option<String>,
+    has_backtrace: bool,
+}
+
+crate::godot_itest! { test_panic_handler_report {
+    let captured = Arc::new(Mutex::new(None));
+
+    {
+        let captured = Arc::clone(&captured);
+        gdnative::init::set_panic_handler(move |report: &PanicReport<'_>| {
+            *captured.lock().unwrap() = Some(Captured {
+                context: report.context().to_owned(),
+                class_name: report.class_name().map(ToOwned::to_owned),
+                message: report.message().map(ToOwned::to_owned),
+                location: report.location().map(ToOwned::to_owned),
+                has_backtrace: report.backtrace().is_some(),
+            });
+        });
+    }
+    gdnative::init::set_panic_policy(PanicPolicy::LogBacktrace);
+    assert_eq!(PanicPolicy::LogBacktrace, gdnative::init::panic_policy());
+
+    let base = Panicker::new_instance().into_base();
+    let result = unsafe { base.call("explode", &[]) };
+    assert!(result.is_nil());
+
+    gdnative::init::set_panic_policy(PanicPolicy::default());
+    gdnative::init::clear_panic_handler();
+
+    let captured = captured.lock().unwrap().take().expect("panic handler should be called");
+    assert_eq!("method", captured.context);
+    assert_eq!(Some("Panicker"), captured.class_name.as_deref());
+    assert_eq!(Some("explode was called"), captured.message.as_deref());
+    assert!(captured.location.unwrap().contains("test_panic_policy.rs"));
+    assert!(captured.has_backtrace);
+}}
@@ -0,0 +1,70 @@
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_reentrant_mut_call_is_queued();
+    status &= test_reentrant_shared_call();
+
+    status
+}
+
+pub(crate) fn register(handle: InitHandle) {
+    handle.add_class::<Reentrant>();
+}
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+#[user_data(reentrant)]
+struct Reentrant {
+    log: Vec<String>,
+}
+
+#[methods]
+impl Reentrant {
+    fn new(_base: &Reference) -> Self {
+        Reentrant { log: Vec::new() }
+    }
+
+    #[method]
+    fn outer(&mut self, #[base] base: TRef<Reference>) {
+        self.log.push("outer begin".into());
+        let ret = unsafe { base.call("push", &["inner".to_variant()]) };
+        assert!(ret.is_nil(), "re-entrant call should be queued");
+        self.log.push("outer end".into());
+    }
+
+    #[method]
+    fn push(&mut self, entry: String) -> i64 {
+        self.log.push(entry);
+        self.log.len() as i64
+    }
+
+    #[method]
+    fn outer_shared(&self, #[base] base: TRef<Reference>) -> i64 {
+        unsafe { base.call("len", &[]).to().unwrap() }
+    }
+
+    #[method]
+    fn len(&self) -> i64 {
+        self.log.len() as i64
+    }
+}
+
+crate::godot_itest! { test_reentrant_mut_call_is_queued {
+    let instance = Reentrant::new_instance();
+    let base = instance.base();
+
+    unsafe { base.call("outer", &[]) };
+
+    let log = instance.map(|s, _| s.log.clone()).unwrap();
+    assert_eq!(log, ["outer begin", "outer end", "inner"]);
+}}
+
+crate::godot_itest! { test_reentrant_shared_call {
+    let instance = Reentrant::new_instance();
+    let base = instance.base();
+
+    assert_eq!(Some(1), unsafe { base.call("push", &["a".to_variant()]).to::<i64>() });
+    assert_eq!(Some(1), unsafe { base.call("outer_shared", &[]).to::<i64>() });
+}}
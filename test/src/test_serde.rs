@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+
+use gdnative::core_types::JsonMode;
 use gdnative::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -18,6 +21,9 @@ pub(crate) fn run_tests() -> bool {
     status &= test_yaml();
     status &= test_msgpack();
     status &= test_bincode();
+    status &= test_json_value_tagged();
+    status &= test_json_value_plain();
+    status &= test_variant_format();
 
     status
 }
@@ -209,3 +215,100 @@ crate::godot_itest! { test_bincode {
         Foo::from_variant(&Variant::from(&disp)).expect("Foo from Dispatch from bincode");
     assert_eq!(foo, result);
 }}
+
+crate::godot_itest! { test_json_value_tagged {
+    let foo = Foo::new();
+
+    let json = foo.to_variant().to_json_value(JsonMode::Tagged).expect("Foo to JSON value");
+    let variant = Variant::from_json_value(&json, JsonMode::Tagged).expect("Foo from JSON value");
+    let result = Foo::from_variant(&variant).expect("Foo from Variant from JSON value");
+    assert_eq!(foo, result);
+
+    let int = i64::MAX.to_variant();
+    let json = int.to_json_value(JsonMode::Tagged).expect("int to JSON value");
+    assert_eq!(serde_json::json!({ "I64": i64::MAX }), json);
+    assert_eq!(
+        int,
+        Variant::from_json_value(&json, JsonMode::Tagged).expect("int from JSON value")
+    );
+
+    let object = Reference::new().into_shared().to_variant();
+    assert!(object.to_json_value(JsonMode::Tagged).is_err());
+    assert!(Variant::from_json_value(&serde_json::json!({ "Foo": 1 }), JsonMode::Tagged).is_err());
+}}
+
+crate::godot_itest! { test_json_value_plain {
+    let dictionary = Dictionary::new();
+    dictionary.insert("int", 1);
+    dictionary.insert("float", 1.5);
+    dictionary.insert("vec2", Vector2::new(1.0, 2.0));
+    dictionary.insert(2, PoolArray::from_slice(&[1u8, 2]));
+    dictionary.insert("path", NodePath::from_str("/root"));
+    let variant = dictionary.into_shared().to_variant();
+
+    let json = variant.to_json_value(JsonMode::Plain).expect("Dictionary to JSON value");
+    assert_eq!(
+        serde_json::json!({
+            "int": 1,
+            "float": 1.5,
+            "vec2": { "x": 1.0, "y": 2.0 },
+            "2": [1, 2],
+            "path": "/root",
+        }),
+        json
+    );
+
+    let variant = Variant::from_json_value(&json, JsonMode::Plain).expect("Dictionary from JSON value");
+    let dictionary = variant.to::<Dictionary>().expect("should be a Dictionary");
+    assert_eq!(Some(1.to_variant()), dictionary.get("int"));
+    assert_eq!(Some(1.5.to_variant()), dictionary.get("float"));
+    assert_eq!(Some("/root".to_variant()), dictionary.get("path"));
+
+    let object = Reference::new().into_shared().to_variant();
+    assert!(object.to_json_value(JsonMode::Plain).is_err());
+}}
+
+crate::godot_itest! { test_variant_format {
+    use gdnative::serde::{from_variant, to_variant};
+
+    let foo = Foo::new();
+
+    let variant = to_variant(&foo).expect("Foo to Variant");
+    let result = from_variant::<Foo>(&variant).expect("Foo from Variant");
+    assert_eq!(foo, result);
+
+    // Variants produced by `ToVariant` can be deserialized as well
+    let result = from_variant::<Foo>(&foo.to_variant()).expect("Foo from ToVariant");
+    assert_eq!(foo, result);
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Pair(i8, String),
+        Rect { w: u32, h: u32 },
+    }
+
+    let shapes = vec![
+        Shape::Empty,
+        Shape::Circle(1.5),
+        Shape::Pair(-1, "a".into()),
+        Shape::Rect { w: 2, h: 3 },
+    ];
+    let variant = to_variant(&shapes).expect("shapes to Variant");
+    let array = variant.to::<VariantArray>().expect("should be a VariantArray");
+    assert_eq!("Empty".to_variant(), array.get(0));
+    let circle = array.get(1).to::<Dictionary>().expect("should be a Dictionary");
+    assert_eq!(Some(1.5.to_variant()), circle.get("Circle"));
+    assert_eq!(shapes, from_variant::<Vec<Shape>>(&variant).expect("shapes from Variant"));
+
+    let mut map = HashMap::new();
+    map.insert("some".to_string(), Some(1u64));
+    map.insert("none".to_string(), None);
+    let variant = to_variant(&map).expect("map to Variant");
+    assert_eq!(map, from_variant(&variant).expect("map from Variant"));
+
+    assert!(to_variant(&u64::MAX).is_err());
+    assert!(from_variant::<Foo>(&1.to_variant()).is_err());
+    assert!(from_variant::<u8>(&256.to_variant()).is_err());
+}}
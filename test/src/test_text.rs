@@ -0,0 +1,102 @@
+use gdnative::api::Expression;
+use gdnative::core_types::text::{self, ObjectValue, Value};
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_text_matches_godot();
+    status &= test_text_value_conversion();
+
+    status
+}
+
+pub(crate) fn register(_handle: InitHandle) {}
+
+/// Calls a GDScript built-in function with a single argument through `Expression`.
+fn call_builtin(function: &str, argument: Variant) -> Variant {
+    let expression = Expression::new();
+    expression
+        .parse(
+            format!("{function}(argument)"),
+            PoolArray::from_slice(&[GodotString::from("argument")]),
+        )
+        .expect("expression should parse");
+
+    let inputs = VariantArray::new();
+    inputs.push(argument);
+    expression.execute(inputs.into_shared(), Null::null(), true)
+}
+
+crate::godot_itest! { test_text_matches_godot {
+    let values = [
+        Value::Nil,
+        Value::Bool(true),
+        Value::I64(-42),
+        Value::F64(1.5),
+        Value::F64(2.0),
+        Value::GodotString("héllo \"world\"\n".into()),
+        Value::Vector2(Vector2::new(1.0, 2.5)),
+        Value::Rect2(Rect2::new(Vector2::new(0.0, 1.0), Vector2::new(2.0, 3.0))),
+        Value::Vector3(Vector3::new(1.0, 2.0, 3.0)),
+        Value::Transform2D(Transform2D::IDENTITY.translated(Vector2::new(2.0, 3.0))),
+        Value::Plane(Plane::new(Vector3::UP, 2.0)),
+        Value::Quat(Quat::new(0.0, 0.0, 0.0, 1.0)),
+        Value::Aabb(Aabb::new(Vector3::ZERO, Vector3::new(1.0, 2.0, 3.0))),
+        Value::Basis(Basis::from_diagonal(Vector3::new(1.0, 2.0, 3.0))),
+        Value::Transform(Transform::IDENTITY.translated(Vector3::new(1.0, 2.0, 3.0))),
+        Value::Color(Color::from_rgba(1.0, 0.5, 0.0, 1.0)),
+        Value::NodePath("/root/Player:position:x".into()),
+        Value::Dictionary(vec![
+            (Value::I64(2), Value::Vector2(Vector2::new(1.0, 2.0))),
+            (Value::GodotString("a".into()), Value::I64(1)),
+        ]),
+        Value::VariantArray(vec![
+            Value::I64(1),
+            Value::GodotString("a".into()),
+            Value::Nil,
+        ]),
+        Value::ByteArray(vec![1, 2, 3]),
+        Value::Int32Array(vec![1, -1]),
+        Value::Float32Array(vec![1.0, 2.5]),
+        Value::StringArray(vec!["a".into(), "b\n\"c\"".into()]),
+        Value::Vector2Array(vec![Vector2::new(1.0, 2.0)]),
+        Value::Vector3Array(vec![Vector3::new(1.0, 2.0, 3.0)]),
+        Value::ColorArray(vec![Color::from_rgba(0.0, 0.5, 1.0, 1.0)]),
+    ];
+
+    for value in values {
+        let godot_text = call_builtin("var2str", value.to_variant())
+            .to::<String>()
+            .expect("var2str should return a string");
+        assert_eq!(godot_text, text::to_string(&value), "{value:?}");
+        assert_eq!(Ok(value.clone()), text::parse(&godot_text));
+
+        let parsed = call_builtin("str2var", godot_text.to_variant());
+        assert_eq!(Ok(value), Value::from_variant(&parsed));
+    }
+}}
+
+crate::godot_itest! { test_text_value_conversion {
+    let reference = Reference::new().into_shared();
+    let id = unsafe { reference.assume_safe() }.get_instance_id();
+    assert_eq!(
+        Ok(Value::ObjectId(id as u64)),
+        Value::from_variant(&reference.to_variant())
+    );
+
+    let object = Value::Object(Some(Box::new(ObjectValue {
+        class: "Reference".into(),
+        properties: vec![],
+    })));
+    assert!(object.to_variant().is_nil());
+    assert_eq!(VariantType::Rid, Value::Rid.to_variant().get_type());
+
+    let resolved = Value::ObjectId(id as u64).to_variant();
+    assert_eq!(reference.to_variant(), resolved);
+    assert!(Value::ObjectId(0).to_variant().is_nil());
+
+    drop(resolved);
+    drop(reference);
+    assert!(Value::ObjectId(id as u64).to_variant().is_nil());
+}}
@@ -0,0 +1,94 @@
+use gdnative::init::{self, MainThreadCheck};
+#[cfg(feature = "main-thread-checks")]
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_main_thread_recorded();
+    status &= test_main_thread_check_policy();
+
+    #[cfg(feature = "main-thread-checks")]
+    {
+        status &= test_main_thread_check_panic();
+        status &= test_main_thread_check_warn();
+    }
+
+    status
+}
+
+crate::godot_itest! { test_main_thread_recorded {
+    let main_id = init::main_thread_id().expect("main thread should be recorded");
+    assert_eq!(std::thread::current().id(), main_id);
+    assert!(init::is_main_thread());
+
+    let on_other_thread = std::thread::spawn(init::is_main_thread)
+        .join()
+        .expect("thread should not panic");
+    assert!(!on_other_thread);
+}}
+
+crate::godot_itest! { test_main_thread_check_policy {
+    assert_eq!(MainThreadCheck::Panic, init::main_thread_check());
+
+    init::set_main_thread_check(MainThreadCheck::Warn);
+    assert_eq!(MainThreadCheck::Warn, init::main_thread_check());
+
+    init::set_main_thread_check(MainThreadCheck::Panic);
+}}
+
+/// Adds a new node to the root of the scene tree, calls `f` with it, and frees it again.
+#[cfg(feature = "main-thread-checks")]
+fn with_node_in_tree(f: impl FnOnce(Ref<Node, Shared>)) {
+    let root = unsafe {
+        Engine::godot_singleton()
+            .get_main_loop()
+            .and_then(|main_loop| main_loop.assume_safe().cast::<SceneTree>())
+            .and_then(|tree| tree.root())
+            .map(|root| root.assume_safe())
+            .expect("the main loop should be a SceneTree with a root")
+    };
+
+    let node = Node::new();
+    node.set_name("ThreadAffinityTarget");
+    let node = node.into_shared();
+    root.add_child(node.clone(), false);
+
+    f(node.clone());
+
+    root.remove_child(node.clone());
+    unsafe { node.assume_unique() }.free();
+}
+
+#[cfg(feature = "main-thread-checks")]
+crate::godot_itest! { test_main_thread_check_panic {
+    with_node_in_tree(|node| {
+        let payload = std::thread::spawn(move || {
+            let _ = unsafe { node.assume_safe() }.name();
+        })
+        .join()
+        .expect_err("access from another thread should panic");
+
+        let msg = payload
+            .downcast_ref::<String>()
+            .map(String::as_str)
+            .unwrap_or_default();
+        assert!(msg.contains("on a node of class Node"), "{msg}");
+        assert!(msg.contains("which is not the main thread"), "{msg}");
+        assert!(msg.contains(file!()), "panic should report the caller: {msg}");
+    });
+}}
+
+#[cfg(feature = "main-thread-checks")]
+crate::godot_itest! { test_main_thread_check_warn {
+    init::set_main_thread_check(MainThreadCheck::Warn);
+
+    with_node_in_tree(|node| {
+        let name = std::thread::spawn(move || unsafe { node.assume_safe() }.name().to_string())
+            .join()
+            .expect("access from another thread should only be logged");
+        assert_eq!("ThreadAffinityTarget", name);
+    });
+
+    init::set_main_thread_check(MainThreadCheck::Panic);
+}}
@@ -0,0 +1,84 @@
+use gdnative::object::{WeakInstance, WeakRef};
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_weak_ref_manually_managed();
+    status &= test_weak_ref_ref_counted();
+    status &= test_weak_instance();
+
+    status
+}
+
+pub(crate) fn register(handle: InitHandle) {
+    handle.add_class::<WeakTarget>();
+}
+
+#[derive(NativeClass)]
+#[inherit(Node)]
+struct WeakTarget;
+
+#[methods]
+impl WeakTarget {
+    fn new(_base: &Node) -> Self {
+        WeakTarget
+    }
+}
+
+crate::godot_itest! { test_weak_ref_manually_managed {
+    let node = Node::new().into_shared();
+    let node_ref = unsafe { node.assume_safe() };
+
+    let weak = node_ref.downgrade();
+    assert_eq!(node_ref.get_instance_id(), weak.instance_id());
+    assert!(weak.is_alive());
+
+    let upgraded = weak.upgrade().expect("node should be alive");
+    assert_eq!(node_ref.get_instance_id(), upgraded.get_instance_id());
+    drop(upgraded);
+
+    // Wrong class
+    let weak_spatial = WeakRef::<Spatial>::from_instance_id(weak.instance_id());
+    assert!(weak_spatial.upgrade().is_none());
+
+    unsafe { node_ref.assume_unique().free() };
+    assert!(!weak.is_alive());
+    assert!(weak.upgrade().is_none());
+}}
+
+crate::godot_itest! { test_weak_ref_ref_counted {
+    let reference = Reference::new().into_shared();
+    let weak = unsafe { reference.assume_safe() }.downgrade();
+
+    let upgraded = weak.upgrade().expect("reference should be alive");
+    drop(reference);
+    assert!(weak.is_alive(), "upgraded reference should keep the object alive");
+
+    let claimed = upgraded.claim();
+    assert!(weak.is_alive(), "claimed reference should keep the object alive");
+
+    drop(claimed);
+    assert!(weak.upgrade().is_none());
+}}
+
+crate::godot_itest! { test_weak_instance {
+    let instance = WeakTarget::new_instance().into_shared();
+    let instance = unsafe { instance.assume_safe() };
+
+    let weak: WeakInstance<WeakTarget> = instance.downgrade();
+    let upgraded = weak.upgrade().expect("instance should be alive");
+    assert_eq!(
+        instance.base().get_instance_id(),
+        upgraded.get().base().get_instance_id(),
+    );
+    drop(upgraded);
+
+    // Same object, script removed
+    instance.base().set_script(Null::null());
+    assert!(weak.base().is_alive());
+    assert!(weak.upgrade().is_none());
+
+    unsafe { instance.base().assume_unique().free() };
+    assert!(!weak.base().is_alive());
+}}